@@ -0,0 +1,201 @@
+use crate::issue::{list_issues_filtered, parse_date, Issue, IssueCrudError, IssueFilter};
+use crate::pr::{list_prs, PrCrudError, PullRequest};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use std::path::Path;
+use thiserror::Error;
+
+/// Longest content line allowed by RFC 5545, in octets
+const MAX_LINE_OCTETS: usize = 75;
+
+#[derive(Error, Debug)]
+pub enum CalendarError {
+    #[error("Issue error: {0}")]
+    IssueError(#[from] IssueCrudError),
+
+    #[error("PR error: {0}")]
+    PrError(#[from] PrCrudError),
+}
+
+/// A rendered calendar feed
+#[derive(Debug, Clone)]
+pub struct CalendarExport {
+    /// The `.ics` document (CRLF line endings)
+    pub ics: String,
+    pub event_count: u32,
+}
+
+/// Export every issue with a start or due date and every merged PR as an
+/// iCalendar feed
+pub async fn export_calendar(project_path: &Path) -> Result<CalendarExport, CalendarError> {
+    let issues = list_issues_filtered(project_path, &IssueFilter::default()).await?;
+    let prs = list_prs(project_path, None, None, None, None).await?;
+
+    let project_name = project_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "centy".to_string());
+
+    let mut events: Vec<Vec<String>> = Vec::new();
+    events.extend(issues.iter().filter_map(issue_event));
+    events.extend(prs.iter().filter_map(pr_event));
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//centy//centy-daemon//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(&project_name)),
+    ];
+    let event_count = events.len() as u32;
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.extend(event);
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let ics = lines.iter().map(|l| fold_line(l)).collect::<Vec<_>>().concat();
+
+    Ok(CalendarExport { ics, event_count })
+}
+
+/// All-day event from the start date through the due date. With only one of
+/// the two, the event covers that single day.
+fn issue_event(issue: &Issue) -> Option<Vec<String>> {
+    let day = |date: Option<&str>| date.and_then(|d| parse_date(d).ok());
+    let (start, due) = match (
+        day(issue.metadata.start_date.as_deref()),
+        day(issue.metadata.due_date.as_deref()),
+    ) {
+        (Some(start), Some(due)) if start <= due => (start, due),
+        (_, Some(due)) => (due, due),
+        (Some(start), None) => (start, start),
+        (None, None) => return None,
+    };
+
+    let mut description = format!(
+        "Status: {}\nPriority: {}",
+        issue.metadata.status, issue.metadata.priority
+    );
+    if !issue.description.is_empty() {
+        description.push_str("\n\n");
+        description.push_str(&issue.description);
+    }
+
+    Some(vec![
+        format!("UID:issue-{}@centy", issue.id),
+        format!("DTSTAMP:{}", format_timestamp(&issue.metadata.updated_at)),
+        format!("DTSTART;VALUE=DATE:{}", format_day(start)),
+        // DTEND is exclusive for all-day events
+        format!("DTEND;VALUE=DATE:{}", format_day(due + Duration::days(1))),
+        format!(
+            "SUMMARY:{}",
+            escape_text(&format!("#{} {}", issue.metadata.display_number, issue.title))
+        ),
+        format!("DESCRIPTION:{}", escape_text(&description)),
+        format!("CATEGORIES:{}", escape_text(&issue.metadata.status)),
+    ])
+}
+
+/// Event at the moment a PR was merged
+fn pr_event(pr: &PullRequest) -> Option<Vec<String>> {
+    let merged_at = DateTime::parse_from_rfc3339(&pr.metadata.merged_at).ok()?;
+    let merged_at = format_utc(merged_at.with_timezone(&Utc));
+
+    Some(vec![
+        format!("UID:pr-{}@centy", pr.id),
+        format!("DTSTAMP:{}", format_timestamp(&pr.metadata.updated_at)),
+        format!("DTSTART:{}", merged_at),
+        format!("DTEND:{}", merged_at),
+        format!(
+            "SUMMARY:{}",
+            escape_text(&format!("PR #{} merged: {}", pr.metadata.display_number, pr.title))
+        ),
+        format!(
+            "DESCRIPTION:{}",
+            escape_text(&format!(
+                "{} → {}",
+                pr.metadata.source_branch, pr.metadata.target_branch
+            ))
+        ),
+    ])
+}
+
+fn format_day(date: NaiveDate) -> String {
+    date.format("%Y%m%d").to_string()
+}
+
+fn format_utc(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Convert an RFC 3339 timestamp to iCalendar UTC form, falling back to now
+fn format_timestamp(timestamp: &str) -> String {
+    let time = DateTime::parse_from_rfc3339(timestamp)
+        .map(|t| t.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now());
+    format_utc(time)
+}
+
+/// Escape a TEXT value (RFC 5545 §3.3.11)
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Fold a content line at 75 octets and terminate it with CRLF (RFC 5545 §3.1).
+/// Continuation lines start with a space, which counts toward their length.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 4);
+    let mut line_len = 0;
+    for c in line.chars() {
+        if line_len + c.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            line_len = 1;
+        }
+        folded.push(c);
+        line_len += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(escape_text("a, b; c\\d\nnext"), "a\\, b\\; c\\\\d\\nnext");
+    }
+
+    #[test]
+    fn test_fold_line() {
+        let line = "x".repeat(160);
+        let folded = fold_line(&line);
+        let parts: Vec<_> = folded.trim_end_matches("\r\n").split("\r\n").collect();
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[0].len(), 75);
+        assert!(parts[1].starts_with(' ') && parts[1].len() == 75);
+        assert_eq!(parts.concat().replace(' ', ""), line);
+
+        // Multi-byte characters are never split
+        let folded = fold_line(&"é".repeat(80));
+        assert!(folded.split("\r\n").all(|l| l.len() <= 75));
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp("2024-03-01T10:20:30+02:00"), "20240301T082030Z");
+    }
+}
@@ -0,0 +1,10 @@
+//! iCalendar export of dated issues and PRs.
+//!
+//! Issues with a start or due date become all-day events spanning from the
+//! start date to the due date, or the single day when only one is set.
+//! Merged PRs become events at their merge time. The feed is regenerated on every request, so calendar
+//! clients subscribing to it always see the current state.
+
+mod ics;
+
+pub use ics::{export_calendar, CalendarError, CalendarExport};
@@ -1,10 +1,15 @@
-use crate::utils::get_centy_path;
+use crate::feed::{notify_change, ChangeEntity, ChangeKind};
+use crate::utils::{compute_file_hash, get_centy_path, write_atomic};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
 use thiserror::Error;
 use tokio::fs;
 
+mod workflow;
+
+pub use workflow::{TransitionRule, WorkflowConfig, WorkflowError, ANY_STATE};
+
 #[derive(Error, Debug)]
 pub enum ConfigError {
     #[error("IO error: {0}")]
@@ -12,6 +17,12 @@ pub enum ConfigError {
 
     #[error("JSON error: {0}")]
     JsonError(#[from] serde_json::Error),
+
+    #[error("Config was changed by someone else")]
+    Conflict {
+        current: Box<CentyConfig>,
+        etag: String,
+    },
 }
 
 /// Custom field definition
@@ -29,6 +40,29 @@ pub struct CustomFieldDefinition {
     pub enum_values: Vec<String>,
 }
 
+/// Label definition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelDefinition {
+    pub name: String,
+    /// Hex color (e.g., "#ef4444")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Project member who can be assigned to issues
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberDefinition {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// Unique identifier stored in issue assignees (e.g., "alice")
+    pub handle: String,
+}
+
 /// Default priority levels (3 = high/medium/low)
 fn default_priority_levels() -> u32 {
     3
@@ -48,6 +82,16 @@ fn default_state() -> String {
     "open".to_string()
 }
 
+/// Default states that count as closed
+pub fn default_closed_states() -> Vec<String> {
+    vec!["closed".to_string()]
+}
+
+/// Default number of days deleted items stay in the trash
+fn default_trash_retention_days() -> u32 {
+    30
+}
+
 /// LLM configuration for automated issue management
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -96,6 +140,36 @@ pub struct CentyConfig {
     /// LLM configuration for automated issue management
     #[serde(default)]
     pub llm: LlmConfig,
+    /// Labels that can be attached to issues. Empty = any label is allowed.
+    #[serde(default)]
+    pub labels: Vec<LabelDefinition>,
+    /// Members that can be assigned to issues. Empty = any assignee is allowed.
+    #[serde(default)]
+    pub members: Vec<MemberDefinition>,
+    /// If true, new issues without assignees are assigned to the local git user
+    #[serde(default)]
+    pub assign_git_user_by_default: bool,
+    /// States that count as closed, e.g. for child issue rollups (default: ["closed"])
+    #[serde(default = "default_closed_states")]
+    pub closed_states: Vec<String>,
+    /// If true, a parent issue cannot be closed while it has open children.
+    /// If false, closing it only produces a warning.
+    #[serde(default)]
+    pub block_close_with_open_children: bool,
+    /// Status workflow for issues (transitions, terminal states, strict mode)
+    #[serde(default)]
+    pub workflow: WorkflowConfig,
+    /// Status workflow for PRs
+    #[serde(default)]
+    pub pr_workflow: WorkflowConfig,
+    /// Closed issues untouched for this many days are moved to the archive by
+    /// `archive_issues` (default: 0 = never)
+    #[serde(default)]
+    pub archive_after_days: u32,
+    /// Days deleted items stay in the trash before being purged (default: 30).
+    /// 0 keeps them until the trash is emptied.
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
 }
 
 impl CentyConfig {
@@ -119,6 +193,15 @@ impl Default for CentyConfig {
             state_colors: HashMap::new(),
             priority_colors: HashMap::new(),
             llm: LlmConfig::default(),
+            labels: Vec::new(),
+            members: Vec::new(),
+            assign_git_user_by_default: false,
+            closed_states: default_closed_states(),
+            block_close_with_open_children: false,
+            workflow: WorkflowConfig::default(),
+            pr_workflow: WorkflowConfig::default(),
+            archive_after_days: 0,
+            trash_retention_days: default_trash_retention_days(),
         }
     }
 }
@@ -140,6 +223,40 @@ pub async fn read_config(project_path: &Path) -> Result<Option<CentyConfig>, Con
 pub async fn write_config(project_path: &Path, config: &CentyConfig) -> Result<(), ConfigError> {
     let config_path = get_centy_path(project_path).join("config.json");
     let content = serde_json::to_string_pretty(config)?;
-    fs::write(&config_path, content).await?;
+    write_atomic(&config_path, content).await?;
+    notify_change(project_path, ChangeEntity::Config, ChangeKind::Updated, "config");
     Ok(())
 }
+
+/// Version tag of the stored config: a hash of `config.json`, or empty when
+/// the project has none
+pub async fn config_etag(project_path: &Path) -> Result<String, ConfigError> {
+    let config_path = get_centy_path(project_path).join("config.json");
+    if !config_path.exists() {
+        return Ok(String::new());
+    }
+    Ok(compute_file_hash(&config_path).await?)
+}
+
+/// Write the configuration file unless it changed since `expected_etag` was
+/// read (None = no check). Returns the etag of the written config.
+/// The caller must hold the project lock.
+pub(crate) async fn write_config_if_unchanged(
+    project_path: &Path,
+    config: &CentyConfig,
+    expected_etag: Option<&str>,
+) -> Result<String, ConfigError> {
+    if let Some(expected) = expected_etag {
+        let etag = config_etag(project_path).await?;
+        if etag != expected {
+            let current = read_config(project_path).await?.unwrap_or_default();
+            return Err(ConfigError::Conflict {
+                current: Box::new(current),
+                etag,
+            });
+        }
+    }
+
+    write_config(project_path, config).await?;
+    config_etag(project_path).await
+}
@@ -0,0 +1,233 @@
+//! Status workflow rules
+//!
+//! A workflow restricts how an issue (or PR) may move between states. In
+//! lenient mode (the default) rule violations are reported as warnings; in
+//! strict mode they are rejected.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Matches any state in a `TransitionRule`
+pub const ANY_STATE: &str = "*";
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum WorkflowError {
+    #[error("Status '{0}' is not in the allowed states")]
+    UnknownStatus(String),
+
+    #[error("Status '{0}' is terminal and cannot be changed")]
+    TerminalState(String),
+
+    #[error("Transition from '{from}' to '{to}' is not allowed")]
+    TransitionNotAllowed { from: String, to: String },
+
+    #[error("Transition from '{from}' to '{to}' requires a comment")]
+    CommentRequired { from: String, to: String },
+}
+
+/// A transition between two states. `from` may be "*" to match any state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransitionRule {
+    pub from: String,
+    pub to: String,
+}
+
+impl TransitionRule {
+    fn matches(&self, from: &str, to: &str) -> bool {
+        (self.from == ANY_STATE || self.from == from) && self.to == to
+    }
+}
+
+/// Status workflow configuration
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowConfig {
+    /// If true, violations are rejected. If false, they are only warned about.
+    #[serde(default)]
+    pub strict: bool,
+    /// Allowed transitions: state → states it may move to.
+    /// States without an entry may move to any state.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub transitions: HashMap<String, Vec<String>>,
+    /// Transitions that must be accompanied by a comment
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comment_required: Vec<TransitionRule>,
+    /// States that cannot be left once entered
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub terminal_states: Vec<String>,
+}
+
+impl WorkflowConfig {
+    /// Rule violations for an initial state
+    pub fn state_violations(&self, state: &str, allowed_states: &[String]) -> Vec<WorkflowError> {
+        if allowed_states.iter().any(|s| s == state) {
+            Vec::new()
+        } else {
+            vec![WorkflowError::UnknownStatus(state.to_string())]
+        }
+    }
+
+    /// Rule violations for moving from one state to another.
+    /// Staying in the same state is always allowed.
+    pub fn transition_violations(
+        &self,
+        from: &str,
+        to: &str,
+        allowed_states: &[String],
+    ) -> Vec<WorkflowError> {
+        if from == to {
+            return Vec::new();
+        }
+
+        let mut violations = self.state_violations(to, allowed_states);
+
+        if self.terminal_states.iter().any(|s| s == from) {
+            violations.push(WorkflowError::TerminalState(from.to_string()));
+        } else if let Some(targets) = self.transitions.get(from) {
+            if !targets.iter().any(|s| s == to) {
+                violations.push(WorkflowError::TransitionNotAllowed {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                });
+            }
+        }
+
+        violations
+    }
+
+    /// Whether moving from one state to another needs a comment
+    pub fn requires_comment(&self, from: &str, to: &str) -> bool {
+        from != to && self.comment_required.iter().any(|r| r.matches(from, to))
+    }
+
+    /// Apply the workflow mode to a set of violations.
+    ///
+    /// In strict mode the first violation is returned as an error. Otherwise
+    /// each violation is returned as a warning message.
+    pub fn enforce(&self, violations: Vec<WorkflowError>) -> Result<Vec<String>, WorkflowError> {
+        if self.strict {
+            if let Some(violation) = violations.into_iter().next() {
+                return Err(violation);
+            }
+            return Ok(Vec::new());
+        }
+
+        Ok(violations.iter().map(ToString::to_string).collect())
+    }
+
+    /// Check that every state named in the workflow is an allowed state
+    pub fn validate(&self, allowed_states: &[String]) -> Result<(), String> {
+        let is_allowed = |s: &String| allowed_states.contains(s);
+
+        for (from, targets) in &self.transitions {
+            if let Some(state) = std::iter::once(from).chain(targets).find(|s| !is_allowed(s)) {
+                return Err(format!("workflow transition state '{}' must be in allowed states", state));
+            }
+        }
+        for rule in &self.comment_required {
+            if (rule.from != ANY_STATE && !is_allowed(&rule.from)) || !is_allowed(&rule.to) {
+                return Err(format!(
+                    "workflow comment rule '{}' → '{}' must use allowed states",
+                    rule.from, rule.to
+                ));
+            }
+        }
+        if let Some(state) = self.terminal_states.iter().find(|s| !is_allowed(s)) {
+            return Err(format!("workflow terminal state '{}' must be in allowed states", state));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn states() -> Vec<String> {
+        ["open", "in-progress", "closed", "wontfix"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    fn workflow() -> WorkflowConfig {
+        WorkflowConfig {
+            strict: true,
+            transitions: HashMap::from([
+                ("open".to_string(), vec!["in-progress".to_string(), "wontfix".to_string()]),
+                ("in-progress".to_string(), vec!["open".to_string(), "closed".to_string()]),
+            ]),
+            comment_required: vec![TransitionRule {
+                from: ANY_STATE.to_string(),
+                to: "wontfix".to_string(),
+            }],
+            terminal_states: vec!["wontfix".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_transition_allowed() {
+        let workflow = workflow();
+        assert!(workflow.transition_violations("open", "in-progress", &states()).is_empty());
+        assert!(workflow.transition_violations("open", "open", &states()).is_empty());
+        // No entry for "closed": anything goes
+        assert!(workflow.transition_violations("closed", "open", &states()).is_empty());
+    }
+
+    #[test]
+    fn test_transition_not_allowed() {
+        let violations = workflow().transition_violations("open", "closed", &states());
+        assert_eq!(
+            violations,
+            vec![WorkflowError::TransitionNotAllowed {
+                from: "open".to_string(),
+                to: "closed".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_terminal_and_unknown_states() {
+        let workflow = workflow();
+        assert_eq!(
+            workflow.transition_violations("wontfix", "open", &states()),
+            vec![WorkflowError::TerminalState("wontfix".to_string())]
+        );
+        assert_eq!(
+            workflow.transition_violations("closed", "done", &states()),
+            vec![WorkflowError::UnknownStatus("done".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_requires_comment() {
+        let workflow = workflow();
+        assert!(workflow.requires_comment("open", "wontfix"));
+        assert!(workflow.requires_comment("in-progress", "wontfix"));
+        assert!(!workflow.requires_comment("open", "in-progress"));
+    }
+
+    #[test]
+    fn test_enforce_strict_and_lenient() {
+        let violation = WorkflowError::TerminalState("wontfix".to_string());
+
+        let strict = workflow();
+        assert_eq!(strict.enforce(vec![violation.clone()]), Err(violation.clone()));
+
+        let lenient = WorkflowConfig::default();
+        let warnings = lenient.enforce(vec![violation]).unwrap();
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_states() {
+        assert!(workflow().validate(&states()).is_ok());
+
+        let mut workflow = workflow();
+        workflow.terminal_states.push("archived".to_string());
+        assert!(workflow.validate(&states()).is_err());
+    }
+}
@@ -1,8 +1,11 @@
+use crate::feed::{notify_change, ChangeEntity, ChangeKind};
 use crate::manifest::{
     read_manifest, write_manifest, update_manifest_timestamp, CentyManifest,
 };
+use crate::search::{index_doc, remove_doc};
+use crate::trash::{move_to_trash, RemovedLinks, TrashKind};
 use crate::template::{DocTemplateContext, TemplateEngine, TemplateError};
-use crate::utils::{get_centy_path, now_iso};
+use crate::utils::{get_centy_path, lock_project, now_iso, write_atomic};
 use std::path::Path;
 use thiserror::Error;
 use tokio::fs;
@@ -35,6 +38,9 @@ pub enum DocError {
 
     #[error("Template error: {0}")]
     TemplateError(#[from] TemplateError),
+
+    #[error("Doc '{}' was changed by someone else (last updated {})", .0.slug, .0.metadata.updated_at)]
+    Conflict(Box<Doc>),
 }
 
 /// Full doc data
@@ -94,6 +100,9 @@ pub struct UpdateDocOptions {
     pub title: Option<String>,
     pub content: Option<String>,
     pub new_slug: Option<String>,
+    /// Fail with `Conflict` unless the doc's `updated_at` still has this
+    /// value. None = overwrite whatever is stored.
+    pub expected_updated_at: Option<String>,
 }
 
 /// Result of doc update
@@ -123,6 +132,7 @@ pub async fn create_doc(
     let mut manifest = read_manifest(project_path)
         .await?
         .ok_or(DocError::NotInitialized)?;
+    let _lock = lock_project(project_path).await?;
 
     let centy_path = get_centy_path(project_path);
     let docs_path = centy_path.join("docs");
@@ -171,12 +181,17 @@ pub async fn create_doc(
     };
 
     // Write the doc file
-    fs::write(&doc_path, &doc_content).await?;
+    write_atomic(&doc_path, &doc_content).await?;
 
     // Update manifest timestamp
     update_manifest_timestamp(&mut manifest);
     write_manifest(project_path, &manifest).await?;
 
+    if let Ok(doc) = read_doc_from_disk(&doc_path, &slug).await {
+        index_doc(project_path, &doc);
+    }
+    notify_change(project_path, ChangeEntity::Doc, ChangeKind::Created, &slug);
+
     let created_file = format!(".centy/docs/{}.md", slug);
 
     Ok(CreateDocResult {
@@ -261,8 +276,16 @@ pub async fn update_doc(
         return Err(DocError::DocNotFound(slug.to_string()));
     }
 
-    // Read current doc
+    // Read current doc. Nobody may write until it is written back.
+    let _lock = lock_project(project_path).await?;
     let current = read_doc_from_disk(&doc_path, slug).await?;
+    if options
+        .expected_updated_at
+        .as_ref()
+        .is_some_and(|expected| *expected != current.metadata.updated_at)
+    {
+        return Err(DocError::Conflict(Box::new(current)));
+    }
 
     // Apply updates
     let new_title = options.title.unwrap_or(current.title);
@@ -301,12 +324,12 @@ pub async fn update_doc(
 
         // Write new file
         let new_path = docs_path.join(format!("{}.md", new_slug));
-        fs::write(&new_path, &doc_content).await?;
+        write_atomic(&new_path, &doc_content).await?;
 
         new_slug.clone()
     } else {
         // Just update the existing file
-        fs::write(&doc_path, &doc_content).await?;
+        write_atomic(&doc_path, &doc_content).await?;
 
         slug.to_string()
     };
@@ -322,6 +345,15 @@ pub async fn update_doc(
         metadata: updated_metadata,
     };
 
+    if doc.slug != slug {
+        remove_doc(project_path, slug);
+        notify_change(project_path, ChangeEntity::Doc, ChangeKind::Deleted, slug);
+        notify_change(project_path, ChangeEntity::Doc, ChangeKind::Created, &doc.slug);
+    } else {
+        notify_change(project_path, ChangeEntity::Doc, ChangeKind::Updated, slug);
+    }
+    index_doc(project_path, &doc);
+
     Ok(UpdateDocResult { doc, manifest })
 }
 
@@ -331,6 +363,7 @@ pub async fn delete_doc(project_path: &Path, slug: &str) -> Result<DeleteDocResu
     let mut manifest = read_manifest(project_path)
         .await?
         .ok_or(DocError::NotInitialized)?;
+    let _lock = lock_project(project_path).await?;
 
     let centy_path = get_centy_path(project_path);
     let doc_path = centy_path.join("docs").join(format!("{}.md", slug));
@@ -339,8 +372,20 @@ pub async fn delete_doc(project_path: &Path, slug: &str) -> Result<DeleteDocResu
         return Err(DocError::DocNotFound(slug.to_string()));
     }
 
-    // Remove the file
-    fs::remove_file(&doc_path).await?;
+    // Move the file to the trash
+    let doc = read_doc_from_disk(&doc_path, slug).await?;
+    move_to_trash(
+        project_path,
+        TrashKind::Doc,
+        slug,
+        &doc.title,
+        None,
+        &doc_path,
+        RemovedLinks::default(),
+    )
+    .await?;
+    remove_doc(project_path, slug);
+    notify_change(project_path, ChangeEntity::Doc, ChangeKind::Deleted, slug);
 
     // Update manifest timestamp
     update_manifest_timestamp(&mut manifest);
@@ -350,7 +395,7 @@ pub async fn delete_doc(project_path: &Path, slug: &str) -> Result<DeleteDocResu
 }
 
 /// Read a doc from disk
-async fn read_doc_from_disk(doc_path: &Path, slug: &str) -> Result<Doc, DocError> {
+pub(crate) async fn read_doc_from_disk(doc_path: &Path, slug: &str) -> Result<Doc, DocError> {
     let content = fs::read_to_string(doc_path).await?;
     let (title, body, metadata) = parse_doc_content(&content);
 
@@ -362,6 +407,60 @@ async fn read_doc_from_disk(doc_path: &Path, slug: &str) -> Result<Doc, DocError
     })
 }
 
+/// What is wrong with a doc's frontmatter, if anything. Docs without
+/// frontmatter are accepted; a block that is not closed, has no title or
+/// holds a timestamp that does not parse is not.
+pub(crate) fn frontmatter_problem(content: &str) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    if lines.first() != Some(&"---") {
+        return None;
+    }
+    let Some(end_idx) = lines.iter().skip(1).position(|&line| line == "---") else {
+        return Some("Frontmatter is not closed with '---'".to_string());
+    };
+
+    let field = |name: &str| {
+        lines[1..=end_idx]
+            .iter()
+            .find_map(|line| line.strip_prefix(name))
+            .map(|value| value.trim().trim_matches('"'))
+    };
+    if field("title:").is_none_or(str::is_empty) {
+        return Some("Frontmatter has no title".to_string());
+    }
+    for name in ["createdAt", "updatedAt"] {
+        if let Some(value) = field(&format!("{}:", name)).filter(|v| !v.is_empty()) {
+            if chrono::DateTime::parse_from_rfc3339(value).is_err() {
+                return Some(format!("{} '{}' is not a valid timestamp", name, value));
+            }
+        }
+    }
+    None
+}
+
+/// Rewrite a doc's frontmatter in the default format, keeping its title
+/// (or the slug, if it has none), body and any valid timestamps
+pub(crate) async fn rewrite_frontmatter(doc_path: &Path, slug: &str) -> Result<(), DocError> {
+    let content = fs::read_to_string(doc_path).await?;
+    let (title, body, metadata) = parse_doc_content(&content);
+
+    let valid_or_now = |timestamp: String| {
+        if chrono::DateTime::parse_from_rfc3339(&timestamp).is_ok() {
+            timestamp
+        } else {
+            now_iso()
+        }
+    };
+    let title = if title.is_empty() { slug.to_string() } else { title };
+    let metadata = DocMetadata {
+        created_at: valid_or_now(metadata.created_at),
+        updated_at: valid_or_now(metadata.updated_at),
+    };
+
+    write_atomic(doc_path, generate_doc_content(&title, &body, &metadata)).await?;
+    Ok(())
+}
+
 /// Generate doc content with YAML frontmatter
 fn generate_doc_content(title: &str, content: &str, metadata: &DocMetadata) -> String {
     format!(
@@ -453,7 +552,7 @@ fn parse_doc_content(content: &str) -> (String, String, DocMetadata) {
 }
 
 /// Convert a string to a URL-friendly slug
-fn slugify(s: &str) -> String {
+pub(crate) fn slugify(s: &str) -> String {
     s.to_lowercase()
         .chars()
         .map(|c| {
@@ -569,4 +668,15 @@ This is the content."#;
         assert_eq!(escape_yaml_string("with \"quotes\""), "with \\\"quotes\\\"");
         assert_eq!(escape_yaml_string("back\\slash"), "back\\\\slash");
     }
+
+    #[test]
+    fn test_frontmatter_problem() {
+        let valid = "---\ntitle: \"Doc\"\ncreatedAt: \"2024-01-01T00:00:00Z\"\n---\n\n# Doc\n";
+        assert_eq!(frontmatter_problem(valid), None);
+        assert_eq!(frontmatter_problem("# Plain doc\n"), None);
+
+        assert!(frontmatter_problem("---\ntitle: \"Doc\"\n\n# Doc\n").is_some());
+        assert!(frontmatter_problem("---\ncreatedAt: \"2024-01-01T00:00:00Z\"\n---\n").is_some());
+        assert!(frontmatter_problem("---\ntitle: \"Doc\"\nupdatedAt: \"yesterday\"\n---\n").is_some());
+    }
 }
@@ -4,3 +4,4 @@ pub use crud::{
     create_doc, delete_doc, get_doc, list_docs, update_doc, CreateDocOptions, CreateDocResult,
     DeleteDocResult, Doc, DocError, DocMetadata, UpdateDocOptions, UpdateDocResult,
 };
+pub(crate) use crud::{frontmatter_problem, read_doc_from_disk, rewrite_frontmatter, slugify};
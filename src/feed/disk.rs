@@ -0,0 +1,284 @@
+use super::events::{publish_disk_change, ChangeEntity, ChangeKind};
+use crate::issue::{invalidate_issue, is_valid_issue_folder};
+use crate::pr::is_valid_pr_folder;
+use crate::search::invalidate_project;
+use crate::utils::is_temp_file;
+use notify::event::{EventKind, ModifyKind, RenameMode};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+/// Quiet time after the last filesystem event before a burst is reported
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// One watcher per project, shared by all of its subscribers
+struct SharedWatcher {
+    _watcher: RecommendedWatcher,
+    subscribers: usize,
+}
+
+static WATCHERS: OnceLock<Mutex<HashMap<PathBuf, SharedWatcher>>> = OnceLock::new();
+
+fn with_watchers<R>(f: impl FnOnce(&mut HashMap<PathBuf, SharedWatcher>) -> R) -> R {
+    let mut watchers = WATCHERS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    f(&mut watchers)
+}
+
+/// Keeps the project's watcher running while held
+pub(crate) struct DiskWatchGuard {
+    project_path: PathBuf,
+}
+
+impl Drop for DiskWatchGuard {
+    fn drop(&mut self) {
+        with_watchers(|watchers| {
+            if let Some(shared) = watchers.get_mut(&self.project_path) {
+                shared.subscribers -= 1;
+                if shared.subscribers == 0 {
+                    watchers.remove(&self.project_path);
+                }
+            }
+        });
+    }
+}
+
+/// Start watching `.centy/` of a project (a canonical path), or join the
+/// watcher that is already running. Must be called inside a Tokio runtime.
+pub(crate) fn watch_disk(project_path: &Path) -> Result<DiskWatchGuard, notify::Error> {
+    with_watchers(|watchers| {
+        if let Some(shared) = watchers.get_mut(project_path) {
+            shared.subscribers += 1;
+        } else {
+            let (tx, rx) = mpsc::unbounded_channel();
+            let mut watcher = notify::recommended_watcher(move |result: notify::Result<Event>| {
+                match result {
+                    // Reads change nothing, and a steady reader would keep
+                    // the debounce below from ever settling
+                    Ok(event) if matches!(event.kind, EventKind::Access(_)) => {}
+                    Ok(event) => {
+                        let _ = tx.send(event);
+                    }
+                    Err(e) => warn!(error = %e, "Filesystem watcher error"),
+                }
+            })?;
+            watcher.watch(&project_path.join(".centy"), RecursiveMode::Recursive)?;
+            tokio::spawn(forward_events(project_path.to_path_buf(), rx));
+            watchers.insert(
+                project_path.to_path_buf(),
+                SharedWatcher {
+                    _watcher: watcher,
+                    subscribers: 1,
+                },
+            );
+        }
+        Ok(DiskWatchGuard {
+            project_path: project_path.to_path_buf(),
+        })
+    })
+}
+
+/// What a path below `.centy/` belongs to
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) struct DiskTarget {
+    pub entity: ChangeEntity,
+    pub id: String,
+    /// The file or folder holding the item, relative to `.centy/`
+    pub root: PathBuf,
+}
+
+/// Map a path relative to `.centy/` to the item it belongs to. Paths that
+/// are not part of an issue, doc, PR, asset or the config are ignored, as
+/// are the temporary files of an atomic write.
+pub(super) fn classify(relative: &Path) -> Option<DiskTarget> {
+    let parts: Vec<&str> = relative
+        .components()
+        .map(|c| match c {
+            Component::Normal(s) => s.to_str(),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+    if parts.last().is_some_and(|name| is_temp_file(name)) {
+        return None;
+    }
+
+    let target = |entity, id: String, root: &[&str]| DiskTarget {
+        entity,
+        id,
+        root: root.iter().collect(),
+    };
+
+    match parts.as_slice() {
+        ["config.json"] => Some(target(ChangeEntity::Config, "config".to_string(), &parts)),
+        ["issues", id, "assets", name, ..] if is_valid_issue_folder(id) => Some(target(
+            ChangeEntity::Asset,
+            format!("issues/{}/assets/{}", id, name),
+            &parts[..4],
+        )),
+        ["issues", id, ..] if is_valid_issue_folder(id) => {
+            Some(target(ChangeEntity::Issue, id.to_string(), &parts[..2]))
+        }
+        ["docs", file] => {
+            let slug = file.strip_suffix(".md")?;
+            if slug == "README" {
+                return None;
+            }
+            Some(target(ChangeEntity::Doc, slug.to_string(), &parts))
+        }
+        ["prs", id, ..] if is_valid_pr_folder(id) => {
+            Some(target(ChangeEntity::Pr, id.to_string(), &parts[..2]))
+        }
+        ["assets", name, ..] => Some(target(
+            ChangeEntity::Asset,
+            format!("assets/{}", name),
+            &parts[..2],
+        )),
+        _ => None,
+    }
+}
+
+/// The issue a path below `.centy/` belongs to, whether active or archived
+fn issue_folder(relative: &Path) -> Option<&str> {
+    let mut parts = relative.components().map(|c| c.as_os_str().to_str());
+    let id = match parts.next()?? {
+        "issues" => parts.next()??,
+        "archive" if parts.next()?? == "issues" => parts.next()??,
+        _ => return None,
+    };
+    is_valid_issue_folder(id).then_some(id)
+}
+
+/// Collect filesystem events into bursts and publish one change per item
+async fn forward_events(project_path: PathBuf, mut rx: mpsc::UnboundedReceiver<Event>) {
+    let centy_path = project_path.join(".centy");
+    while let Some(event) = rx.recv().await {
+        // Item -> whether its file or folder was created during the burst
+        let mut pending: HashMap<DiskTarget, bool> = HashMap::new();
+        collect(&centy_path, &event, &mut pending);
+
+        let mut closed = false;
+        loop {
+            match tokio::time::timeout(DEBOUNCE, rx.recv()).await {
+                Ok(Some(event)) => collect(&centy_path, &event, &mut pending),
+                Ok(None) => {
+                    closed = true;
+                    break;
+                }
+                Err(_) => break,
+            }
+        }
+
+        // Whether the daemon made the edit or not: an outside edit can land
+        // right after a daemon write and be taken for its echo below
+        if pending
+            .keys()
+            .any(|t| matches!(t.entity, ChangeEntity::Issue | ChangeEntity::Doc | ChangeEntity::Pr))
+        {
+            invalidate_project(&project_path);
+        }
+        for (target, created) in pending {
+            let kind = if !centy_path.join(&target.root).exists() {
+                ChangeKind::Deleted
+            } else if created {
+                ChangeKind::Created
+            } else {
+                ChangeKind::Updated
+            };
+            publish_disk_change(&project_path, target.entity, kind, &target.id);
+        }
+
+        if closed {
+            break;
+        }
+    }
+}
+
+fn collect(centy_path: &Path, event: &Event, pending: &mut HashMap<DiskTarget, bool>) {
+    let project_path = centy_path.parent().unwrap_or(centy_path);
+    let creates = matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(RenameMode::To))
+    );
+    for path in &event.paths {
+        let Ok(relative) = path.strip_prefix(centy_path) else {
+            continue;
+        };
+        // Right away rather than after the burst, so reads never see the old issue
+        if let Some(id) = issue_folder(relative) {
+            invalidate_issue(project_path, id);
+        }
+        let Some(target) = classify(relative) else {
+            continue;
+        };
+        let created = creates && relative == target.root;
+        *pending.entry(target).or_default() |= created;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "550e8400-e29b-41d4-a716-446655440000";
+
+    fn classified(path: &str) -> Option<(ChangeEntity, String, PathBuf)> {
+        classify(Path::new(path)).map(|t| (t.entity, t.id, t.root))
+    }
+
+    #[test]
+    fn test_classify_paths() {
+        assert_eq!(
+            classified(&format!("issues/{}/metadata.json", ID)),
+            Some((ChangeEntity::Issue, ID.to_string(), PathBuf::from(format!("issues/{}", ID))))
+        );
+        assert_eq!(
+            classified(&format!("issues/{}/assets/shot.png", ID)),
+            Some((
+                ChangeEntity::Asset,
+                format!("issues/{}/assets/shot.png", ID),
+                PathBuf::from(format!("issues/{}/assets/shot.png", ID))
+            ))
+        );
+        assert_eq!(
+            classified("docs/getting-started.md"),
+            Some((ChangeEntity::Doc, "getting-started".to_string(), PathBuf::from("docs/getting-started.md")))
+        );
+        assert_eq!(
+            classified(&format!("prs/{}", ID)),
+            Some((ChangeEntity::Pr, ID.to_string(), PathBuf::from(format!("prs/{}", ID))))
+        );
+        assert_eq!(
+            classified("assets/logo.svg"),
+            Some((ChangeEntity::Asset, "assets/logo.svg".to_string(), PathBuf::from("assets/logo.svg")))
+        );
+        assert_eq!(classified("config.json").map(|t| t.0), Some(ChangeEntity::Config));
+    }
+
+    #[test]
+    fn test_classify_ignores_other_files() {
+        assert_eq!(classified(".centy-manifest.json"), None);
+        assert_eq!(classified("issues/README.md"), None);
+        assert_eq!(classified("docs/README.md"), None);
+        assert_eq!(classified("docs/notes.txt"), None);
+        assert_eq!(classified(&format!("trash/issue-{}/data/metadata.json", ID)), None);
+        assert_eq!(classified(&format!("archive/issues/{}/metadata.json", ID)), None);
+        assert_eq!(classified(&format!("issues/{}/.metadata.json.42-0.tmp", ID)), None);
+        assert_eq!(classified("assets/.logo.svg.42-1.tmp"), None);
+    }
+
+    #[test]
+    fn test_issue_folder() {
+        let folder = |path: String| issue_folder(Path::new(&path)).map(String::from);
+        assert_eq!(folder(format!("issues/{}/issue.md", ID)), Some(ID.to_string()));
+        assert_eq!(folder(format!("archive/issues/{}/metadata.json", ID)), Some(ID.to_string()));
+        assert_eq!(folder(format!("issues/{}", ID)), Some(ID.to_string()));
+        assert_eq!(folder("issues/README.md".to_string()), None);
+        assert_eq!(folder(format!("prs/{}/pr.md", ID)), None);
+    }
+}
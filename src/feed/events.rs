@@ -0,0 +1,195 @@
+use crate::issue::invalidate_issue;
+use crate::utils::now_iso;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+
+/// Events buffered per subscriber before slow ones start missing events
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// How long after the daemon reports a change matching disk events are
+/// treated as echoes of its own write
+const ECHO_WINDOW: Duration = Duration::from_secs(2);
+
+/// Kind of item that changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeEntity {
+    Issue,
+    Doc,
+    Pr,
+    Asset,
+    Config,
+}
+
+impl ChangeEntity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeEntity::Issue => "issue",
+            ChangeEntity::Doc => "doc",
+            ChangeEntity::Pr => "pr",
+            ChangeEntity::Asset => "asset",
+            ChangeEntity::Config => "config",
+        }
+    }
+
+    /// Parse an entity name ("issue", "doc", "pr", "asset" or "config")
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "issue" | "issues" => Some(ChangeEntity::Issue),
+            "doc" | "docs" => Some(ChangeEntity::Doc),
+            "pr" | "prs" => Some(ChangeEntity::Pr),
+            "asset" | "assets" => Some(ChangeEntity::Asset),
+            "config" => Some(ChangeEntity::Config),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Created,
+    Updated,
+    Deleted,
+}
+
+impl ChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeKind::Created => "created",
+            ChangeKind::Updated => "updated",
+            ChangeKind::Deleted => "deleted",
+        }
+    }
+}
+
+/// Where a change was noticed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeSource {
+    /// Made through the daemon
+    Daemon,
+    /// Seen on disk, e.g. after `git pull` or a direct edit
+    Disk,
+}
+
+impl ChangeSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeSource::Daemon => "daemon",
+            ChangeSource::Disk => "disk",
+        }
+    }
+}
+
+/// A single change to a project
+#[derive(Debug, Clone)]
+pub struct ChangeEvent {
+    /// Canonical project path
+    pub project_path: PathBuf,
+    pub entity: ChangeEntity,
+    pub kind: ChangeKind,
+    /// Issue/PR UUID, doc slug, asset path below `.centy/` (e.g.
+    /// `issues/{id}/assets/screenshot.png`) or `config`
+    pub id: String,
+    pub source: ChangeSource,
+    pub timestamp: String,
+}
+
+#[derive(Default)]
+struct FeedState {
+    /// When the daemon last reported a change, per project and echo key
+    recent: HashMap<(PathBuf, String), Instant>,
+}
+
+static CHANNEL: OnceLock<broadcast::Sender<ChangeEvent>> = OnceLock::new();
+static FEED_STATE: OnceLock<Mutex<FeedState>> = OnceLock::new();
+
+fn channel() -> &'static broadcast::Sender<ChangeEvent> {
+    CHANNEL.get_or_init(|| broadcast::channel(CHANNEL_CAPACITY).0)
+}
+
+fn with_state<R>(f: impl FnOnce(&mut FeedState) -> R) -> R {
+    let mut state = FEED_STATE
+        .get_or_init(|| Mutex::new(FeedState::default()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    f(&mut state)
+}
+
+pub(super) fn subscribe_channel() -> broadcast::Receiver<ChangeEvent> {
+    channel().subscribe()
+}
+
+/// Canonicalize the project path so different spellings share one feed
+pub(super) fn project_key(project_path: &Path) -> PathBuf {
+    project_path
+        .canonicalize()
+        .unwrap_or_else(|_| project_path.to_path_buf())
+}
+
+/// An issue's assets, comments and history live in its folder, so they
+/// share one echo key with the issue itself
+fn echo_key(entity: ChangeEntity, id: &str) -> String {
+    match (entity, id.strip_prefix("issues/")) {
+        (ChangeEntity::Asset, Some(rest)) => {
+            format!("issue:{}", rest.split('/').next().unwrap_or(rest))
+        }
+        _ => format!("{}:{}", entity.as_str(), id),
+    }
+}
+
+/// Report a change made through the daemon to everyone watching the project.
+/// Changed issues are also dropped from the in-memory issue cache.
+pub(crate) fn notify_change(project_path: &Path, entity: ChangeEntity, kind: ChangeKind, id: &str) {
+    if entity == ChangeEntity::Issue {
+        invalidate_issue(project_path, id);
+    }
+    if channel().receiver_count() == 0 {
+        return;
+    }
+
+    let project_path = project_key(project_path);
+    let now = Instant::now();
+    with_state(|state| {
+        state.recent.retain(|_, at| now.duration_since(*at) < ECHO_WINDOW);
+        state
+            .recent
+            .insert((project_path.clone(), echo_key(entity, id)), now);
+    });
+
+    send(project_path, entity, kind, id, ChangeSource::Daemon);
+}
+
+/// Report a change seen on disk, unless it is the echo of a daemon write
+pub(super) fn publish_disk_change(
+    project_path: &Path,
+    entity: ChangeEntity,
+    kind: ChangeKind,
+    id: &str,
+) {
+    let key = (project_path.to_path_buf(), echo_key(entity, id));
+    let is_echo = with_state(|state| {
+        state
+            .recent
+            .get(&key)
+            .is_some_and(|at| at.elapsed() < ECHO_WINDOW)
+    });
+    if is_echo {
+        return;
+    }
+
+    send(project_path.to_path_buf(), entity, kind, id, ChangeSource::Disk);
+}
+
+fn send(project_path: PathBuf, entity: ChangeEntity, kind: ChangeKind, id: &str, source: ChangeSource) {
+    // Sending only fails when nobody is subscribed
+    let _ = channel().send(ChangeEvent {
+        project_path,
+        entity,
+        kind,
+        id: id.to_string(),
+        source,
+        timestamp: now_iso(),
+    });
+}
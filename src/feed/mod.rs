@@ -0,0 +1,16 @@
+//! Change feed for live clients.
+//!
+//! Changes reach subscribers two ways: the CRUD modules report their own
+//! writes through `notify_change`, and a filesystem watcher over `.centy/`
+//! picks up edits made behind the daemon's back (`git pull`, editors, LLMs
+//! with direct edits). Disk events that echo a write the daemon just
+//! reported are dropped, so each change is delivered once.
+
+mod disk;
+mod events;
+mod subscription;
+
+pub use events::{ChangeEntity, ChangeEvent, ChangeKind, ChangeSource};
+pub(crate) use disk::{watch_disk, DiskWatchGuard};
+pub(crate) use events::notify_change;
+pub use subscription::{watch_project, Subscription, WatchError};
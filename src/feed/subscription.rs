@@ -0,0 +1,72 @@
+use super::disk::{watch_disk, DiskWatchGuard};
+use super::events::{project_key, subscribe_channel, ChangeEntity, ChangeEvent};
+use crate::utils::get_centy_path;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::warn;
+
+#[derive(Error, Debug)]
+pub enum WatchError {
+    #[error("Centy not initialized. Run 'centy init' first.")]
+    NotInitialized,
+
+    #[error("Unknown entity: {0}")]
+    UnknownEntity(String),
+
+    #[error("Failed to watch project files: {0}")]
+    WatcherError(#[from] notify::Error),
+}
+
+/// A live feed of changes to one project
+pub struct Subscription {
+    project_path: PathBuf,
+    entities: Vec<ChangeEntity>,
+    receiver: tokio::sync::broadcast::Receiver<ChangeEvent>,
+    _disk: DiskWatchGuard,
+}
+
+impl Subscription {
+    /// Wait for the next change. Returns None once the feed has shut down.
+    pub async fn recv(&mut self) -> Option<ChangeEvent> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) => {
+                    if event.project_path == self.project_path
+                        && (self.entities.is_empty() || self.entities.contains(&event.entity))
+                    {
+                        return Some(event);
+                    }
+                }
+                Err(RecvError::Lagged(missed)) => {
+                    warn!(missed, project = %self.project_path.display(), "Change feed subscriber fell behind");
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+/// Subscribe to changes in a project, whether made through the daemon or
+/// directly on disk. `entities` limits the feed to some kinds of item;
+/// empty = all.
+pub fn watch_project(
+    project_path: &Path,
+    entities: Vec<ChangeEntity>,
+) -> Result<Subscription, WatchError> {
+    if !get_centy_path(project_path).exists() {
+        return Err(WatchError::NotInitialized);
+    }
+
+    let project_path = project_key(project_path);
+    // Subscribe before the watcher starts so no disk event is missed
+    let receiver = subscribe_channel();
+    let disk = watch_disk(&project_path)?;
+
+    Ok(Subscription {
+        project_path,
+        entities,
+        receiver,
+        _disk: disk,
+    })
+}
@@ -0,0 +1,578 @@
+use crate::config::{read_config, CentyConfig};
+use crate::docs::frontmatter_problem;
+use crate::issue::{is_valid_issue_folder, validate_priority, IssueMetadata};
+use crate::manifest::read_manifest;
+use crate::pr::{is_valid_pr_folder, PrMetadata};
+use crate::reconciliation::build_reconciliation_plan;
+use crate::utils::{get_centy_path, is_temp_file};
+use std::collections::BTreeMap;
+use std::path::Path;
+use thiserror::Error;
+use tokio::fs;
+
+/// Name of the manifest inside `.centy/`
+pub(super) const MANIFEST_FILE: &str = ".centy-manifest.json";
+
+#[derive(Error, Debug)]
+pub enum IntegrityError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Manifest error: {0}")]
+    ManifestError(#[from] crate::manifest::ManifestError),
+
+    #[error("Config error: {0}")]
+    ConfigError(#[from] crate::config::ConfigError),
+
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("Centy not initialized. Run 'centy init' first.")]
+    NotInitialized,
+
+    #[error("Finding '{0}' not found")]
+    FindingNotFound(String),
+
+    #[error("Plan error: {0}")]
+    PlanError(#[from] crate::reconciliation::PlanError),
+
+    #[error("Reconciliation error: {0}")]
+    ExecuteError(#[from] crate::reconciliation::ExecuteError),
+
+    #[error("Doc error: {0}")]
+    DocError(#[from] crate::docs::DocError),
+
+    #[error("Reconcile error: {0}")]
+    ReconcileError(#[from] crate::issue::ReconcileError),
+
+    #[error("PR reconcile error: {0}")]
+    PrReconcileError(#[from] crate::pr::ReconcileError),
+}
+
+/// How much a finding matters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Harmless leftovers and local edits
+    Info,
+    /// Data that is read, but not the way it was meant to be
+    Warning,
+    /// Data the daemon cannot read, so it is missing from lists
+    Error,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// What a finding is about. Repairs run in this order, so the config and
+/// metadata are fixed before values in them are checked against each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FindingKind {
+    /// `config.json` cannot be parsed
+    InvalidConfig,
+    /// The manifest or a managed file is missing or differs from its template
+    ManifestDrift,
+    /// An issue or PR `metadata.json` cannot be parsed
+    InvalidMetadata,
+    /// An issue or PR folder has its markdown file but no `metadata.json`
+    MissingMetadata,
+    /// An issue or PR folder has `metadata.json` but no markdown file
+    MissingContent,
+    /// An issue status is not in `allowedStates`
+    UnknownStatus,
+    /// A priority is outside `1..=priorityLevels`
+    PriorityOutOfRange,
+    /// Several active issues or PRs share a display number
+    DuplicateDisplayNumber,
+    /// Invalid doc frontmatter
+    InvalidFrontmatter,
+    /// Assets in a folder that holds no issue or PR
+    OrphanedAssets,
+    /// A zero-byte asset
+    EmptyAsset,
+    /// A temporary file left by an interrupted write
+    TempFile,
+}
+
+impl FindingKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FindingKind::InvalidConfig => "invalid-config",
+            FindingKind::ManifestDrift => "manifest-drift",
+            FindingKind::InvalidMetadata => "invalid-metadata",
+            FindingKind::MissingMetadata => "missing-metadata",
+            FindingKind::MissingContent => "missing-content",
+            FindingKind::UnknownStatus => "unknown-status",
+            FindingKind::PriorityOutOfRange => "priority-out-of-range",
+            FindingKind::DuplicateDisplayNumber => "duplicate-display-number",
+            FindingKind::InvalidFrontmatter => "invalid-frontmatter",
+            FindingKind::OrphanedAssets => "orphaned-assets",
+            FindingKind::EmptyAsset => "empty-asset",
+            FindingKind::TempFile => "temp-file",
+        }
+    }
+}
+
+/// One problem found in `.centy/`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    /// Stable across checks while the problem remains, e.g.
+    /// "unknown-status:issues/{uuid}". Used to pick findings to repair.
+    pub id: String,
+    pub kind: FindingKind,
+    pub severity: Severity,
+    /// File or folder the finding is about, relative to `.centy/`
+    pub path: String,
+    pub message: String,
+    /// What `repair_project` does about it
+    pub suggested_fix: String,
+}
+
+impl Finding {
+    fn new(
+        kind: FindingKind,
+        severity: Severity,
+        path: impl Into<String>,
+        message: impl Into<String>,
+        suggested_fix: impl Into<String>,
+    ) -> Self {
+        let path = path.into();
+        Self {
+            id: format!("{}:{}", kind.as_str(), path),
+            kind,
+            severity,
+            path,
+            message: message.into(),
+            suggested_fix: suggested_fix.into(),
+        }
+    }
+}
+
+/// Issues and PRs share a folder layout and are checked the same way
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ItemKind {
+    Issue,
+    Pr,
+}
+
+impl ItemKind {
+    /// The kind of item a path relative to `.centy/` points into
+    pub(super) fn of_path(path: &str) -> Self {
+        if path.starts_with("prs/") || path == "prs" {
+            ItemKind::Pr
+        } else {
+            ItemKind::Issue
+        }
+    }
+
+    pub(super) fn content_file(&self) -> &'static str {
+        match self {
+            ItemKind::Issue => "issue.md",
+            ItemKind::Pr => "pr.md",
+        }
+    }
+
+    fn noun(&self) -> &'static str {
+        match self {
+            ItemKind::Issue => "issue",
+            ItemKind::Pr => "PR",
+        }
+    }
+
+    fn is_valid_folder(&self, name: &str) -> bool {
+        match self {
+            ItemKind::Issue => is_valid_issue_folder(name),
+            ItemKind::Pr => is_valid_pr_folder(name),
+        }
+    }
+}
+
+/// The fields of an issue or PR `metadata.json` that are checked
+struct ItemFields {
+    display_number: u32,
+    status: String,
+    priority: u32,
+}
+
+fn parse_fields(kind: ItemKind, data: &[u8]) -> Result<ItemFields, serde_json::Error> {
+    Ok(match kind {
+        ItemKind::Issue => {
+            let metadata: IssueMetadata = serde_json::from_slice(data)?;
+            ItemFields {
+                display_number: metadata.display_number,
+                status: metadata.status,
+                priority: metadata.priority,
+            }
+        }
+        ItemKind::Pr => {
+            let metadata: PrMetadata = serde_json::from_slice(data)?;
+            ItemFields {
+                display_number: metadata.display_number,
+                status: metadata.status,
+                priority: metadata.priority,
+            }
+        }
+    })
+}
+
+/// Check the project's `.centy/` folder. Findings are sorted with the most
+/// severe first. Nothing is changed on disk.
+pub async fn check_project(project_path: &Path) -> Result<Vec<Finding>, IntegrityError> {
+    let centy_path = get_centy_path(project_path);
+    if !centy_path.exists() {
+        return Err(IntegrityError::NotInitialized);
+    }
+
+    let mut findings = Vec::new();
+    check_manifest(project_path, &mut findings).await?;
+    let config = check_config(project_path, &mut findings).await;
+
+    check_temp_files(&centy_path, "", &mut findings).await?;
+    check_items(&centy_path, "issues", ItemKind::Issue, &config, &mut findings).await?;
+    check_items(&centy_path, "archive/issues", ItemKind::Issue, &config, &mut findings).await?;
+    check_items(&centy_path, "prs", ItemKind::Pr, &config, &mut findings).await?;
+    check_assets(&centy_path, "assets", &mut findings).await?;
+    check_docs(&centy_path, &mut findings).await?;
+
+    findings.sort_by(|a, b| b.severity.cmp(&a.severity).then_with(|| a.path.cmp(&b.path)));
+    Ok(findings)
+}
+
+async fn check_manifest(project_path: &Path, findings: &mut Vec<Finding>) -> Result<(), IntegrityError> {
+    let problem = match read_manifest(project_path).await {
+        Ok(Some(_)) => None,
+        Ok(None) => Some("Manifest is missing".to_string()),
+        Err(e) => Some(format!("Manifest cannot be read: {}", e)),
+    };
+    if let Some(message) = problem {
+        findings.push(Finding::new(
+            FindingKind::ManifestDrift,
+            Severity::Error,
+            MANIFEST_FILE,
+            message,
+            "Recreate the manifest",
+        ));
+    }
+
+    let plan = build_reconciliation_plan(project_path).await?;
+    for file in &plan.to_create {
+        findings.push(Finding::new(
+            FindingKind::ManifestDrift,
+            Severity::Warning,
+            &file.path,
+            "Managed file is missing",
+            "Recreate it from the Centy template",
+        ));
+    }
+    for file in &plan.to_reset {
+        findings.push(Finding::new(
+            FindingKind::ManifestDrift,
+            Severity::Info,
+            &file.path,
+            "Managed file differs from the Centy template",
+            "Reset it to the template (local edits are lost)",
+        ));
+    }
+    Ok(())
+}
+
+/// Report an unreadable config and fall back to the defaults for the checks
+/// that depend on it
+async fn check_config(project_path: &Path, findings: &mut Vec<Finding>) -> CentyConfig {
+    match read_config(project_path).await {
+        Ok(config) => config.unwrap_or_default(),
+        Err(e) => {
+            findings.push(Finding::new(
+                FindingKind::InvalidConfig,
+                Severity::Error,
+                "config.json",
+                format!("config.json cannot be read: {}", e),
+                "Replace config.json with the defaults and keep the broken file as config.json.bak",
+            ));
+            CentyConfig::default()
+        }
+    }
+}
+
+/// Check every issue or PR folder in `dir` (relative to `.centy/`)
+async fn check_items(
+    centy_path: &Path,
+    dir: &str,
+    kind: ItemKind,
+    config: &CentyConfig,
+    findings: &mut Vec<Finding>,
+) -> Result<(), IntegrityError> {
+    let dir_path = centy_path.join(dir);
+    if !dir_path.exists() {
+        return Ok(());
+    }
+
+    // Display number -> how many items use it
+    let mut numbers: BTreeMap<u32, usize> = BTreeMap::new();
+    let mut entries = fs::read_dir(&dir_path).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        if !entry.file_type().await?.is_dir() {
+            continue;
+        }
+        let Some(name) = entry.file_name().to_str().map(String::from) else {
+            continue;
+        };
+        if !kind.is_valid_folder(&name) {
+            continue;
+        }
+        let folder = entry.path();
+        let path = format!("{}/{}", dir, name);
+
+        check_temp_files(&folder, &path, findings).await?;
+        let asset_count = check_assets(centy_path, &format!("{}/assets", path), findings).await?;
+
+        let has_content = folder.join(kind.content_file()).exists();
+        let metadata_path = folder.join("metadata.json");
+        if !metadata_path.exists() {
+            if has_content {
+                findings.push(Finding::new(
+                    FindingKind::MissingMetadata,
+                    Severity::Error,
+                    &path,
+                    format!(
+                        "{} has {} but no metadata.json, so it is not listed",
+                        capitalize(kind.noun()),
+                        kind.content_file()
+                    ),
+                    "Create metadata.json with the next display number and the default status and priority",
+                ));
+            } else if asset_count > 0 {
+                findings.push(Finding::new(
+                    FindingKind::OrphanedAssets,
+                    Severity::Warning,
+                    &path,
+                    format!("Folder holds {} asset(s) but no {}", asset_count, kind.noun()),
+                    "Delete the folder",
+                ));
+            }
+            continue;
+        }
+
+        if !has_content {
+            findings.push(Finding::new(
+                FindingKind::MissingContent,
+                Severity::Error,
+                &path,
+                format!(
+                    "{} has metadata.json but no {}, so it is not listed",
+                    capitalize(kind.noun()),
+                    kind.content_file()
+                ),
+                format!("Create {} with a placeholder title", kind.content_file()),
+            ));
+        }
+
+        let fields = match parse_fields(kind, &fs::read(&metadata_path).await?) {
+            Ok(fields) => fields,
+            Err(e) => {
+                findings.push(Finding::new(
+                    FindingKind::InvalidMetadata,
+                    Severity::Error,
+                    &path,
+                    format!("metadata.json cannot be parsed: {}", e),
+                    "Replace metadata.json with defaults and keep the broken file as metadata.json.bak",
+                ));
+                continue;
+            }
+        };
+
+        if kind == ItemKind::Issue && !config.allowed_states.contains(&fields.status) {
+            findings.push(Finding::new(
+                FindingKind::UnknownStatus,
+                Severity::Warning,
+                &path,
+                format!("Status '{}' is not in allowedStates", fields.status),
+                format!("Set the status to '{}'", config.default_state),
+            ));
+        }
+        if validate_priority(fields.priority, config.priority_levels).is_err() {
+            findings.push(Finding::new(
+                FindingKind::PriorityOutOfRange,
+                Severity::Warning,
+                &path,
+                format!(
+                    "Priority {} is outside 1 to {}",
+                    fields.priority, config.priority_levels
+                ),
+                format!(
+                    "Set the priority to {}",
+                    clamp_priority(fields.priority, config.priority_levels)
+                ),
+            ));
+        }
+        *numbers.entry(fields.display_number).or_default() += 1;
+    }
+
+    // Archived items keep their numbers; only active ones must be unique
+    let duplicates: Vec<String> = numbers
+        .iter()
+        .filter(|(_, count)| **count > 1)
+        .map(|(number, _)| number.to_string())
+        .collect();
+    if !dir.starts_with("archive/") && !duplicates.is_empty() {
+        findings.push(Finding::new(
+            FindingKind::DuplicateDisplayNumber,
+            Severity::Warning,
+            dir,
+            format!(
+                "Display number(s) {} are used by more than one {}",
+                duplicates.join(", "),
+                kind.noun()
+            ),
+            "Renumber the newer ones; their old numbers keep working as aliases",
+        ));
+    }
+    Ok(())
+}
+
+/// Check the files in an asset folder (relative to `.centy/`). Returns how
+/// many assets it holds.
+async fn check_assets(
+    centy_path: &Path,
+    dir: &str,
+    findings: &mut Vec<Finding>,
+) -> Result<usize, IntegrityError> {
+    let dir_path = centy_path.join(dir);
+    if !dir_path.is_dir() {
+        return Ok(0);
+    }
+
+    let mut count = 0;
+    let mut entries = fs::read_dir(&dir_path).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let metadata = entry.metadata().await?;
+        if !metadata.is_file() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let path = format!("{}/{}", dir, name);
+        if is_temp_file(&name) {
+            findings.push(temp_file_finding(path));
+        } else if metadata.len() == 0 {
+            count += 1;
+            findings.push(Finding::new(
+                FindingKind::EmptyAsset,
+                Severity::Warning,
+                path,
+                "Asset is empty (0 bytes)",
+                "Delete the empty file",
+            ));
+        } else {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+async fn check_docs(centy_path: &Path, findings: &mut Vec<Finding>) -> Result<(), IntegrityError> {
+    let docs_path = centy_path.join("docs");
+    if !docs_path.is_dir() {
+        return Ok(());
+    }
+    check_temp_files(&docs_path, "docs", findings).await?;
+
+    let mut entries = fs::read_dir(&docs_path).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name == "README.md" || !name.ends_with(".md") || !entry.file_type().await?.is_file() {
+            continue;
+        }
+        let problem = match fs::read_to_string(entry.path()).await {
+            Ok(content) => frontmatter_problem(&content),
+            Err(e) => Some(format!("Doc cannot be read: {}", e)),
+        };
+        if let Some(message) = problem {
+            findings.push(Finding::new(
+                FindingKind::InvalidFrontmatter,
+                Severity::Warning,
+                format!("docs/{}", name),
+                message,
+                "Rewrite the frontmatter from the doc's title, keeping valid timestamps",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Report temporary files directly inside `dir` (`path` relative to `.centy/`)
+async fn check_temp_files(dir: &Path, path: &str, findings: &mut Vec<Finding>) -> Result<(), IntegrityError> {
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if is_temp_file(&name) && entry.file_type().await?.is_file() {
+            let file_path = if path.is_empty() {
+                name
+            } else {
+                format!("{}/{}", path, name)
+            };
+            findings.push(temp_file_finding(file_path));
+        }
+    }
+    Ok(())
+}
+
+fn temp_file_finding(path: String) -> Finding {
+    Finding::new(
+        FindingKind::TempFile,
+        Severity::Info,
+        path,
+        "Temporary file left behind by an interrupted write",
+        "Delete the temporary file",
+    )
+}
+
+/// The nearest priority within `1..=priority_levels`
+pub(super) fn clamp_priority(priority: u32, priority_levels: u32) -> u32 {
+    priority.clamp(1, priority_levels.max(1))
+}
+
+fn capitalize(noun: &str) -> String {
+    let mut chars = noun.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finding_id() {
+        let finding = Finding::new(
+            FindingKind::UnknownStatus,
+            Severity::Warning,
+            "issues/abc",
+            "message",
+            "fix",
+        );
+        assert_eq!(finding.id, "unknown-status:issues/abc");
+    }
+
+    #[test]
+    fn test_item_kind_of_path() {
+        assert_eq!(ItemKind::of_path("prs/abc"), ItemKind::Pr);
+        assert_eq!(ItemKind::of_path("prs"), ItemKind::Pr);
+        assert_eq!(ItemKind::of_path("issues/abc"), ItemKind::Issue);
+        assert_eq!(ItemKind::of_path("archive/issues/abc"), ItemKind::Issue);
+    }
+
+    #[test]
+    fn test_clamp_priority() {
+        assert_eq!(clamp_priority(0, 3), 1);
+        assert_eq!(clamp_priority(2, 3), 2);
+        assert_eq!(clamp_priority(7, 3), 3);
+    }
+}
@@ -0,0 +1,12 @@
+//! Integrity check and repair for the `.centy/` folder
+//!
+//! `check_project` reports what the rest of the daemon would skip or misread:
+//! broken or missing issue and PR files, duplicate display numbers, values
+//! outside the config, bad assets and docs, and managed files that drifted.
+//! Each finding names a fix, which `repair_project` applies on request.
+
+mod check;
+mod repair;
+
+pub use check::{check_project, Finding, FindingKind, IntegrityError, Severity};
+pub use repair::{repair_project, RepairFailure, RepairResult};
@@ -0,0 +1,198 @@
+use super::check::{check_project, clamp_priority, Finding, FindingKind, IntegrityError, ItemKind, MANIFEST_FILE};
+use crate::config::{read_config, write_config, CentyConfig};
+use crate::docs::rewrite_frontmatter;
+use crate::issue::{
+    default_priority, get_next_display_number, invalidate_issues, reconcile_display_numbers,
+    IssueMetadata,
+};
+use crate::manifest::{create_manifest, write_manifest};
+use crate::pr::{get_next_pr_display_number, reconcile_pr_display_numbers, PrMetadata};
+use crate::reconciliation::restore_managed_file;
+use crate::search::invalidate_project;
+use crate::utils::{get_centy_path, lock_project, now_iso, write_atomic};
+use std::collections::HashMap;
+use std::path::Path;
+use tokio::fs;
+
+/// A finding whose repair failed
+#[derive(Debug, Clone)]
+pub struct RepairFailure {
+    pub finding: Finding,
+    pub error: String,
+}
+
+/// Result of repairing a project
+#[derive(Debug, Clone, Default)]
+pub struct RepairResult {
+    pub repaired: Vec<Finding>,
+    pub failed: Vec<RepairFailure>,
+    /// Findings of a fresh check after the repair
+    pub remaining: Vec<Finding>,
+}
+
+/// Apply the suggested fix of the findings with the given ids, or of every
+/// finding if `finding_ids` is empty.
+///
+/// The project is checked again first, so ids must come from a recent check.
+/// A failed repair does not stop the others.
+pub async fn repair_project(
+    project_path: &Path,
+    finding_ids: &[String],
+) -> Result<RepairResult, IntegrityError> {
+    let findings = check_project(project_path).await?;
+    if let Some(unknown) = finding_ids
+        .iter()
+        .find(|id| !findings.iter().any(|f| &f.id == *id))
+    {
+        return Err(IntegrityError::FindingNotFound(unknown.clone()));
+    }
+
+    let mut selected: Vec<Finding> = findings
+        .into_iter()
+        .filter(|f| finding_ids.is_empty() || finding_ids.contains(&f.id))
+        .collect();
+    selected.sort_by_key(|f| f.kind);
+
+    let mut result = RepairResult::default();
+    {
+        let _lock = lock_project(project_path).await?;
+        for finding in selected {
+            match apply_fix(project_path, &finding).await {
+                Ok(()) => result.repaired.push(finding),
+                Err(e) => result.failed.push(RepairFailure {
+                    finding,
+                    error: e.to_string(),
+                }),
+            }
+        }
+    }
+
+    if !result.repaired.is_empty() {
+        invalidate_issues(project_path);
+        invalidate_project(project_path);
+    }
+    result.remaining = check_project(project_path).await?;
+    Ok(result)
+}
+
+async fn apply_fix(project_path: &Path, finding: &Finding) -> Result<(), IntegrityError> {
+    let centy_path = get_centy_path(project_path);
+    let path = centy_path.join(&finding.path);
+    let kind = ItemKind::of_path(&finding.path);
+
+    match finding.kind {
+        FindingKind::InvalidConfig => {
+            fs::rename(&path, centy_path.join("config.json.bak")).await?;
+            write_config(project_path, &CentyConfig::default()).await?;
+        }
+        FindingKind::ManifestDrift if finding.path == MANIFEST_FILE => {
+            write_manifest(project_path, &create_manifest()).await?;
+        }
+        FindingKind::ManifestDrift => {
+            restore_managed_file(project_path, &finding.path).await?;
+        }
+        FindingKind::InvalidMetadata => {
+            fs::rename(path.join("metadata.json"), path.join("metadata.json.bak")).await?;
+            write_default_metadata(project_path, &path, kind).await?;
+        }
+        FindingKind::MissingMetadata => {
+            write_default_metadata(project_path, &path, kind).await?;
+        }
+        FindingKind::MissingContent => {
+            let title = match kind {
+                ItemKind::Issue => "Untitled issue",
+                ItemKind::Pr => "Untitled PR",
+            };
+            write_atomic(path.join(kind.content_file()), format!("# {}\n", title)).await?;
+        }
+        FindingKind::UnknownStatus => {
+            let config = current_config(project_path).await;
+            update_metadata_field(&path, "status", config.default_state.into()).await?;
+        }
+        FindingKind::PriorityOutOfRange => {
+            let config = current_config(project_path).await;
+            let metadata_path = path.join("metadata.json");
+            let metadata: serde_json::Value = serde_json::from_slice(&fs::read(&metadata_path).await?)?;
+            let priority = metadata["priority"].as_u64().unwrap_or(0) as u32;
+            let priority = clamp_priority(priority, config.priority_levels);
+            update_metadata_field(&path, "priority", priority.into()).await?;
+        }
+        FindingKind::DuplicateDisplayNumber => match kind {
+            ItemKind::Issue => {
+                reconcile_display_numbers(&path).await?;
+            }
+            ItemKind::Pr => {
+                reconcile_pr_display_numbers(&path).await?;
+            }
+        },
+        FindingKind::InvalidFrontmatter => {
+            let slug = finding
+                .path
+                .trim_start_matches("docs/")
+                .trim_end_matches(".md");
+            rewrite_frontmatter(&path, slug).await?;
+        }
+        FindingKind::OrphanedAssets => {
+            fs::remove_dir_all(&path).await?;
+        }
+        FindingKind::EmptyAsset | FindingKind::TempFile => {
+            fs::remove_file(&path).await?;
+        }
+    }
+    Ok(())
+}
+
+/// The config as it is now, which an earlier repair may have replaced
+async fn current_config(project_path: &Path) -> CentyConfig {
+    read_config(project_path).await.ok().flatten().unwrap_or_default()
+}
+
+/// Give an issue or PR folder fresh metadata with the next display number
+async fn write_default_metadata(
+    project_path: &Path,
+    folder: &Path,
+    kind: ItemKind,
+) -> Result<(), IntegrityError> {
+    let config = current_config(project_path).await;
+    let centy_path = get_centy_path(project_path);
+    let priority = default_priority(config.priority_levels);
+
+    let content = match kind {
+        ItemKind::Issue => {
+            let display_number = get_next_display_number(&centy_path.join("issues")).await?;
+            let metadata =
+                IssueMetadata::new(display_number, config.default_state, priority, HashMap::new());
+            serde_json::to_string_pretty(&metadata)?
+        }
+        ItemKind::Pr => {
+            let display_number = get_next_pr_display_number(&centy_path.join("prs")).await?;
+            let metadata = PrMetadata::new(
+                display_number,
+                "draft".to_string(),
+                String::new(),
+                String::new(),
+                Vec::new(),
+                Vec::new(),
+                priority,
+                HashMap::new(),
+            );
+            serde_json::to_string_pretty(&metadata)?
+        }
+    };
+    write_atomic(folder.join("metadata.json"), content).await?;
+    Ok(())
+}
+
+/// Set one field of a `metadata.json`, leaving the others as they are
+async fn update_metadata_field(
+    folder: &Path,
+    field: &str,
+    value: serde_json::Value,
+) -> Result<(), IntegrityError> {
+    let metadata_path = folder.join("metadata.json");
+    let mut metadata: serde_json::Value = serde_json::from_slice(&fs::read(&metadata_path).await?)?;
+    metadata[field] = value;
+    metadata["updatedAt"] = now_iso().into();
+    write_atomic(&metadata_path, serde_json::to_string_pretty(&metadata)?).await?;
+    Ok(())
+}
@@ -0,0 +1,134 @@
+//! Archiving of long-closed issues
+//!
+//! Archived issues live in `.centy/archive/issues/{id}/`, outside the folder
+//! that `list_issues` scans. They keep their display numbers, which are never
+//! handed out again, so lookups by number still find them.
+
+use crate::feed::{notify_change, ChangeEntity, ChangeKind};
+use crate::config::{default_closed_states, read_config};
+use crate::manifest::{read_manifest, update_manifest_timestamp, write_manifest, CentyManifest};
+use crate::search::{index_issue, remove_issue};
+use crate::utils::{get_centy_path, lock_project};
+use super::crud::{list_issues_filtered, read_issue_from_disk, Issue, IssueCrudError, IssueFilter};
+use super::id::is_valid_issue_folder;
+use chrono::{DateTime, Duration, Utc};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+#[derive(Debug, Clone)]
+pub struct ArchiveIssuesResult {
+    /// IDs of the issues moved to the archive
+    pub archived: Vec<String>,
+    /// Updated manifest, or None when nothing was archived
+    pub manifest: Option<CentyManifest>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UnarchiveIssueResult {
+    pub issue: Issue,
+    pub manifest: CentyManifest,
+}
+
+/// Folder holding archived issues
+pub(crate) fn archived_issues_path(project_path: &Path) -> PathBuf {
+    get_centy_path(project_path).join("archive").join("issues")
+}
+
+/// The archive folder that sits next to an `issues` folder
+pub(crate) fn archive_for_issues_dir(issues_path: &Path) -> Option<PathBuf> {
+    Some(issues_path.parent()?.join("archive").join("issues"))
+}
+
+/// Whether an issue folder lives in the archive
+pub(crate) fn is_archived_path(issue_path: &Path) -> bool {
+    issue_path
+        .parent()
+        .is_some_and(|p| p.ends_with(Path::new("archive").join("issues")))
+}
+
+/// Move issues in a closed state that have not changed for `older_than_days`
+/// into the archive. None uses `archiveAfterDays` from config; 0 archives
+/// nothing.
+pub async fn archive_issues(
+    project_path: &Path,
+    older_than_days: Option<u32>,
+) -> Result<ArchiveIssuesResult, IssueCrudError> {
+    let mut manifest = read_manifest(project_path)
+        .await?
+        .ok_or(IssueCrudError::NotInitialized)?;
+    let _lock = lock_project(project_path).await?;
+
+    let config = read_config(project_path).await.ok().flatten();
+    let days = older_than_days
+        .or_else(|| config.as_ref().map(|c| c.archive_after_days))
+        .unwrap_or(0);
+    if days == 0 {
+        return Ok(ArchiveIssuesResult {
+            archived: Vec::new(),
+            manifest: None,
+        });
+    }
+    let closed_states = config
+        .map(|c| c.closed_states)
+        .unwrap_or_else(default_closed_states);
+    let cutoff = Utc::now() - Duration::days(i64::from(days));
+
+    let archive_path = archived_issues_path(project_path);
+    let issues_path = get_centy_path(project_path).join("issues");
+    let mut archived = Vec::new();
+    for issue in list_issues_filtered(project_path, &IssueFilter::default()).await? {
+        let stale = DateTime::parse_from_rfc3339(&issue.metadata.updated_at)
+            .is_ok_and(|t| t < cutoff);
+        if !stale || !closed_states.contains(&issue.metadata.status) {
+            continue;
+        }
+
+        fs::create_dir_all(&archive_path).await?;
+        fs::rename(issues_path.join(&issue.id), archive_path.join(&issue.id)).await?;
+        remove_issue(project_path, &issue.id);
+        notify_change(project_path, ChangeEntity::Issue, ChangeKind::Deleted, &issue.id);
+        archived.push(issue.id);
+    }
+
+    let manifest = if archived.is_empty() {
+        None
+    } else {
+        update_manifest_timestamp(&mut manifest);
+        write_manifest(project_path, &manifest).await?;
+        Some(manifest)
+    };
+
+    Ok(ArchiveIssuesResult { archived, manifest })
+}
+
+/// Move an archived issue back into the active issues folder
+pub async fn unarchive_issue(
+    project_path: &Path,
+    issue_id: &str,
+) -> Result<UnarchiveIssueResult, IssueCrudError> {
+    if !is_valid_issue_folder(issue_id) {
+        return Err(IssueCrudError::IssueNotFound(issue_id.to_string()));
+    }
+    let mut manifest = read_manifest(project_path)
+        .await?
+        .ok_or(IssueCrudError::NotInitialized)?;
+    let _lock = lock_project(project_path).await?;
+
+    let archived_path = archived_issues_path(project_path).join(issue_id);
+    if !archived_path.exists() {
+        return Err(IssueCrudError::IssueNotFound(issue_id.to_string()));
+    }
+
+    let issue_path = get_centy_path(project_path).join("issues").join(issue_id);
+    fs::create_dir_all(get_centy_path(project_path).join("issues")).await?;
+    fs::rename(&archived_path, &issue_path).await?;
+
+    let issue = read_issue_from_disk(&issue_path, issue_id).await?;
+    index_issue(project_path, &issue);
+    notify_change(project_path, ChangeEntity::Issue, ChangeKind::Created, issue_id);
+
+    update_manifest_timestamp(&mut manifest);
+    write_manifest(project_path, &manifest).await?;
+
+    Ok(UnarchiveIssueResult { issue, manifest })
+}
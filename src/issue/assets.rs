@@ -3,8 +3,10 @@
 //! Provides functionality to add, list, retrieve, and delete assets (images, videos, etc.)
 //! attached to issues. Assets can be either issue-specific or shared across all issues.
 
+use crate::feed::{notify_change, ChangeEntity, ChangeKind};
 use crate::manifest::{read_manifest, write_manifest, update_manifest_timestamp};
-use crate::utils::{get_centy_path, now_iso};
+use crate::utils::{get_centy_path, is_temp_file, lock_project, now_iso, write_atomic};
+use super::history::{asset_event, default_actor, record_history};
 use sha2::{Digest, Sha256};
 use std::path::Path;
 use thiserror::Error;
@@ -194,6 +196,7 @@ pub async fn add_asset(
     let manifest = read_manifest(project_path)
         .await?
         .ok_or(AssetError::NotInitialized)?;
+    let _lock = lock_project(project_path).await?;
 
     let centy_path = get_centy_path(project_path);
 
@@ -236,7 +239,13 @@ pub async fn add_asset(
     let created_at = now_iso();
 
     // Write the file
-    fs::write(&asset_path, &data).await?;
+    write_atomic(&asset_path, &data).await?;
+    notify_change(
+        project_path,
+        ChangeEntity::Asset,
+        ChangeKind::Created,
+        &format!("{}{}", manifest_base_path, sanitized_filename),
+    );
 
     // Update manifest timestamp
     let mut manifest = manifest;
@@ -252,6 +261,11 @@ pub async fn add_asset(
         created_at,
     };
 
+    if let (AssetScope::IssueSpecific, Some(id)) = (scope, issue_id) {
+        let event = asset_event(default_actor(project_path).as_deref(), None, Some(sanitized_filename.clone()));
+        record_history(project_path, id, &[event]).await;
+    }
+
     Ok(AddAssetResult {
         asset: asset_info,
         path: format!(".centy/{}{}", manifest_base_path, sanitized_filename),
@@ -293,7 +307,7 @@ pub async fn list_assets(
         let mut entries = fs::read_dir(&issue_assets_path).await?;
         while let Some(entry) = entries.next_entry().await? {
             if entry.file_type().await?.is_file() {
-                if let Some(filename) = entry.file_name().to_str() {
+                if let Some(filename) = entry.file_name().to_str().filter(|name| !is_temp_file(name)) {
                     let asset_path = entry.path();
                     let data = fs::read(&asset_path).await?;
                     let hash = compute_binary_hash(&data);
@@ -330,7 +344,7 @@ pub async fn list_assets(
             let mut entries = fs::read_dir(&shared_assets_path).await?;
             while let Some(entry) = entries.next_entry().await? {
                 if entry.file_type().await?.is_file() {
-                    if let Some(filename) = entry.file_name().to_str() {
+                    if let Some(filename) = entry.file_name().to_str().filter(|name| !is_temp_file(name)) {
                         let asset_path = entry.path();
                         let data = fs::read(&asset_path).await?;
                         let hash = compute_binary_hash(&data);
@@ -459,6 +473,7 @@ pub async fn delete_asset(
     let mut manifest = read_manifest(project_path)
         .await?
         .ok_or(AssetError::NotInitialized)?;
+    let _lock = lock_project(project_path).await?;
 
     let centy_path = get_centy_path(project_path);
     let sanitized_filename = sanitize_filename(filename)?;
@@ -486,11 +501,21 @@ pub async fn delete_asset(
 
     // Delete the file
     fs::remove_file(&asset_path).await?;
+    let asset_id = match (is_shared, issue_id) {
+        (false, Some(id)) => format!("issues/{}/assets/{}", id, sanitized_filename),
+        _ => format!("assets/{}", sanitized_filename),
+    };
+    notify_change(project_path, ChangeEntity::Asset, ChangeKind::Deleted, &asset_id);
 
     // Update manifest timestamp
     update_manifest_timestamp(&mut manifest);
     write_manifest(project_path, &manifest).await?;
 
+    if let (false, Some(id)) = (is_shared, issue_id) {
+        let event = asset_event(default_actor(project_path).as_deref(), Some(sanitized_filename.clone()), None);
+        record_history(project_path, id, &[event]).await;
+    }
+
     Ok(DeleteAssetResult {
         filename: sanitized_filename,
         was_shared: is_shared,
@@ -518,7 +543,7 @@ pub async fn list_shared_assets(project_path: &Path) -> Result<Vec<AssetInfo>, A
         let mut entries = fs::read_dir(&shared_assets_path).await?;
         while let Some(entry) = entries.next_entry().await? {
             if entry.file_type().await?.is_file() {
-                if let Some(filename) = entry.file_name().to_str() {
+                if let Some(filename) = entry.file_name().to_str().filter(|name| !is_temp_file(name)) {
                     let asset_path = entry.path();
                     let data = fs::read(&asset_path).await?;
                     let hash = compute_binary_hash(&data);
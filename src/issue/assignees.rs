@@ -0,0 +1,140 @@
+//! Issue assignees
+//!
+//! Assignees are stored on each issue by member handle. When the config
+//! defines a `members` list, assignees given by handle or email are resolved
+//! against it and anything else is rejected. Without a member list any value
+//! is stored as given.
+
+use crate::config::MemberDefinition;
+use crate::pr::git::get_git_user_email;
+use std::collections::HashSet;
+use std::path::Path;
+use thiserror::Error;
+use tracing::warn;
+
+#[derive(Error, Debug)]
+pub enum AssigneeError {
+    #[error("Unknown member: {0}")]
+    UnknownMember(String),
+}
+
+/// Find the member matching a handle or email (emails compare case-insensitively)
+pub fn find_member<'a>(members: &'a [MemberDefinition], value: &str) -> Option<&'a MemberDefinition> {
+    members.iter().find(|m| {
+        m.handle == value
+            || m
+                .email
+                .as_ref()
+                .is_some_and(|email| email.eq_ignore_ascii_case(value))
+    })
+}
+
+/// Resolve a single assignee to the stored form: the member handle when
+/// members are configured, the trimmed value otherwise.
+pub fn resolve_assignee(value: &str, members: &[MemberDefinition]) -> Result<String, AssigneeError> {
+    let value = value.trim();
+    if members.is_empty() {
+        return Ok(value.to_string());
+    }
+
+    find_member(members, value)
+        .map(|m| m.handle.clone())
+        .ok_or_else(|| AssigneeError::UnknownMember(value.to_string()))
+}
+
+/// Resolve assignees to their stored form, dropping empty values and duplicates
+pub fn resolve_assignees(
+    assignees: Vec<String>,
+    members: &[MemberDefinition],
+) -> Result<Vec<String>, AssigneeError> {
+    let mut seen = HashSet::new();
+    let mut resolved = Vec::new();
+
+    for assignee in assignees {
+        if assignee.trim().is_empty() {
+            continue;
+        }
+        let handle = resolve_assignee(&assignee, members)?;
+        if seen.insert(handle.clone()) {
+            resolved.push(handle);
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// Default assignee taken from the local git identity (`git config user.email`).
+///
+/// Returns `None` when git has no email configured or when members are
+/// configured and none of them matches it.
+pub fn git_default_assignee(project_path: &Path, members: &[MemberDefinition]) -> Option<String> {
+    let email = get_git_user_email(project_path).ok().flatten()?;
+
+    if members.is_empty() {
+        return Some(email);
+    }
+
+    match find_member(members, &email) {
+        Some(member) => Some(member.handle.clone()),
+        None => {
+            warn!(
+                email = %email,
+                "Git user '{}' is not a project member. Leaving issue unassigned.",
+                email
+            );
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn members() -> Vec<MemberDefinition> {
+        vec![
+            MemberDefinition {
+                name: "Alice Smith".to_string(),
+                email: Some("alice@example.com".to_string()),
+                handle: "alice".to_string(),
+            },
+            MemberDefinition {
+                name: "Bob".to_string(),
+                email: None,
+                handle: "bob".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_resolve_assignee_by_handle_or_email() {
+        let members = members();
+        assert_eq!(resolve_assignee("alice", &members).unwrap(), "alice");
+        assert_eq!(resolve_assignee("Alice@Example.com", &members).unwrap(), "alice");
+        assert_eq!(resolve_assignee(" bob ", &members).unwrap(), "bob");
+    }
+
+    #[test]
+    fn test_resolve_assignee_unknown_member() {
+        assert!(matches!(
+            resolve_assignee("carol", &members()),
+            Err(AssigneeError::UnknownMember(_))
+        ));
+    }
+
+    #[test]
+    fn test_resolve_assignee_without_members_keeps_value() {
+        assert_eq!(resolve_assignee(" anyone ", &[]).unwrap(), "anyone");
+    }
+
+    #[test]
+    fn test_resolve_assignees_deduplicates() {
+        let assignees = vec![
+            "alice".to_string(),
+            "alice@example.com".to_string(),
+            "".to_string(),
+            "bob".to_string(),
+        ];
+        assert_eq!(resolve_assignees(assignees, &members()).unwrap(), vec!["alice", "bob"]);
+    }
+}
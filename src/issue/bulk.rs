@@ -0,0 +1,226 @@
+use super::crud::{
+    apply_issue_update, get_issue, list_issues_filtered, remove_issue_files, Issue,
+    IssueCrudError, IssueFilter, UpdateIssueOptions,
+};
+use crate::config::read_config;
+use crate::manifest::{
+    read_manifest, update_manifest_timestamp, write_manifest, CentyManifest,
+};
+use crate::query::{apply_list_query, ListQuery, QueryContext};
+use crate::utils::lock_project;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Which issues a bulk operation applies to
+#[derive(Debug, Clone)]
+pub enum BulkTarget {
+    /// Explicit issue IDs, processed in the given order
+    Ids(Vec<String>),
+    /// Every issue matching the filter
+    Filter(IssueFilter),
+    /// Every issue matching both the filter and the query, in the query's
+    /// sort order. Paging fields are ignored.
+    Query(IssueFilter, ListQuery),
+}
+
+/// Changes applied to every issue in a bulk update. Unset fields are left alone.
+#[derive(Debug, Clone, Default)]
+pub struct BulkIssuePatch {
+    pub status: Option<String>,
+    pub priority: Option<u32>,
+    /// Custom fields to set. An empty value removes the field.
+    pub custom_fields: HashMap<String, String>,
+    pub add_labels: Vec<String>,
+    pub remove_labels: Vec<String>,
+    /// Who is making the change, for history. None = local git user.
+    pub actor: Option<String>,
+    /// Comment added to every updated issue
+    pub comment: Option<String>,
+}
+
+/// Outcome for a single issue in a bulk operation
+#[derive(Debug, Clone)]
+pub struct BulkItemResult {
+    pub issue_id: String,
+    /// None when the issue was updated or deleted
+    pub error: Option<String>,
+    pub warnings: Vec<String>,
+}
+
+impl BulkItemResult {
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BulkResult {
+    pub items: Vec<BulkItemResult>,
+    /// The manifest after the operation, or None when nothing changed
+    pub manifest: Option<CentyManifest>,
+}
+
+impl BulkResult {
+    pub fn succeeded_count(&self) -> u32 {
+        self.items.iter().filter(|i| i.succeeded()).count() as u32
+    }
+
+    pub fn failed_count(&self) -> u32 {
+        self.items.len() as u32 - self.succeeded_count()
+    }
+}
+
+/// Apply a patch to every targeted issue. A failure on one issue does not
+/// stop the others; the manifest is written once at the end.
+pub async fn bulk_update_issues(
+    project_path: &Path,
+    target: BulkTarget,
+    patch: BulkIssuePatch,
+) -> Result<BulkResult, IssueCrudError> {
+    let mut manifest = read_manifest(project_path)
+        .await?
+        .ok_or(IssueCrudError::NotInitialized)?;
+    let config = read_config(project_path).await.ok().flatten();
+
+    let mut items = Vec::new();
+    for resolved in resolve_target(project_path, target).await? {
+        let issue = match resolved {
+            Ok(issue) => issue,
+            Err(item) => {
+                items.push(item);
+                continue;
+            }
+        };
+        let options = patch_options(&patch, &issue);
+        let _lock = lock_project(project_path).await?;
+        items.push(
+            match apply_issue_update(project_path, &issue.id, options, config.as_ref()).await {
+                Ok((_, warnings)) => BulkItemResult {
+                    issue_id: issue.id,
+                    error: None,
+                    warnings,
+                },
+                Err(e) => failed(issue.id, &e),
+            },
+        );
+    }
+
+    let manifest = if items.iter().any(BulkItemResult::succeeded) {
+        update_manifest_timestamp(&mut manifest);
+        write_manifest(project_path, &manifest).await?;
+        Some(manifest)
+    } else {
+        None
+    };
+
+    Ok(BulkResult { items, manifest })
+}
+
+/// Delete every targeted issue. A failure on one issue does not stop the
+/// others; the manifest is written once at the end.
+pub async fn bulk_delete_issues(
+    project_path: &Path,
+    target: BulkTarget,
+) -> Result<BulkResult, IssueCrudError> {
+    let mut manifest = read_manifest(project_path)
+        .await?
+        .ok_or(IssueCrudError::NotInitialized)?;
+
+    let mut items = Vec::new();
+    for resolved in resolve_target(project_path, target).await? {
+        let issue = match resolved {
+            Ok(issue) => issue,
+            Err(item) => {
+                items.push(item);
+                continue;
+            }
+        };
+        let _lock = lock_project(project_path).await?;
+        items.push(match remove_issue_files(project_path, &issue.id, true).await {
+            Ok(()) => BulkItemResult {
+                issue_id: issue.id,
+                error: None,
+                warnings: Vec::new(),
+            },
+            Err(e) => failed(issue.id, &e),
+        });
+    }
+
+    let manifest = if items.iter().any(BulkItemResult::succeeded) {
+        update_manifest_timestamp(&mut manifest);
+        write_manifest(project_path, &manifest).await?;
+        Some(manifest)
+    } else {
+        None
+    };
+
+    Ok(BulkResult { items, manifest })
+}
+
+/// Load the targeted issues. IDs that cannot be read become failed items.
+async fn resolve_target(
+    project_path: &Path,
+    target: BulkTarget,
+) -> Result<Vec<Result<Issue, BulkItemResult>>, IssueCrudError> {
+    match target {
+        BulkTarget::Filter(filter) => Ok(list_issues_filtered(project_path, &filter)
+            .await?
+            .into_iter()
+            .map(Ok)
+            .collect()),
+        BulkTarget::Query(filter, query) => {
+            let issues = list_issues_filtered(project_path, &filter).await?;
+            let query = ListQuery {
+                page_size: 0,
+                page_token: String::new(),
+                ..query
+            };
+            let context = QueryContext::for_query(project_path, &query).await;
+            let page = apply_list_query(issues, &query, &context)?;
+            Ok(page.items.into_iter().map(Ok).collect())
+        }
+        BulkTarget::Ids(ids) => {
+            let mut resolved = Vec::with_capacity(ids.len());
+            for id in ids {
+                resolved.push(get_issue(project_path, &id).await.map_err(|e| failed(id, &e)));
+            }
+            Ok(resolved)
+        }
+    }
+}
+
+fn patch_options(patch: &BulkIssuePatch, issue: &Issue) -> UpdateIssueOptions {
+    let labels = if patch.add_labels.is_empty() && patch.remove_labels.is_empty() {
+        None
+    } else {
+        let mut labels: Vec<String> = issue
+            .metadata
+            .labels
+            .iter()
+            .filter(|l| !patch.remove_labels.contains(l))
+            .cloned()
+            .collect();
+        labels.extend(patch.add_labels.iter().cloned());
+        Some(labels)
+    };
+
+    UpdateIssueOptions {
+        status: patch.status.clone(),
+        priority: patch.priority,
+        custom_fields: patch.custom_fields.clone(),
+        labels,
+        actor: patch.actor.clone(),
+        comment: patch.comment.clone(),
+        // Labels were computed from this read of the issue
+        expected_updated_at: Some(issue.metadata.updated_at.clone()),
+        ..Default::default()
+    }
+}
+
+fn failed(issue_id: String, error: &IssueCrudError) -> BulkItemResult {
+    BulkItemResult {
+        issue_id,
+        error: Some(error.to_string()),
+        warnings: Vec::new(),
+    }
+}
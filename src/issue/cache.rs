@@ -0,0 +1,273 @@
+//! In-memory copy of each project's issues, so listing issues and looking
+//! them up by display number do not read every issue folder.
+//!
+//! Entries are refreshed lazily. Daemon writes (reported through
+//! `notify_change`) and the project's filesystem watcher mark single issues
+//! stale, and the next read re-reads only those folders. Display numbers are
+//! reconciled only when a refresh brings in a new issue or a changed number,
+//! and only under the project lock; while a writer holds it the duplicates
+//! are served as they are and the next read tries again.
+//!
+//! Only active issues are cached. Archived ones are read from disk when a
+//! caller asks for them, so a large archive stays out of the hot path.
+
+use crate::feed::{watch_disk, DiskWatchGuard};
+use crate::search::invalidate_project;
+use crate::utils::{get_centy_path, try_lock_project};
+use super::archive::archived_issues_path;
+use super::crud::{read_issue_from_disk, Issue, IssueCrudError};
+use super::id::is_valid_issue_folder;
+use super::reconcile::reconcile_display_numbers;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+use tokio::fs;
+use tracing::warn;
+
+/// Projects kept in memory before the least recently read one is dropped.
+/// Each holds a filesystem watcher, which the OS limits per process.
+const MAX_CACHED_PROJECTS: usize = 64;
+
+struct ProjectIssues {
+    /// Active issues by id
+    issues: HashMap<String, Issue>,
+    /// Whether `issues` holds a full read of the project
+    loaded: bool,
+    /// Issues changed since they were read
+    stale: HashSet<String>,
+    /// Bumped whenever the whole project is invalidated, so a refresh that
+    /// started before does not store outdated issues
+    epoch: u64,
+    last_read: Instant,
+    _watch: DiskWatchGuard,
+}
+
+static CACHE: OnceLock<Mutex<HashMap<PathBuf, ProjectIssues>>> = OnceLock::new();
+
+fn with_cache<R>(f: impl FnOnce(&mut HashMap<PathBuf, ProjectIssues>) -> R) -> R {
+    let mut cache = CACHE
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    f(&mut cache)
+}
+
+/// Canonicalize the project path so different spellings share one entry
+fn project_key(project_path: &Path) -> PathBuf {
+    project_path
+        .canonicalize()
+        .unwrap_or_else(|_| project_path.to_path_buf())
+}
+
+/// Mark one issue as changed so the next read picks it up from disk
+pub(crate) fn invalidate_issue(project_path: &Path, issue_id: &str) {
+    let key = project_key(project_path);
+    with_cache(|cache| {
+        if let Some(entry) = cache.get_mut(&key) {
+            entry.stale.insert(issue_id.to_string());
+        }
+    });
+}
+
+/// Drop a project's issues so the next read loads them all from disk.
+/// Used when many issues change at once (e.g. migrations).
+pub(crate) fn invalidate_issues(project_path: &Path) {
+    let key = project_key(project_path);
+    with_cache(|cache| {
+        if let Some(entry) = cache.get_mut(&key) {
+            entry.issues.clear();
+            entry.stale.clear();
+            entry.loaded = false;
+            entry.epoch += 1;
+        }
+    });
+}
+
+enum Refresh {
+    Full,
+    Stale(Vec<String>),
+}
+
+/// All active issues of a project, in no particular order
+pub(super) async fn cached_issues(project_path: &Path) -> Result<Vec<Issue>, IssueCrudError> {
+    let key = project_key(project_path);
+    let Some((refresh, epoch)) = begin_refresh(&key) else {
+        // Without a watcher outside edits would go unnoticed, so read from disk
+        return load_issues(project_path).await;
+    };
+
+    match refresh {
+        Refresh::Full => {
+            let issues = load_issues(project_path).await?;
+            // Unreconciled duplicates leave the entry unloaded, so the next
+            // read loads and reconciles again
+            let reconciled = !has_duplicate_numbers(&issues);
+            with_cache(|cache| {
+                if let Some(entry) = cache.get_mut(&key).filter(|e| e.epoch == epoch) {
+                    entry.issues = issues.iter().map(|i| (i.id.clone(), i.clone())).collect();
+                    entry.loaded = reconciled;
+                }
+            });
+            Ok(issues)
+        }
+        Refresh::Stale(ids) => {
+            let mut changed = Vec::with_capacity(ids.len());
+            for id in ids {
+                changed.push((read_issue(project_path, &id).await, id));
+            }
+
+            let result = with_cache(|cache| {
+                let entry = cache.get_mut(&key).filter(|e| e.epoch == epoch)?;
+                let mut renumbered = false;
+                for (issue, id) in changed {
+                    match issue {
+                        Some(issue) => {
+                            let previous = entry.issues.insert(id, issue.clone());
+                            renumbered |= previous.is_none_or(|p| {
+                                p.metadata.display_number != issue.metadata.display_number
+                            });
+                        }
+                        None => {
+                            entry.issues.remove(&id);
+                        }
+                    }
+                }
+                let issues: Vec<Issue> = entry.issues.values().cloned().collect();
+                if renumbered && has_duplicate_numbers(&issues) {
+                    entry.loaded = false;
+                    entry.epoch += 1;
+                    return Some(None);
+                }
+                Some(Some(issues))
+            });
+
+            match result {
+                Some(Some(issues)) => Ok(issues),
+                // Duplicates need reconciling, which rewrites other issues too
+                Some(None) => Box::pin(cached_issues(project_path)).await,
+                None => load_issues(project_path).await,
+            }
+        }
+    }
+}
+
+/// Find or create the project's entry and decide what needs reading
+fn begin_refresh(key: &Path) -> Option<(Refresh, u64)> {
+    with_cache(|cache| {
+        if !cache.contains_key(key) {
+            if cache.len() >= MAX_CACHED_PROJECTS {
+                let oldest = cache
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_read)
+                    .map(|(path, _)| path.clone());
+                if let Some(oldest) = oldest {
+                    // Its watcher goes too, and nothing else would tell the
+                    // search index about outside edits
+                    cache.remove(&oldest);
+                    invalidate_project(&oldest);
+                }
+            }
+
+            // Start watching before the first read so no edit slips through
+            let watch = match watch_disk(key) {
+                Ok(watch) => watch,
+                Err(e) => {
+                    warn!(error = %e, project = %key.display(), "Cannot watch project, issues will not be cached");
+                    return None;
+                }
+            };
+            cache.insert(
+                key.to_path_buf(),
+                ProjectIssues {
+                    issues: HashMap::new(),
+                    loaded: false,
+                    stale: HashSet::new(),
+                    epoch: 0,
+                    last_read: Instant::now(),
+                    _watch: watch,
+                },
+            );
+        }
+
+        let entry = cache.get_mut(key)?;
+        entry.last_read = Instant::now();
+        let refresh = if entry.loaded {
+            Refresh::Stale(entry.stale.drain().collect())
+        } else {
+            entry.stale.clear();
+            Refresh::Full
+        };
+        Some((refresh, entry.epoch))
+    })
+}
+
+/// Read every issue from disk, reconciling display numbers if two active
+/// issues share one and the project lock is free.
+///
+/// Reconciling rewrites other issues' metadata, so it must not race a locked
+/// writer. Callers may already hold the lock themselves, hence `try_lock`.
+async fn load_issues(project_path: &Path) -> Result<Vec<Issue>, IssueCrudError> {
+    let issues_path = get_centy_path(project_path).join("issues");
+    let mut issues = read_issue_dir(&issues_path).await?;
+
+    if has_duplicate_numbers(&issues) {
+        if let Some(_lock) = try_lock_project(project_path).await? {
+            if reconcile_display_numbers(&issues_path).await? > 0 {
+                invalidate_project(project_path);
+                issues = read_issue_dir(&issues_path).await?;
+            }
+        }
+    }
+
+    Ok(issues)
+}
+
+/// All archived issues of a project, read from disk on every call
+pub(super) async fn archived_issues(project_path: &Path) -> Result<Vec<Issue>, IssueCrudError> {
+    read_issue_dir(&archived_issues_path(project_path)).await
+}
+
+async fn read_issue_dir(dir: &Path) -> Result<Vec<Issue>, IssueCrudError> {
+    let mut issues = Vec::new();
+    if !dir.exists() {
+        return Ok(issues);
+    }
+
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        if !entry.file_type().await?.is_dir() {
+            continue;
+        }
+        let Some(folder_name) = entry.file_name().to_str().map(String::from) else {
+            continue;
+        };
+        // Accept both UUID and legacy 4-digit format
+        if !is_valid_issue_folder(&folder_name) {
+            continue;
+        }
+        match read_issue_from_disk(&entry.path(), &folder_name).await {
+            Ok(issue) => issues.push(issue),
+            // Left out of lists until repaired; CheckProject reports why
+            Err(e) => warn!(issue = %folder_name, error = %e, "Skipping unreadable issue"),
+        }
+    }
+
+    Ok(issues)
+}
+
+/// Read one active issue, or None if it is gone or was archived
+async fn read_issue(project_path: &Path, issue_id: &str) -> Option<Issue> {
+    let path = get_centy_path(project_path).join("issues").join(issue_id);
+    if !path.exists() {
+        return None;
+    }
+    read_issue_from_disk(&path, issue_id).await.ok()
+}
+
+/// Whether two active issues share a display number. Several legacy issues
+/// without one (0) count too, as reconciliation numbers them.
+fn has_duplicate_numbers(issues: &[Issue]) -> bool {
+    let mut seen = HashSet::new();
+    issues.iter().any(|i| !seen.insert(i.metadata.display_number))
+}
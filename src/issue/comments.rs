@@ -0,0 +1,368 @@
+//! Threaded comments on issues
+//!
+//! Each comment is stored as its own JSON file in `.centy/issues/{id}/comments/`,
+//! named after the comment's UUID. One file per comment keeps concurrent
+//! discussions from different machines free of merge conflicts.
+
+use crate::feed::{notify_change, ChangeEntity, ChangeKind};
+use crate::manifest::{read_manifest, write_manifest, update_manifest_timestamp, CentyManifest};
+use crate::utils::{get_centy_path, lock_project, now_iso, write_atomic};
+use super::id::is_valid_issue_folder;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use tokio::fs;
+use uuid::Uuid;
+
+/// A comment on an issue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Comment {
+    /// Comment UUID
+    pub id: String,
+    /// Who wrote the comment
+    pub author: String,
+    /// Markdown body
+    pub body: String,
+    /// ID of the comment this one replies to (None = top-level)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Errors that can occur during comment operations
+#[derive(Error, Debug)]
+pub enum CommentError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Manifest error: {0}")]
+    ManifestError(#[from] crate::manifest::ManifestError),
+
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("Centy not initialized. Run 'centy init' first.")]
+    NotInitialized,
+
+    #[error("Issue not found: {0}")]
+    IssueNotFound(String),
+
+    #[error("Comment not found: {0}")]
+    CommentNotFound(String),
+
+    #[error("Reply target not found: {0}")]
+    ReplyTargetNotFound(String),
+
+    #[error("Comment author is required")]
+    AuthorRequired,
+
+    #[error("Comment body is required")]
+    BodyRequired,
+}
+
+/// Options for adding a comment
+#[derive(Debug, Clone, Default)]
+pub struct AddCommentOptions {
+    pub author: String,
+    pub body: String,
+    /// ID of the comment being replied to
+    pub reply_to: Option<String>,
+}
+
+/// Result of adding a comment
+#[derive(Debug, Clone)]
+pub struct AddCommentResult {
+    pub comment: Comment,
+    pub manifest: CentyManifest,
+}
+
+/// Result of updating a comment
+#[derive(Debug, Clone)]
+pub struct UpdateCommentResult {
+    pub comment: Comment,
+    pub manifest: CentyManifest,
+}
+
+/// Result of deleting a comment
+#[derive(Debug, Clone)]
+pub struct DeleteCommentResult {
+    /// IDs of all removed comments (the comment itself plus its replies)
+    pub deleted_ids: Vec<String>,
+    pub manifest: CentyManifest,
+}
+
+/// Resolve the comments directory of an issue, checking that the issue exists
+fn comments_dir(project_path: &Path, issue_id: &str) -> Result<PathBuf, CommentError> {
+    let issue_path = get_centy_path(project_path).join("issues").join(issue_id);
+    if !is_valid_issue_folder(issue_id) || !issue_path.exists() {
+        return Err(CommentError::IssueNotFound(issue_id.to_string()));
+    }
+    Ok(issue_path.join("comments"))
+}
+
+/// Path of a single comment file. Rejects IDs that aren't UUIDs so they can't
+/// escape the comments directory.
+fn comment_path(dir: &Path, comment_id: &str) -> Result<PathBuf, CommentError> {
+    if Uuid::parse_str(comment_id).is_err() {
+        return Err(CommentError::CommentNotFound(comment_id.to_string()));
+    }
+    Ok(dir.join(format!("{comment_id}.json")))
+}
+
+async fn read_comment(path: &Path) -> Result<Comment, CommentError> {
+    let content = fs::read_to_string(path).await?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+async fn write_comment(dir: &Path, comment: &Comment) -> Result<(), CommentError> {
+    fs::create_dir_all(dir).await?;
+    let path = dir.join(format!("{}.json", comment.id));
+    write_atomic(&path, serde_json::to_string_pretty(comment)?).await?;
+    Ok(())
+}
+
+/// Read every comment in a comments directory, oldest first
+async fn read_all_comments(dir: &Path) -> Result<Vec<Comment>, CommentError> {
+    let mut comments = Vec::new();
+
+    if !dir.exists() {
+        return Ok(comments);
+    }
+
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            match read_comment(&path).await {
+                Ok(comment) => comments.push(comment),
+                Err(_) => continue, // Skip comments that can't be read
+            }
+        }
+    }
+
+    comments.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+
+    Ok(comments)
+}
+
+/// Add a comment to an issue
+pub async fn add_comment(
+    project_path: &Path,
+    issue_id: &str,
+    options: AddCommentOptions,
+) -> Result<AddCommentResult, CommentError> {
+    // Check if centy is initialized
+    let mut manifest = read_manifest(project_path)
+        .await?
+        .ok_or(CommentError::NotInitialized)?;
+    let _lock = lock_project(project_path).await?;
+
+    let comment = insert_comment(project_path, issue_id, options).await?;
+
+    // Update manifest timestamp
+    update_manifest_timestamp(&mut manifest);
+    write_manifest(project_path, &manifest).await?;
+
+    Ok(AddCommentResult { comment, manifest })
+}
+
+/// Validate and write a new comment without touching the manifest.
+/// The caller must hold the project lock.
+pub(crate) async fn insert_comment(
+    project_path: &Path,
+    issue_id: &str,
+    options: AddCommentOptions,
+) -> Result<Comment, CommentError> {
+    let author = options.author.trim();
+    if author.is_empty() {
+        return Err(CommentError::AuthorRequired);
+    }
+    if options.body.trim().is_empty() {
+        return Err(CommentError::BodyRequired);
+    }
+
+    let dir = comments_dir(project_path, issue_id)?;
+
+    let reply_to = match options.reply_to {
+        Some(parent_id) if !parent_id.is_empty() => {
+            let parent_path = comment_path(&dir, &parent_id)
+                .map_err(|_| CommentError::ReplyTargetNotFound(parent_id.clone()))?;
+            if !parent_path.exists() {
+                return Err(CommentError::ReplyTargetNotFound(parent_id));
+            }
+            Some(parent_id)
+        }
+        _ => None,
+    };
+
+    let now = now_iso();
+    let comment = Comment {
+        id: Uuid::new_v4().to_string(),
+        author: author.to_string(),
+        body: options.body,
+        reply_to,
+        created_at: now.clone(),
+        updated_at: now,
+    };
+
+    write_comment(&dir, &comment).await?;
+    notify_change(project_path, ChangeEntity::Issue, ChangeKind::Updated, issue_id);
+
+    Ok(comment)
+}
+
+/// List all comments on an issue, oldest first.
+/// Replies reference their parent through `reply_to`.
+pub async fn list_comments(
+    project_path: &Path,
+    issue_id: &str,
+) -> Result<Vec<Comment>, CommentError> {
+    // Check if centy is initialized
+    read_manifest(project_path)
+        .await?
+        .ok_or(CommentError::NotInitialized)?;
+
+    let dir = comments_dir(project_path, issue_id)?;
+    read_all_comments(&dir).await
+}
+
+/// Replace the body of a comment
+pub async fn update_comment(
+    project_path: &Path,
+    issue_id: &str,
+    comment_id: &str,
+    body: String,
+) -> Result<UpdateCommentResult, CommentError> {
+    if body.trim().is_empty() {
+        return Err(CommentError::BodyRequired);
+    }
+
+    // Check if centy is initialized
+    let mut manifest = read_manifest(project_path)
+        .await?
+        .ok_or(CommentError::NotInitialized)?;
+    let _lock = lock_project(project_path).await?;
+
+    let dir = comments_dir(project_path, issue_id)?;
+    let path = comment_path(&dir, comment_id)?;
+    if !path.exists() {
+        return Err(CommentError::CommentNotFound(comment_id.to_string()));
+    }
+
+    let mut comment = read_comment(&path).await?;
+    comment.body = body;
+    comment.updated_at = now_iso();
+
+    write_comment(&dir, &comment).await?;
+    notify_change(project_path, ChangeEntity::Issue, ChangeKind::Updated, issue_id);
+
+    // Update manifest timestamp
+    update_manifest_timestamp(&mut manifest);
+    write_manifest(project_path, &manifest).await?;
+
+    Ok(UpdateCommentResult { comment, manifest })
+}
+
+/// Delete a comment together with every reply below it
+pub async fn delete_comment(
+    project_path: &Path,
+    issue_id: &str,
+    comment_id: &str,
+) -> Result<DeleteCommentResult, CommentError> {
+    // Check if centy is initialized
+    let mut manifest = read_manifest(project_path)
+        .await?
+        .ok_or(CommentError::NotInitialized)?;
+    let _lock = lock_project(project_path).await?;
+
+    let dir = comments_dir(project_path, issue_id)?;
+    let path = comment_path(&dir, comment_id)?;
+    if !path.exists() {
+        return Err(CommentError::CommentNotFound(comment_id.to_string()));
+    }
+
+    let comments = read_all_comments(&dir).await?;
+    let deleted_ids = collect_thread(&comments, comment_id);
+
+    for id in &deleted_ids {
+        fs::remove_file(dir.join(format!("{id}.json"))).await?;
+    }
+    notify_change(project_path, ChangeEntity::Issue, ChangeKind::Updated, issue_id);
+
+    // Update manifest timestamp
+    update_manifest_timestamp(&mut manifest);
+    write_manifest(project_path, &manifest).await?;
+
+    Ok(DeleteCommentResult {
+        deleted_ids,
+        manifest,
+    })
+}
+
+/// Collect a comment ID and the IDs of all its transitive replies
+fn collect_thread(comments: &[Comment], root_id: &str) -> Vec<String> {
+    let mut ids = vec![root_id.to_string()];
+    let mut seen: HashSet<String> = ids.iter().cloned().collect();
+    let mut i = 0;
+
+    while i < ids.len() {
+        let parent = ids[i].clone();
+        for comment in comments {
+            if comment.reply_to.as_deref() == Some(parent.as_str()) && seen.insert(comment.id.clone()) {
+                ids.push(comment.id.clone());
+            }
+        }
+        i += 1;
+    }
+
+    ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment(id: &str, reply_to: Option<&str>) -> Comment {
+        Comment {
+            id: id.to_string(),
+            author: "alice".to_string(),
+            body: "body".to_string(),
+            reply_to: reply_to.map(String::from),
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_collect_thread_includes_nested_replies() {
+        let comments = vec![
+            comment("a", None),
+            comment("b", Some("a")),
+            comment("c", Some("b")),
+            comment("d", None),
+            comment("e", Some("d")),
+        ];
+
+        assert_eq!(collect_thread(&comments, "a"), vec!["a", "b", "c"]);
+        assert_eq!(collect_thread(&comments, "c"), vec!["c"]);
+    }
+
+    #[test]
+    fn test_comment_path_rejects_non_uuid() {
+        let dir = Path::new("/tmp/comments");
+        assert!(comment_path(dir, "../metadata").is_err());
+        assert!(comment_path(dir, "550e8400-e29b-41d4-a716-446655440000").is_ok());
+    }
+
+    #[test]
+    fn test_comment_serialization_omits_missing_reply_to() {
+        let json = serde_json::to_string(&comment("a", None)).unwrap();
+        assert!(!json.contains("replyTo"));
+
+        let json = serde_json::to_string(&comment("b", Some("a"))).unwrap();
+        assert!(json.contains("\"replyTo\":\"a\""));
+    }
+}
@@ -0,0 +1,122 @@
+//! Replacing a project's config
+//!
+//! Some config changes reach into the issues: labels dropped from the config
+//! are stripped from the issues that carry them, and a changed number of
+//! priority levels can be mapped onto existing issues and PRs. Those
+//! follow-up writes happen under the same project lock as the config write,
+//! and are worked out against the config as it was right before it.
+
+use crate::config::{read_config, write_config_if_unchanged, CentyConfig, ConfigError};
+use crate::manifest::read_manifest;
+use crate::utils::{get_centy_path, lock_project, write_atomic};
+use super::labels::{remove_dropped_labels, LabelError};
+use super::rescale::{apply_priority_rescale, validate_levels, PriorityChange, RescaleError};
+use std::path::Path;
+use thiserror::Error;
+use tokio::fs;
+
+#[derive(Error, Debug)]
+pub enum ConfigUpdateError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Manifest error: {0}")]
+    ManifestError(#[from] crate::manifest::ManifestError),
+
+    #[error(transparent)]
+    ConfigError(#[from] ConfigError),
+
+    #[error("Label error: {0}")]
+    LabelError(#[from] LabelError),
+
+    #[error("Rescale error: {0}")]
+    RescaleError(#[from] RescaleError),
+
+    #[error("Centy not initialized. Run 'centy init' first.")]
+    NotInitialized,
+}
+
+/// Result of replacing the config
+#[derive(Debug, Clone)]
+pub struct ConfigUpdateResult {
+    /// Etag of the written config
+    pub etag: String,
+    /// IDs of issues that lost a label the new config no longer defines
+    pub stripped_issue_ids: Vec<String>,
+    /// Priorities mapped onto the new number of levels (empty unless asked for)
+    pub priority_changes: Vec<PriorityChange>,
+}
+
+/// Replace the project config unless it changed since `expected_etag` was
+/// read (None = no check), then strip labels it no longer defines from
+/// every issue. With `rescale_priorities`, a changed `priority_levels` is
+/// also applied to every issue and PR priority; if that fails, the previous
+/// config is put back.
+///
+/// Labels are matched by name, so renaming a label here removes it from its
+/// issues. Use `rename_label` to rename a label and keep it on them.
+pub async fn update_config(
+    project_path: &Path,
+    config: &CentyConfig,
+    expected_etag: Option<&str>,
+    rescale_priorities: bool,
+) -> Result<ConfigUpdateResult, ConfigUpdateError> {
+    let mut manifest = read_manifest(project_path)
+        .await?
+        .ok_or(ConfigUpdateError::NotInitialized)?;
+
+    let _lock = lock_project(project_path).await?;
+    let previous = read_config(project_path)
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+
+    let rescale = rescale_priorities && previous.priority_levels != config.priority_levels;
+    if rescale {
+        validate_levels(previous.priority_levels)?;
+        validate_levels(config.priority_levels)?;
+    }
+
+    let config_path = get_centy_path(project_path).join("config.json");
+    let previous_bytes = match fs::read(&config_path).await {
+        Ok(bytes) => Some(bytes),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+
+    let etag = write_config_if_unchanged(project_path, config, expected_etag).await?;
+
+    let priority_changes = if rescale {
+        match apply_priority_rescale(
+            project_path,
+            &mut manifest,
+            previous.priority_levels,
+            config.priority_levels,
+        )
+        .await
+        {
+            Ok(changes) => changes,
+            Err(e) => {
+                // The rescale wrote no item, so keep the config on the scale
+                // the stored priorities still use
+                match previous_bytes {
+                    Some(bytes) => write_atomic(&config_path, bytes).await?,
+                    None => fs::remove_file(&config_path).await?,
+                }
+                return Err(e.into());
+            }
+        }
+    } else {
+        Vec::new()
+    };
+
+    let stripped_issue_ids =
+        remove_dropped_labels(project_path, &previous.labels, &config.labels).await?;
+
+    Ok(ConfigUpdateResult {
+        etag,
+        stripped_issue_ids,
+        priority_changes,
+    })
+}
@@ -1,10 +1,19 @@
-use crate::config::read_config;
+use crate::feed::{notify_change, ChangeEntity, ChangeKind};
+use crate::config::{read_config, WorkflowError};
+use crate::milestone::milestone_exists;
 use crate::manifest::{
     read_manifest, write_manifest, update_manifest_timestamp, CentyManifest,
 };
+use crate::search::index_issue;
 use crate::template::{IssueTemplateContext, TemplateEngine, TemplateError};
-use crate::utils::get_centy_path;
-use super::id::generate_issue_id;
+use crate::utils::{get_centy_path, lock_project, write_files_atomic};
+use super::assignees::{git_default_assignee, resolve_assignees, AssigneeError};
+use super::crud::read_issue_from_disk;
+use super::custom_fields::{apply_custom_fields, CustomFieldError};
+use super::dates::validate_dates;
+use super::history::{default_actor, diff_issue, record_history};
+use super::id::{generate_issue_id, is_valid_issue_folder};
+use super::labels::{normalize_labels, validate_labels, LabelError};
 use super::metadata::IssueMetadata;
 use super::priority::{default_priority, priority_label, validate_priority, PriorityError};
 use super::reconcile::{get_next_display_number, ReconcileError};
@@ -39,6 +48,27 @@ pub enum IssueError {
 
     #[error("Reconcile error: {0}")]
     ReconcileError(#[from] ReconcileError),
+
+    #[error("Label error: {0}")]
+    LabelError(#[from] LabelError),
+
+    #[error("Assignee error: {0}")]
+    AssigneeError(#[from] AssigneeError),
+
+    #[error("Parent issue {0} not found")]
+    ParentNotFound(String),
+
+    #[error("Workflow error: {0}")]
+    WorkflowError(#[from] WorkflowError),
+
+    #[error("{0}")]
+    CustomFieldError(#[from] CustomFieldError),
+
+    #[error("Invalid date: {0}")]
+    InvalidDate(String),
+
+    #[error("Milestone '{0}' not found")]
+    MilestoneNotFound(String),
 }
 
 /// Options for creating an issue
@@ -52,6 +82,21 @@ pub struct CreateIssueOptions {
     pub custom_fields: HashMap<String, String>,
     /// Optional template name (without .md extension)
     pub template: Option<String>,
+    /// Label names (must be defined in config when config defines labels)
+    pub labels: Vec<String>,
+    /// Member handles or emails. Empty = unassigned, or the local git user
+    /// when `assign_git_user_by_default` is enabled in config.
+    pub assignees: Vec<String>,
+    /// Parent issue ID. None = top-level issue.
+    pub parent_id: Option<String>,
+    /// Who is creating the issue, for history. None = local git user.
+    pub actor: Option<String>,
+    /// Planned start day (YYYY-MM-DD)
+    pub start_date: Option<String>,
+    /// Due day (YYYY-MM-DD)
+    pub due_date: Option<String>,
+    /// Milestone slug. None = no milestone.
+    pub milestone: Option<String>,
 }
 
 /// Result of issue creation
@@ -91,10 +136,20 @@ pub async fn create_issue(
         fs::create_dir_all(&issues_path).await?;
     }
 
+    if let Some(ref parent_id) = options.parent_id {
+        if !is_valid_issue_folder(parent_id)
+            || !issues_path.join(parent_id).join("metadata.json").exists()
+        {
+            return Err(IssueError::ParentNotFound(parent_id.clone()));
+        }
+    }
+
     // Generate UUID for folder name (prevents git conflicts)
     let issue_id = generate_issue_id();
 
-    // Get next display number for human-readable reference
+    // Get next display number for human-readable reference. The lock is held
+    // until the issue is on disk, so no other writer can take the same number.
+    let _lock = lock_project(project_path).await?;
     let display_number = get_next_display_number(&issues_path).await?;
 
     // Read config for defaults and priority_levels
@@ -125,33 +180,56 @@ pub async fn create_issue(
             .unwrap_or_else(|| "open".to_string())
     });
 
-    // Lenient validation: log warning if status is not in allowed_states
+    // Lenient validation: log warning if status is not in allowed_states.
+    // A strict workflow rejects it instead.
     if let Some(ref config) = config {
+        let workflow = &config.workflow;
+        workflow.enforce(workflow.state_violations(&status, &config.allowed_states))?;
         validate_status(&status, &config.allowed_states);
     }
 
-    // Build custom fields with defaults from config
-    let mut custom_field_values: HashMap<String, serde_json::Value> = HashMap::new();
-
+    let labels = normalize_labels(options.labels.clone());
     if let Some(ref config) = config {
-        // Apply defaults from config
-        for field in &config.custom_fields {
-            if let Some(default_value) = &field.default_value {
-                custom_field_values.insert(
-                    field.name.clone(),
-                    serde_json::Value::String(default_value.clone()),
-                );
-            }
+        validate_labels(&labels, &config.labels)?;
+    }
+
+    let (start_date, due_date) =
+        validate_dates(options.start_date.as_deref(), options.due_date.as_deref())
+            .map_err(IssueError::InvalidDate)?;
+
+    let milestone = options.milestone.clone().filter(|m| !m.is_empty());
+    if let Some(ref milestone) = milestone {
+        if !milestone_exists(project_path, milestone) {
+            return Err(IssueError::MilestoneNotFound(milestone.clone()));
         }
     }
 
-    // Override with provided custom fields
-    for (key, value) in &options.custom_fields {
-        custom_field_values.insert(key.clone(), serde_json::Value::String(value.clone()));
+    // Resolve assignees against the member list, falling back to the git user if configured
+    let members = config.as_ref().map(|c| c.members.as_slice()).unwrap_or_default();
+    let mut assignees = resolve_assignees(options.assignees.clone(), members)?;
+    if assignees.is_empty() && config.as_ref().is_some_and(|c| c.assign_git_user_by_default) {
+        assignees.extend(git_default_assignee(project_path, members));
     }
 
+    // Build custom fields from config defaults overridden by the provided
+    // values, then convert them to their declared types
+    let definitions = config.as_ref().map(|c| c.custom_fields.as_slice()).unwrap_or_default();
+    let mut raw_custom_fields: HashMap<String, String> = definitions
+        .iter()
+        .filter_map(|field| Some((field.name.clone(), field.default_value.clone()?)))
+        .collect();
+    raw_custom_fields.extend(options.custom_fields.clone());
+    let custom_field_values =
+        apply_custom_fields(HashMap::new(), &raw_custom_fields, definitions, members, true)?;
+
     // Create metadata
-    let metadata = IssueMetadata::new(display_number, status.clone(), priority, custom_field_values);
+    let mut metadata = IssueMetadata::new(display_number, status.clone(), priority, custom_field_values);
+    metadata.labels = labels;
+    metadata.assignees = assignees;
+    metadata.parent_id = options.parent_id.clone();
+    metadata.start_date = start_date;
+    metadata.due_date = due_date;
+    metadata.milestone = milestone;
 
     // Create issue content
     let issue_md = if let Some(ref template_name) = options.template {
@@ -182,8 +260,13 @@ pub async fn create_issue(
     let metadata_path = issue_folder.join("metadata.json");
     let assets_path = issue_folder.join("assets");
 
-    fs::write(&issue_md_path, &issue_md).await?;
-    fs::write(&metadata_path, serde_json::to_string_pretty(&metadata)?).await?;
+    // Metadata last: without it the folder is not read as an issue
+    let metadata_json = serde_json::to_string_pretty(&metadata)?;
+    write_files_atomic(&[
+        (issue_md_path.as_path(), issue_md.as_bytes()),
+        (metadata_path.as_path(), metadata_json.as_bytes()),
+    ])
+    .await?;
     fs::create_dir_all(&assets_path).await?;
 
     // Update manifest timestamp
@@ -191,6 +274,14 @@ pub async fn create_issue(
     update_manifest_timestamp(&mut manifest);
     write_manifest(project_path, &manifest).await?;
 
+    if let Ok(issue) = read_issue_from_disk(&issue_folder, &issue_id).await {
+        index_issue(project_path, &issue);
+
+        let actor = options.actor.clone().or_else(|| default_actor(project_path));
+        record_history(project_path, &issue_id, &diff_issue(None, &issue, actor.as_deref())).await;
+    }
+    notify_change(project_path, ChangeEntity::Issue, ChangeKind::Created, &issue_id);
+
     let created_files = vec![
         format!(".centy/issues/{}/issue.md", issue_id),
         format!(".centy/issues/{}/metadata.json", issue_id),
@@ -1,17 +1,34 @@
-use crate::config::read_config;
+use crate::feed::{notify_change, ChangeEntity, ChangeKind};
+use crate::config::{default_closed_states, read_config, CentyConfig, WorkflowError};
+use crate::milestone::milestone_exists;
 use crate::manifest::{
     read_manifest, write_manifest, update_manifest_timestamp, CentyManifest,
 };
-use crate::utils::{get_centy_path, now_iso};
-use super::id::is_valid_issue_folder;
+use crate::search::{index_issue, remove_issue};
+use crate::trash::{move_to_trash, RemovedLinks, TrashKind};
+use crate::utils::{get_centy_path, lock_project, now_iso, write_files_atomic};
+use super::archive::{archived_issues_path, is_archived_path};
+use super::cache::{archived_issues, cached_issues};
+use super::assignees::{resolve_assignee, resolve_assignees, AssigneeError};
+use super::comments::{insert_comment, AddCommentOptions, CommentError};
+use super::custom_fields::{apply_custom_fields, compare_value, value_to_string, CustomFieldError};
+use super::dates::{is_past_due, parse_date, today, validate_dates};
+use super::hierarchy::{detach_children, open_children};
+use super::history::{default_actor, diff_issue, record_history};
+use super::labels::{normalize_labels, validate_labels, LabelError};
 use super::metadata::IssueMetadata;
+use super::relations::{remove_issue_relations, IssueRelation, RelationError};
 use super::priority::{validate_priority, PriorityError};
-use super::reconcile::{reconcile_display_numbers, ReconcileError};
+use super::reconcile::ReconcileError;
 use super::status::validate_status;
+use super::transfer::{find_redirect, find_redirect_by_display_number, IssueRedirect};
+use chrono::NaiveDate;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::path::Path;
 use thiserror::Error;
 use tokio::fs;
+use tracing::warn;
 
 #[derive(Error, Debug)]
 pub enum IssueCrudError {
@@ -41,6 +58,65 @@ pub enum IssueCrudError {
 
     #[error("Reconcile error: {0}")]
     ReconcileError(#[from] ReconcileError),
+
+    #[error("Label error: {0}")]
+    LabelError(#[from] LabelError),
+
+    #[error("Assignee error: {0}")]
+    AssigneeError(#[from] AssigneeError),
+
+    #[error("Workflow error: {0}")]
+    WorkflowError(#[from] WorkflowError),
+
+    #[error("Comment error: {0}")]
+    CommentError(#[from] CommentError),
+
+    #[error("Cannot close issue: {0} child issue(s) still open")]
+    OpenChildren(usize),
+
+    #[error("Relation error: {0}")]
+    RelationError(Box<RelationError>),
+
+    #[error("{0}")]
+    CustomFieldError(#[from] CustomFieldError),
+
+    #[error("Invalid date: {0}")]
+    InvalidDate(String),
+
+    #[error("Invalid query: {0}")]
+    InvalidQuery(#[from] crate::query::QueryError),
+
+    #[error("Milestone '{0}' not found")]
+    MilestoneNotFound(String),
+
+    #[error("Issue {0} was moved to {1} as #{2} ({3})")]
+    IssueMoved(String, String, u32, String),
+
+    #[error("Issue {0} is archived; unarchive it first")]
+    IssueArchived(String),
+
+    #[error("Issue {} was changed by someone else (last updated {})", .0.id, .0.metadata.updated_at)]
+    Conflict(Box<Issue>),
+}
+
+impl IssueCrudError {
+    fn moved(id: &str, redirect: IssueRedirect) -> Self {
+        IssueCrudError::IssueMoved(
+            id.to_string(),
+            redirect.project_path,
+            redirect.new_display_number,
+            redirect.issue_id,
+        )
+    }
+}
+
+/// Error for an issue that is not in the active issues folder
+fn missing_issue(project_path: &Path, issue_number: &str) -> IssueCrudError {
+    if archived_issues_path(project_path).join(issue_number).exists() {
+        IssueCrudError::IssueArchived(issue_number.to_string())
+    } else {
+        IssueCrudError::IssueNotFound(issue_number.to_string())
+    }
 }
 
 /// Full issue data
@@ -66,7 +142,24 @@ pub struct IssueMetadataFlat {
     pub priority: u32,
     pub created_at: String,
     pub updated_at: String,
+    /// Custom field values rendered as strings (lists are comma-separated)
     pub custom_fields: HashMap<String, String>,
+    /// Custom field values as stored, typed per the field definitions
+    pub custom_field_values: HashMap<String, serde_json::Value>,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+    pub relations: Vec<IssueRelation>,
+    pub parent_id: Option<String>,
+    /// Planned start day (YYYY-MM-DD)
+    pub start_date: Option<String>,
+    /// Due day (YYYY-MM-DD)
+    pub due_date: Option<String>,
+    /// Milestone slug
+    pub milestone: Option<String>,
+    /// Whether the issue lives in the archive
+    pub archived: bool,
+    /// Numbers the issue had before being renumbered, oldest first
+    pub previous_display_numbers: Vec<u32>,
 }
 
 /// Options for updating an issue
@@ -77,7 +170,157 @@ pub struct UpdateIssueOptions {
     pub status: Option<String>,
     /// Priority as a number (1 = highest). None = don't update.
     pub priority: Option<u32>,
+    /// Custom fields to set, validated against their definitions. An empty value removes the field.
     pub custom_fields: HashMap<String, String>,
+    /// Replacement label list. None = don't update, Some(empty) = clear.
+    pub labels: Option<Vec<String>>,
+    /// Replacement assignee list (handles or emails). None = don't update, Some(empty) = clear.
+    pub assignees: Option<Vec<String>>,
+    /// Who is making the change, for history. None = local git user.
+    pub actor: Option<String>,
+    /// Comment explaining the change, added to the issue's comments.
+    /// Required for transitions listed in `workflow.commentRequired`.
+    pub comment: Option<String>,
+    /// Start date (YYYY-MM-DD). None = don't update, Some(empty) = clear.
+    pub start_date: Option<String>,
+    /// Due date (YYYY-MM-DD). None = don't update, Some(empty) = clear.
+    pub due_date: Option<String>,
+    /// Milestone slug. None = don't update, Some(empty) = clear.
+    pub milestone: Option<String>,
+    /// Fail with `Conflict` unless the issue's `updated_at` still has this
+    /// value. None = overwrite whatever is stored.
+    pub expected_updated_at: Option<String>,
+}
+
+/// Filters for listing issues. Unset fields match every issue.
+#[derive(Debug, Clone, Default)]
+pub struct IssueFilter {
+    pub status: Option<String>,
+    pub priority: Option<u32>,
+    /// Issues must carry every one of these labels
+    pub labels: Vec<String>,
+    /// Issues assigned to this member (handle or email)
+    pub assignee: Option<String>,
+    /// Issues in this milestone (slug)
+    pub milestone: Option<String>,
+    /// Issues must satisfy every one of these custom field conditions
+    pub custom_fields: Vec<CustomFieldFilter>,
+    /// Issues due on or before this day
+    pub due_before: Option<NaiveDate>,
+    /// Issues due on or after this day
+    pub due_after: Option<NaiveDate>,
+    /// Only issues past their due date that are not in a closed state
+    pub overdue: bool,
+    /// States that count as closed for `overdue`. Filled from config by
+    /// `list_issues_filtered` when empty.
+    pub closed_states: Vec<String>,
+    /// Also list archived issues
+    pub include_archived: bool,
+}
+
+/// Comparison operator for a custom field filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl FilterOp {
+    /// Parse an operator such as "=", "!=" or ">="
+    pub fn parse(op: &str) -> Option<Self> {
+        match op.trim() {
+            "" | "=" | "==" => Some(FilterOp::Eq),
+            "!=" => Some(FilterOp::Ne),
+            "<" => Some(FilterOp::Lt),
+            "<=" => Some(FilterOp::Le),
+            ">" => Some(FilterOp::Gt),
+            ">=" => Some(FilterOp::Ge),
+            _ => None,
+        }
+    }
+}
+
+/// Condition on a custom field. Numbers compare numerically, other values
+/// as strings; `=` on a multi-value field matches if any value is equal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomFieldFilter {
+    pub field: String,
+    pub op: FilterOp,
+    pub value: String,
+}
+
+impl CustomFieldFilter {
+    /// Check whether an issue satisfies the condition. Issues without the
+    /// field only match `!=`.
+    pub fn matches(&self, issue: &Issue) -> bool {
+        let Some(ordering) = issue
+            .metadata
+            .custom_field_values
+            .get(&self.field)
+            .and_then(|v| compare_value(v, &self.value))
+        else {
+            return self.op == FilterOp::Ne;
+        };
+
+        match self.op {
+            FilterOp::Eq => ordering == Ordering::Equal,
+            FilterOp::Ne => ordering != Ordering::Equal,
+            FilterOp::Lt => ordering == Ordering::Less,
+            FilterOp::Le => ordering != Ordering::Greater,
+            FilterOp::Gt => ordering == Ordering::Greater,
+            FilterOp::Ge => ordering != Ordering::Less,
+        }
+    }
+}
+
+impl IssueFilter {
+    /// Check whether an issue passes the filter
+    pub fn matches(&self, issue: &Issue) -> bool {
+        let status_match = self
+            .status
+            .as_ref()
+            .map(|s| &issue.metadata.status == s)
+            .unwrap_or(true);
+        let priority_match = self
+            .priority
+            .map(|p| issue.metadata.priority == p)
+            .unwrap_or(true);
+        let labels_match = self
+            .labels
+            .iter()
+            .all(|l| issue.metadata.labels.contains(l));
+        let assignee_match = self
+            .assignee
+            .as_ref()
+            .map(|a| issue.metadata.assignees.contains(a))
+            .unwrap_or(true);
+        let milestone_match = self
+            .milestone
+            .as_ref()
+            .is_none_or(|m| issue.metadata.milestone.as_ref() == Some(m));
+        let custom_fields_match = self.custom_fields.iter().all(|f| f.matches(issue));
+
+        let due = issue.metadata.due_date.as_deref().and_then(|d| parse_date(d).ok());
+        let due_before_match = self.due_before.is_none_or(|d| due.is_some_and(|due| due <= d));
+        let due_after_match = self.due_after.is_none_or(|d| due.is_some_and(|due| due >= d));
+        let overdue_match = !self.overdue
+            || (is_past_due(issue.metadata.due_date.as_deref(), today())
+                && !self.closed_states.contains(&issue.metadata.status));
+
+        status_match
+            && priority_match
+            && labels_match
+            && assignee_match
+            && milestone_match
+            && custom_fields_match
+            && due_before_match
+            && due_after_match
+            && overdue_match
+    }
 }
 
 /// Result of issue update
@@ -85,6 +328,19 @@ pub struct UpdateIssueOptions {
 pub struct UpdateIssueResult {
     pub issue: Issue,
     pub manifest: CentyManifest,
+    /// Non-fatal problems, e.g. closing a parent that still has open children
+    pub warnings: Vec<String>,
+}
+
+/// Result of looking up an issue by display number
+#[derive(Debug, Clone)]
+pub struct DisplayNumberLookup {
+    pub issue: Issue,
+    /// The number is an old one the issue had before reconciliation
+    /// renumbered it
+    pub redirected: bool,
+    /// Other issues that used to have the number, oldest first
+    pub previous_holders: Vec<Issue>,
 }
 
 /// Result of issue deletion
@@ -107,6 +363,13 @@ pub async fn get_issue(
     let issue_path = centy_path.join("issues").join(issue_number);
 
     if !issue_path.exists() {
+        let archived_path = archived_issues_path(project_path).join(issue_number);
+        if archived_path.exists() {
+            return read_issue_from_disk(&archived_path, issue_number).await;
+        }
+        if let Some(redirect) = find_redirect(project_path, issue_number).await {
+            return Err(IssueCrudError::moved(issue_number, redirect));
+        }
         return Err(IssueCrudError::IssueNotFound(issue_number.to_string()));
     }
 
@@ -118,6 +381,19 @@ pub async fn list_issues(
     project_path: &Path,
     status_filter: Option<&str>,
     priority_filter: Option<u32>,
+) -> Result<Vec<Issue>, IssueCrudError> {
+    let filter = IssueFilter {
+        status: status_filter.map(String::from),
+        priority: priority_filter,
+        ..Default::default()
+    };
+    list_issues_filtered(project_path, &filter).await
+}
+
+/// List all issues matching a filter
+pub async fn list_issues_filtered(
+    project_path: &Path,
+    filter: &IssueFilter,
 ) -> Result<Vec<Issue>, IssueCrudError> {
     // Check if centy is initialized
     read_manifest(project_path)
@@ -131,43 +407,28 @@ pub async fn list_issues(
         return Ok(Vec::new());
     }
 
-    // Reconcile display numbers to resolve any conflicts from concurrent creation
-    reconcile_display_numbers(&issues_path).await?;
-
-    let mut issues = Vec::new();
-    let mut entries = fs::read_dir(&issues_path).await?;
-
-    while let Some(entry) = entries.next_entry().await? {
-        if entry.file_type().await?.is_dir() {
-            if let Some(folder_name) = entry.file_name().to_str() {
-                // Accept both UUID and legacy 4-digit format
-                if !is_valid_issue_folder(folder_name) {
-                    continue;
-                }
-
-                match read_issue_from_disk(&entry.path(), folder_name).await {
-                    Ok(issue) => {
-                        // Apply filters
-                        let status_match = status_filter
-                            .map(|s| issue.metadata.status == s)
-                            .unwrap_or(true);
-                        let priority_match = priority_filter
-                            .map(|p| issue.metadata.priority == p)
-                            .unwrap_or(true);
-
-                        if status_match && priority_match {
-                            issues.push(issue);
-                        }
-                    }
-                    Err(_) => {
-                        // Skip issues that can't be read
-                        continue;
-                    }
-                }
-            }
-        }
+    // Resolve an assignee given by email to the member handle stored on issues
+    let mut filter = filter.clone();
+    if let Some(assignee) = filter.assignee.take() {
+        let config = read_config(project_path).await.ok().flatten();
+        let members = config.as_ref().map(|c| c.members.as_slice()).unwrap_or_default();
+        filter.assignee = Some(resolve_assignee(&assignee, members).unwrap_or(assignee));
+    }
+    if filter.overdue && filter.closed_states.is_empty() {
+        filter.closed_states = read_config(project_path)
+            .await
+            .ok()
+            .flatten()
+            .map(|c| c.closed_states)
+            .unwrap_or_else(default_closed_states);
     }
 
+    let mut issues = cached_issues(project_path).await?;
+    if filter.include_archived {
+        issues.extend(archived_issues(project_path).await?);
+    }
+    issues.retain(|i| filter.matches(i));
+
     // Sort by display number (human-readable ordering)
     issues.sort_by_key(|i| i.metadata.display_number);
 
@@ -179,49 +440,69 @@ pub async fn get_issue_by_display_number(
     project_path: &Path,
     display_number: u32,
 ) -> Result<Issue, IssueCrudError> {
+    Ok(resolve_display_number(project_path, display_number).await?.issue)
+}
+
+/// Find the issue a display number refers to.
+///
+/// The issue that has the number now wins. Failing that, an issue that had
+/// it before reconciliation renumbered it is returned as a redirect. Either
+/// way, other issues that used to have the number are listed, since older
+/// links to it may have meant one of them.
+pub async fn resolve_display_number(
+    project_path: &Path,
+    display_number: u32,
+) -> Result<DisplayNumberLookup, IssueCrudError> {
     // Check if centy is initialized
     read_manifest(project_path)
         .await?
         .ok_or(IssueCrudError::NotInitialized)?;
 
-    let centy_path = get_centy_path(project_path);
-    let issues_path = centy_path.join("issues");
-
-    if !issues_path.exists() {
-        return Err(IssueCrudError::IssueDisplayNumberNotFound(display_number));
+    let active = cached_issues(project_path).await?;
+    if let Some(lookup) = lookup_display_number(active, display_number) {
+        return Ok(lookup);
     }
-
-    // Reconcile first to ensure display numbers are unique
-    reconcile_display_numbers(&issues_path).await?;
-
-    let mut entries = fs::read_dir(&issues_path).await?;
-
-    while let Some(entry) = entries.next_entry().await? {
-        if entry.file_type().await?.is_dir() {
-            if let Some(folder_name) = entry.file_name().to_str() {
-                if !is_valid_issue_folder(folder_name) {
-                    continue;
-                }
-
-                let metadata_path = entry.path().join("metadata.json");
-                if !metadata_path.exists() {
-                    continue;
-                }
-
-                if let Ok(content) = fs::read_to_string(&metadata_path).await {
-                    if let Ok(metadata) = serde_json::from_str::<IssueMetadata>(&content) {
-                        if metadata.display_number == display_number {
-                            return read_issue_from_disk(&entry.path(), folder_name).await;
-                        }
-                    }
-                }
-            }
-        }
+    // Archived issues keep their numbers, so they are found too
+    let archived = archived_issues(project_path).await?;
+    if let Some(lookup) = lookup_display_number(archived, display_number) {
+        return Ok(lookup);
     }
 
+    if let Some(redirect) = find_redirect_by_display_number(project_path, display_number).await {
+        return Err(IssueCrudError::moved(&format!("#{}", display_number), redirect));
+    }
     Err(IssueCrudError::IssueDisplayNumberNotFound(display_number))
 }
 
+fn lookup_display_number(issues: Vec<Issue>, display_number: u32) -> Option<DisplayNumberLookup> {
+    let current = issues
+        .iter()
+        .find(|i| i.metadata.display_number == display_number)
+        .cloned();
+    let mut previous_holders: Vec<Issue> = issues
+        .into_iter()
+        .filter(|i| i.metadata.previous_display_numbers.contains(&display_number))
+        .collect();
+    previous_holders.sort_by(|a, b| a.metadata.created_at.cmp(&b.metadata.created_at));
+
+    if let Some(issue) = current {
+        return Some(DisplayNumberLookup {
+            issue,
+            redirected: false,
+            previous_holders,
+        });
+    }
+    if previous_holders.is_empty() {
+        return None;
+    }
+    let issue = previous_holders.remove(0);
+    Some(DisplayNumberLookup {
+        issue,
+        redirected: true,
+        previous_holders,
+    })
+}
+
 /// Update an existing issue
 pub async fn update_issue(
     project_path: &Path,
@@ -233,30 +514,94 @@ pub async fn update_issue(
         .await?
         .ok_or(IssueCrudError::NotInitialized)?;
 
+    let config = read_config(project_path).await.ok().flatten();
+    // Nobody may write between reading the issue and writing it back
+    let _lock = lock_project(project_path).await?;
+    let (issue, warnings) =
+        apply_issue_update(project_path, issue_number, options, config.as_ref()).await?;
+
+    // Update manifest timestamp
+    update_manifest_timestamp(&mut manifest);
+    write_manifest(project_path, &manifest).await?;
+
+    Ok(UpdateIssueResult { issue, manifest, warnings })
+}
+
+/// Update one issue's files, index and history without touching the
+/// manifest. Returns the updated issue and any non-fatal warnings.
+pub(crate) async fn apply_issue_update(
+    project_path: &Path,
+    issue_number: &str,
+    options: UpdateIssueOptions,
+    config: Option<&CentyConfig>,
+) -> Result<(Issue, Vec<String>), IssueCrudError> {
     let centy_path = get_centy_path(project_path);
     let issue_path = centy_path.join("issues").join(issue_number);
 
     if !issue_path.exists() {
-        return Err(IssueCrudError::IssueNotFound(issue_number.to_string()));
+        return Err(missing_issue(project_path, issue_number));
     }
 
-    // Read config for priority_levels validation
-    let config = read_config(project_path).await.ok().flatten();
     let priority_levels = config.as_ref().map(|c| c.priority_levels).unwrap_or(3);
 
     // Read current issue
     let current = read_issue_from_disk(&issue_path, issue_number).await?;
+    if options
+        .expected_updated_at
+        .as_ref()
+        .is_some_and(|expected| *expected != current.metadata.updated_at)
+    {
+        return Err(IssueCrudError::Conflict(Box::new(current)));
+    }
+    let previous = current.clone();
 
     // Apply updates
     let new_title = options.title.unwrap_or(current.title);
     let new_description = options.description.unwrap_or(current.description);
-    let new_status = options.status.unwrap_or(current.metadata.status);
+    let new_status = options.status.unwrap_or(current.metadata.status.clone());
+
+    let comment = options
+        .comment
+        .filter(|c| !c.trim().is_empty());
+
+    // Check the status change against the workflow (warns, or fails in strict mode)
+    let mut warnings = Vec::new();
+    if let Some(config) = config {
+        let workflow = &config.workflow;
+        let from = &current.metadata.status;
+        let mut violations = workflow.transition_violations(from, &new_status, &config.allowed_states);
+        if comment.is_none() && workflow.requires_comment(from, &new_status) {
+            violations.push(WorkflowError::CommentRequired {
+                from: from.clone(),
+                to: new_status.clone(),
+            });
+        }
+        warnings.extend(workflow.enforce(violations)?);
 
-    // Lenient validation: log warning if status is not in allowed_states
-    if let Some(ref config) = config {
+        // Lenient validation: log warning if status is not in allowed_states
         validate_status(&new_status, &config.allowed_states);
     }
 
+    // Closing a parent with open children warns, or fails if config says so
+    let closed_states = config
+        .as_ref()
+        .map(|c| c.closed_states.clone())
+        .unwrap_or_else(default_closed_states);
+    if closed_states.contains(&new_status) && !closed_states.contains(&current.metadata.status) {
+        let open = open_children(project_path, issue_number, &closed_states).await?;
+        if !open.is_empty() {
+            if config.as_ref().is_some_and(|c| c.block_close_with_open_children) {
+                return Err(IssueCrudError::OpenChildren(open.len()));
+            }
+            warn!(
+                issue_id = %issue_number,
+                open_children = open.len(),
+                "Closing issue with open child issues"
+            );
+            warnings.push(format!("{} child issue(s) still open", open.len()));
+        }
+    }
+
     // Validate and apply priority update
     let new_priority = match options.priority {
         Some(p) => {
@@ -266,11 +611,59 @@ pub async fn update_issue(
         None => current.metadata.priority,
     };
 
-    // Merge custom fields
-    let mut new_custom_fields = current.metadata.custom_fields;
-    for (key, value) in options.custom_fields {
-        new_custom_fields.insert(key, value);
-    }
+    // Replace labels if requested
+    let new_labels = match options.labels {
+        Some(labels) => {
+            let labels = normalize_labels(labels);
+            if let Some(config) = config {
+                validate_labels(&labels, &config.labels)?;
+            }
+            labels
+        }
+        None => current.metadata.labels,
+    };
+
+    // Replace assignees if requested
+    let new_assignees = match options.assignees {
+        Some(assignees) => {
+            let members = config.as_ref().map(|c| c.members.as_slice()).unwrap_or_default();
+            resolve_assignees(assignees, members)?
+        }
+        None => current.metadata.assignees,
+    };
+
+    // Merge and validate custom fields
+    let new_custom_field_values = {
+        let definitions = config.as_ref().map(|c| c.custom_fields.as_slice()).unwrap_or_default();
+        let members = config.as_ref().map(|c| c.members.as_slice()).unwrap_or_default();
+        apply_custom_fields(
+            current.metadata.custom_field_values,
+            &options.custom_fields,
+            definitions,
+            members,
+            false,
+        )?
+    };
+    let new_custom_fields = stringify_custom_fields(&new_custom_field_values);
+
+    // Apply and validate start/due dates
+    let (new_start_date, new_due_date) = validate_dates(
+        options.start_date.as_deref().or(current.metadata.start_date.as_deref()),
+        options.due_date.as_deref().or(current.metadata.due_date.as_deref()),
+    )
+    .map_err(IssueCrudError::InvalidDate)?;
+
+    // Move to another milestone if requested
+    let new_milestone = match options.milestone {
+        Some(milestone) if milestone.is_empty() => None,
+        Some(milestone) => {
+            if !milestone_exists(project_path, &milestone) {
+                return Err(IssueCrudError::MilestoneNotFound(milestone));
+            }
+            Some(milestone)
+        }
+        None => current.metadata.milestone.clone(),
+    };
 
     // Create updated metadata
     let updated_metadata = IssueMetadata {
@@ -279,12 +672,34 @@ pub async fn update_issue(
         priority: new_priority,
         created_at: current.metadata.created_at.clone(),
         updated_at: now_iso(),
-        custom_fields: new_custom_fields
-            .iter()
-            .map(|(k, v)| (k.clone(), serde_json::Value::String(v.clone())))
-            .collect(),
+        custom_fields: new_custom_field_values.clone(),
+        labels: new_labels.clone(),
+        assignees: new_assignees.clone(),
+        relations: current.metadata.relations.clone(),
+        parent_id: current.metadata.parent_id.clone(),
+        start_date: new_start_date.clone(),
+        due_date: new_due_date.clone(),
+        milestone: new_milestone.clone(),
+        previous_display_numbers: current.metadata.previous_display_numbers.clone(),
     };
 
+    let actor = options.actor.or_else(|| default_actor(project_path));
+
+    // Write the comment first, so a status change that requires one is never
+    // stored without it
+    if let Some(body) = comment {
+        insert_comment(
+            project_path,
+            issue_number,
+            AddCommentOptions {
+                author: actor.clone().unwrap_or_else(|| "unknown".to_string()),
+                body,
+                reply_to: None,
+            },
+        )
+        .await?;
+    }
+
     // Generate updated content
     let issue_md = generate_issue_md(&new_title, &new_description);
 
@@ -292,12 +707,12 @@ pub async fn update_issue(
     let issue_md_path = issue_path.join("issue.md");
     let metadata_path = issue_path.join("metadata.json");
 
-    fs::write(&issue_md_path, &issue_md).await?;
-    fs::write(&metadata_path, serde_json::to_string_pretty(&updated_metadata)?).await?;
-
-    // Update manifest timestamp
-    update_manifest_timestamp(&mut manifest);
-    write_manifest(project_path, &manifest).await?;
+    let metadata_json = serde_json::to_string_pretty(&updated_metadata)?;
+    write_files_atomic(&[
+        (issue_md_path.as_path(), issue_md.as_bytes()),
+        (metadata_path.as_path(), metadata_json.as_bytes()),
+    ])
+    .await?;
 
     #[allow(deprecated)]
     let issue = Issue {
@@ -312,10 +727,25 @@ pub async fn update_issue(
             created_at: current.metadata.created_at,
             updated_at: updated_metadata.updated_at,
             custom_fields: new_custom_fields,
+            custom_field_values: new_custom_field_values,
+            labels: new_labels,
+            assignees: new_assignees,
+            relations: current.metadata.relations,
+            parent_id: current.metadata.parent_id,
+            start_date: new_start_date,
+            due_date: new_due_date,
+            milestone: new_milestone,
+            archived: false,
+            previous_display_numbers: current.metadata.previous_display_numbers,
         },
     };
 
-    Ok(UpdateIssueResult { issue, manifest })
+    index_issue(project_path, &issue);
+    notify_change(project_path, ChangeEntity::Issue, ChangeKind::Updated, issue_number);
+
+    record_history(project_path, issue_number, &diff_issue(Some(&previous), &issue, actor.as_deref())).await;
+
+    Ok((issue, warnings))
 }
 
 /// Delete an issue
@@ -327,26 +757,68 @@ pub async fn delete_issue(
     let mut manifest = read_manifest(project_path)
         .await?
         .ok_or(IssueCrudError::NotInitialized)?;
+    let _lock = lock_project(project_path).await?;
 
+    remove_issue_files(project_path, issue_number, true).await?;
+
+    // Update manifest timestamp
+    update_manifest_timestamp(&mut manifest);
+    write_manifest(project_path, &manifest).await?;
+
+    Ok(DeleteIssueResult { manifest })
+}
+
+/// Remove one issue's folder and every reference to it, without touching
+/// the manifest. With `trash`, the folder is moved to the trash instead of
+/// being deleted.
+pub(crate) async fn remove_issue_files(
+    project_path: &Path,
+    issue_number: &str,
+    trash: bool,
+) -> Result<(), IssueCrudError> {
     let centy_path = get_centy_path(project_path);
     let issue_path = centy_path.join("issues").join(issue_number);
 
     if !issue_path.exists() {
-        return Err(IssueCrudError::IssueNotFound(issue_number.to_string()));
+        return Err(missing_issue(project_path, issue_number));
     }
 
-    // Remove the issue directory
-    fs::remove_dir_all(&issue_path).await?;
-
-    // Update manifest timestamp
-    update_manifest_timestamp(&mut manifest);
-    write_manifest(project_path, &manifest).await?;
+    // Children of a deleted issue become top-level issues
+    let child_ids = detach_children(project_path, issue_number).await?;
+
+    // Drop links pointing at this issue from the other side
+    remove_issue_relations(project_path, issue_number)
+        .await
+        .map_err(|e| IssueCrudError::RelationError(Box::new(e)))?;
+
+    if trash {
+        // Remember what was cut so a restore can link it up again
+        let issue = read_issue_from_disk(&issue_path, issue_number).await?;
+        let links = RemovedLinks {
+            relations: issue.metadata.relations.clone(),
+            child_ids,
+        };
+        move_to_trash(
+            project_path,
+            TrashKind::Issue,
+            issue_number,
+            &issue.title,
+            Some(issue.metadata.display_number),
+            &issue_path,
+            links,
+        )
+        .await?;
+    } else {
+        fs::remove_dir_all(&issue_path).await?;
+    }
+    remove_issue(project_path, issue_number);
+    notify_change(project_path, ChangeEntity::Issue, ChangeKind::Deleted, issue_number);
 
-    Ok(DeleteIssueResult { manifest })
+    Ok(())
 }
 
 /// Read an issue from disk
-async fn read_issue_from_disk(issue_path: &Path, issue_number: &str) -> Result<Issue, IssueCrudError> {
+pub(crate) async fn read_issue_from_disk(issue_path: &Path, issue_number: &str) -> Result<Issue, IssueCrudError> {
     let issue_md_path = issue_path.join("issue.md");
     let metadata_path = issue_path.join("metadata.json");
 
@@ -365,18 +837,7 @@ async fn read_issue_from_disk(issue_path: &Path, issue_number: &str) -> Result<I
     let metadata_content = fs::read_to_string(&metadata_path).await?;
     let metadata: IssueMetadata = serde_json::from_str(&metadata_content)?;
 
-    // Convert custom fields to strings
-    let custom_fields: HashMap<String, String> = metadata
-        .custom_fields
-        .into_iter()
-        .map(|(k, v)| {
-            let str_val = match v {
-                serde_json::Value::String(s) => s,
-                other => other.to_string(),
-            };
-            (k, str_val)
-        })
-        .collect();
+    let custom_fields = stringify_custom_fields(&metadata.custom_fields);
 
     #[allow(deprecated)]
     Ok(Issue {
@@ -391,10 +852,30 @@ async fn read_issue_from_disk(issue_path: &Path, issue_number: &str) -> Result<I
             created_at: metadata.created_at,
             updated_at: metadata.updated_at,
             custom_fields,
+            custom_field_values: metadata.custom_fields,
+            labels: metadata.labels,
+            assignees: metadata.assignees,
+            relations: metadata.relations,
+            parent_id: metadata.parent_id,
+            start_date: metadata.start_date,
+            due_date: metadata.due_date,
+            milestone: metadata.milestone,
+            archived: is_archived_path(issue_path),
+            previous_display_numbers: metadata.previous_display_numbers,
         },
     })
 }
 
+/// Render typed custom field values as strings
+pub(crate) fn stringify_custom_fields(
+    values: &HashMap<String, serde_json::Value>,
+) -> HashMap<String, String> {
+    values
+        .iter()
+        .map(|(k, v)| (k.clone(), value_to_string(v)))
+        .collect()
+}
+
 /// Parse issue.md content to extract title and description
 fn parse_issue_md(content: &str) -> (String, String) {
     let lines: Vec<&str> = content.lines().collect();
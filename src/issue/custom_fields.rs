@@ -0,0 +1,344 @@
+//! Typed custom field values
+//!
+//! Custom fields arrive as strings (from the CLI, gRPC or templates) and are
+//! stored in `metadata.json` as typed JSON values according to the field's
+//! `CustomFieldDefinition`. Fields without a definition are kept as strings.
+
+use crate::config::{CustomFieldDefinition, MemberDefinition};
+use super::assignees::resolve_assignee;
+use chrono::NaiveDate;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use thiserror::Error;
+
+/// Supported custom field types
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CustomFieldType {
+    String,
+    Number,
+    Boolean,
+    /// Calendar date (YYYY-MM-DD)
+    Date,
+    Enum,
+    /// Several values from `enum_values`
+    MultiEnum,
+    /// Project member handle
+    User,
+    Url,
+}
+
+impl CustomFieldType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CustomFieldType::String => "string",
+            CustomFieldType::Number => "number",
+            CustomFieldType::Boolean => "boolean",
+            CustomFieldType::Date => "date",
+            CustomFieldType::Enum => "enum",
+            CustomFieldType::MultiEnum => "multi-enum",
+            CustomFieldType::User => "user",
+            CustomFieldType::Url => "url",
+        }
+    }
+
+    /// Parse a field type name as stored in config (e.g. "number", "multi-enum")
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().replace('_', "-").as_str() {
+            "string" | "text" => Some(CustomFieldType::String),
+            "number" => Some(CustomFieldType::Number),
+            "boolean" | "bool" => Some(CustomFieldType::Boolean),
+            "date" => Some(CustomFieldType::Date),
+            "enum" => Some(CustomFieldType::Enum),
+            "multi-enum" => Some(CustomFieldType::MultiEnum),
+            "user" => Some(CustomFieldType::User),
+            "url" => Some(CustomFieldType::Url),
+            _ => None,
+        }
+    }
+}
+
+/// Validation failure for a single field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+#[derive(Error, Debug)]
+pub enum CustomFieldError {
+    #[error("Invalid custom fields: {}", describe(.0))]
+    Invalid(Vec<FieldError>),
+}
+
+impl CustomFieldError {
+    /// Per-field errors
+    pub fn field_errors(&self) -> &[FieldError] {
+        match self {
+            CustomFieldError::Invalid(errors) => errors,
+        }
+    }
+}
+
+fn describe(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("{}: {}", e.field, e.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn url_regex() -> &'static regex::Regex {
+    static URL: OnceLock<regex::Regex> = OnceLock::new();
+    URL.get_or_init(|| regex::Regex::new(r"^https?://[^\s/?#]+[^\s]*$").unwrap())
+}
+
+/// Convert a raw string to the typed value for a field definition
+pub fn parse_field_value(
+    definition: &CustomFieldDefinition,
+    raw: &str,
+    members: &[MemberDefinition],
+) -> Result<Value, String> {
+    let raw = raw.trim();
+    let field_type = CustomFieldType::parse(&definition.field_type)
+        .ok_or_else(|| format!("unknown field type '{}'", definition.field_type))?;
+
+    let check_enum = |value: &str| {
+        if definition.enum_values.iter().any(|v| v == value) {
+            Ok(())
+        } else {
+            Err(format!(
+                "'{}' is not one of: {}",
+                value,
+                definition.enum_values.join(", ")
+            ))
+        }
+    };
+
+    match field_type {
+        CustomFieldType::String => Ok(Value::String(raw.to_string())),
+        CustomFieldType::Number => {
+            if let Ok(n) = raw.parse::<i64>() {
+                return Ok(Value::from(n));
+            }
+            raw.parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(Value::Number)
+                .ok_or_else(|| format!("'{}' is not a number", raw))
+        }
+        CustomFieldType::Boolean => match raw.to_lowercase().as_str() {
+            "true" | "yes" | "1" => Ok(Value::Bool(true)),
+            "false" | "no" | "0" => Ok(Value::Bool(false)),
+            _ => Err(format!("'{}' is not a boolean", raw)),
+        },
+        CustomFieldType::Date => NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+            .map(|d| Value::String(d.format("%Y-%m-%d").to_string()))
+            .map_err(|_| format!("'{}' is not a date (expected YYYY-MM-DD)", raw)),
+        CustomFieldType::Enum => {
+            check_enum(raw)?;
+            Ok(Value::String(raw.to_string()))
+        }
+        CustomFieldType::MultiEnum => {
+            let mut values: Vec<String> = Vec::new();
+            for value in raw.split(',').map(str::trim).filter(|v| !v.is_empty()) {
+                check_enum(value)?;
+                if !values.iter().any(|v| v == value) {
+                    values.push(value.to_string());
+                }
+            }
+            Ok(Value::Array(values.into_iter().map(Value::String).collect()))
+        }
+        CustomFieldType::User => resolve_assignee(raw, members)
+            .map(Value::String)
+            .map_err(|e| e.to_string()),
+        CustomFieldType::Url => {
+            if url_regex().is_match(raw) {
+                Ok(Value::String(raw.to_string()))
+            } else {
+                Err(format!("'{}' is not an http(s) URL", raw))
+            }
+        }
+    }
+}
+
+/// Apply raw custom field input on top of existing typed values.
+///
+/// Values are validated against their definitions; an empty value removes
+/// the field. Required fields must end up set: all of them when
+/// `check_all_required` is true (creation), otherwise only the ones the
+/// input touches, so unrelated updates to legacy issues keep working.
+pub fn apply_custom_fields(
+    mut values: HashMap<String, Value>,
+    input: &HashMap<String, String>,
+    definitions: &[CustomFieldDefinition],
+    members: &[MemberDefinition],
+    check_all_required: bool,
+) -> Result<HashMap<String, Value>, CustomFieldError> {
+    let mut errors = Vec::new();
+
+    let mut keys: Vec<&String> = input.keys().collect();
+    keys.sort();
+    for key in keys {
+        let raw = &input[key];
+        if raw.trim().is_empty() {
+            values.remove(key);
+            continue;
+        }
+        match definitions.iter().find(|d| &d.name == key) {
+            Some(definition) => match parse_field_value(definition, raw, members) {
+                Ok(value) => {
+                    values.insert(key.clone(), value);
+                }
+                Err(message) => errors.push(FieldError {
+                    field: key.clone(),
+                    message,
+                }),
+            },
+            None => {
+                values.insert(key.clone(), Value::String(raw.clone()));
+            }
+        }
+    }
+
+    for definition in definitions.iter().filter(|d| d.required) {
+        let touched = check_all_required || input.contains_key(&definition.name);
+        let missing = values.get(&definition.name).is_none_or(is_empty_value);
+        if touched && missing && !errors.iter().any(|e| e.field == definition.name) {
+            errors.push(FieldError {
+                field: definition.name.clone(),
+                message: "is required".to_string(),
+            });
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(values)
+    } else {
+        Err(CustomFieldError::Invalid(errors))
+    }
+}
+
+fn is_empty_value(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.is_empty(),
+        Value::Array(a) => a.is_empty(),
+        _ => false,
+    }
+}
+
+/// Render a typed value as a display string (lists are comma-separated)
+pub fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items
+            .iter()
+            .map(value_to_string)
+            .collect::<Vec<_>>()
+            .join(", "),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Compare a stored value with a raw filter value: numerically when both
+/// are numbers, as strings otherwise. A list equals the value when one of
+/// its items does and is otherwise unordered (None), so only `!=` matches it.
+pub fn compare_value(value: &Value, raw: &str) -> Option<Ordering> {
+    match value {
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|item| compare_value(item, raw))
+            .find(|o| o.is_eq()),
+        Value::Null => None,
+        _ => {
+            let text = value_to_string(value);
+            match (text.parse::<f64>(), raw.trim().parse::<f64>()) {
+                (Ok(a), Ok(b)) => a.partial_cmp(&b),
+                _ => Some(text.as_str().cmp(raw.trim())),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definition(name: &str, field_type: &str) -> CustomFieldDefinition {
+        CustomFieldDefinition {
+            name: name.to_string(),
+            field_type: field_type.to_string(),
+            required: false,
+            default_value: None,
+            enum_values: vec!["low".to_string(), "high".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_parse_typed_values() {
+        let members = [];
+        assert_eq!(parse_field_value(&definition("e", "number"), "3", &members), Ok(Value::from(3)));
+        assert_eq!(
+            parse_field_value(&definition("e", "number"), "1.5", &members),
+            Ok(serde_json::json!(1.5))
+        );
+        assert_eq!(
+            parse_field_value(&definition("b", "boolean"), "Yes", &members),
+            Ok(Value::Bool(true))
+        );
+        assert_eq!(
+            parse_field_value(&definition("d", "date"), "2024-02-29", &members),
+            Ok(Value::String("2024-02-29".to_string()))
+        );
+        assert_eq!(
+            parse_field_value(&definition("m", "multi-enum"), "high, low, high", &members),
+            Ok(serde_json::json!(["high", "low"]))
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_values() {
+        let members = [];
+        assert!(parse_field_value(&definition("e", "number"), "three", &members).is_err());
+        assert!(parse_field_value(&definition("d", "date"), "2023-02-29", &members).is_err());
+        assert!(parse_field_value(&definition("s", "enum"), "medium", &members).is_err());
+        assert!(parse_field_value(&definition("u", "url"), "example.com", &members).is_err());
+        assert!(parse_field_value(&definition("u", "url"), "https://example.com/a", &members).is_ok());
+    }
+
+    #[test]
+    fn test_apply_collects_per_field_errors() {
+        let mut required = definition("owner", "string");
+        required.required = true;
+        let definitions = vec![definition("estimate", "number"), required];
+        let input = HashMap::from([("estimate".to_string(), "lots".to_string())]);
+
+        let err = apply_custom_fields(HashMap::new(), &input, &definitions, &[], true).unwrap_err();
+        let fields: Vec<_> = err.field_errors().iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["estimate", "owner"]);
+    }
+
+    #[test]
+    fn test_apply_keeps_undefined_fields_and_clears_empty() {
+        let existing = HashMap::from([("old".to_string(), Value::String("x".to_string()))]);
+        let input = HashMap::from([
+            ("old".to_string(), String::new()),
+            ("free".to_string(), "text".to_string()),
+        ]);
+
+        let values = apply_custom_fields(existing, &input, &[], &[], false).unwrap();
+        assert_eq!(values.len(), 1);
+        assert_eq!(values["free"], Value::String("text".to_string()));
+    }
+
+    #[test]
+    fn test_compare_value() {
+        assert_eq!(compare_value(&Value::from(8), "10"), Some(Ordering::Less));
+        assert_eq!(compare_value(&Value::String("5".to_string()), "5"), Some(Ordering::Equal));
+        assert_eq!(compare_value(&serde_json::json!(["a", "b"]), "b"), Some(Ordering::Equal));
+        assert_eq!(compare_value(&serde_json::json!(["a", "b"]), "c"), None);
+    }
+}
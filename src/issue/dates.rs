@@ -0,0 +1,88 @@
+//! Issue start and due dates
+//!
+//! Dates are calendar days stored as `YYYY-MM-DD` strings, with no time or
+//! timezone. "Today" for overdue checks is the daemon's local date.
+
+use chrono::{Local, NaiveDate};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Parse a `YYYY-MM-DD` date
+pub fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value.trim(), DATE_FORMAT)
+        .map_err(|_| format!("'{}' is not a date (expected YYYY-MM-DD)", value.trim()))
+}
+
+/// Format a date as `YYYY-MM-DD`
+pub fn format_date(date: NaiveDate) -> String {
+    date.format(DATE_FORMAT).to_string()
+}
+
+/// Today's date in the daemon's local timezone
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+/// Validate optional start and due dates, returning them normalized.
+/// An empty string counts as unset. The start date may not be after the due date.
+pub fn validate_dates(
+    start_date: Option<&str>,
+    due_date: Option<&str>,
+) -> Result<(Option<String>, Option<String>), String> {
+    let parse = |value: Option<&str>| {
+        value
+            .filter(|v| !v.trim().is_empty())
+            .map(parse_date)
+            .transpose()
+    };
+    let start = parse(start_date)?;
+    let due = parse(due_date)?;
+
+    if let (Some(start), Some(due)) = (start, due) {
+        if start > due {
+            return Err(format!(
+                "start date {} is after due date {}",
+                format_date(start),
+                format_date(due)
+            ));
+        }
+    }
+
+    Ok((start.map(format_date), due.map(format_date)))
+}
+
+/// Whether a due date has passed. Unparseable dates never count as overdue.
+pub fn is_past_due(due_date: Option<&str>, today: NaiveDate) -> bool {
+    due_date
+        .and_then(|d| parse_date(d).ok())
+        .is_some_and(|due| due < today)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_dates_normalizes() {
+        let (start, due) = validate_dates(Some("2024-1-5"), Some(" 2024-02-01 ")).unwrap();
+        assert_eq!(start.as_deref(), Some("2024-01-05"));
+        assert_eq!(due.as_deref(), Some("2024-02-01"));
+
+        assert_eq!(validate_dates(Some(""), None).unwrap(), (None, None));
+    }
+
+    #[test]
+    fn test_validate_dates_rejects_bad_input() {
+        assert!(validate_dates(None, Some("tomorrow")).is_err());
+        assert!(validate_dates(Some("2024-03-02"), Some("2024-03-01")).is_err());
+    }
+
+    #[test]
+    fn test_is_past_due() {
+        let today = NaiveDate::from_ymd_opt(2024, 6, 10).unwrap();
+        assert!(is_past_due(Some("2024-06-09"), today));
+        assert!(!is_past_due(Some("2024-06-10"), today));
+        assert!(!is_past_due(None, today));
+        assert!(!is_past_due(Some("garbage"), today));
+    }
+}
@@ -0,0 +1,368 @@
+//! Parent/child issue hierarchy
+//!
+//! Each issue stores an optional `parentId` in its metadata. Children are
+//! found by scanning for issues pointing at a parent, so only one side of
+//! the link is ever written.
+
+use crate::feed::{notify_change, ChangeEntity, ChangeKind};
+use crate::manifest::{read_manifest, write_manifest, update_manifest_timestamp, CentyManifest};
+use crate::utils::{get_centy_path, lock_project, now_iso, write_atomic};
+use super::crud::{
+    get_issue, list_issues, list_issues_filtered, Issue, IssueCrudError, IssueFilter,
+};
+use super::id::is_valid_issue_folder;
+use super::metadata::IssueMetadata;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use thiserror::Error;
+use tokio::fs;
+
+#[derive(Error, Debug)]
+pub enum HierarchyError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Manifest error: {0}")]
+    ManifestError(#[from] crate::manifest::ManifestError),
+
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("Issue error: {0}")]
+    IssueError(#[from] IssueCrudError),
+
+    #[error("Centy not initialized. Run 'centy init' first.")]
+    NotInitialized,
+
+    #[error("Issue {0} not found")]
+    IssueNotFound(String),
+
+    #[error("Parent issue {0} not found")]
+    ParentNotFound(String),
+
+    #[error("An issue cannot be its own parent")]
+    SelfParent,
+
+    #[error("Issue {0} is a descendant of this issue and cannot become its parent")]
+    CycleDetected(String),
+}
+
+/// Result of changing an issue's parent
+#[derive(Debug, Clone)]
+pub struct SetParentResult {
+    pub issue: Issue,
+    pub manifest: CentyManifest,
+}
+
+/// An issue together with its descendants
+#[derive(Debug, Clone)]
+pub struct IssueTreeNode {
+    pub issue: Issue,
+    pub children: Vec<IssueTreeNode>,
+}
+
+/// Set or clear (`None`) the parent of an issue
+pub async fn set_parent(
+    project_path: &Path,
+    issue_id: &str,
+    parent_id: Option<&str>,
+) -> Result<SetParentResult, HierarchyError> {
+    let mut manifest = read_manifest(project_path)
+        .await?
+        .ok_or(HierarchyError::NotInitialized)?;
+    let _lock = lock_project(project_path).await?;
+
+    // Both IDs are joined into paths, so they must be real issue folder names
+    if !is_valid_issue_folder(issue_id) {
+        return Err(HierarchyError::IssueNotFound(issue_id.to_string()));
+    }
+    let issues = list_issues(project_path, None, None).await?;
+    if !issues.iter().any(|i| i.id == issue_id) {
+        return Err(HierarchyError::IssueNotFound(issue_id.to_string()));
+    }
+
+    if let Some(parent_id) = parent_id {
+        if parent_id == issue_id {
+            return Err(HierarchyError::SelfParent);
+        }
+        if !is_valid_issue_folder(parent_id) || !issues.iter().any(|i| i.id == parent_id) {
+            return Err(HierarchyError::ParentNotFound(parent_id.to_string()));
+        }
+        // Walk up from the new parent; reaching the issue means a cycle
+        let parents: HashMap<&str, &str> = issues
+            .iter()
+            .filter_map(|i| i.metadata.parent_id.as_deref().map(|p| (i.id.as_str(), p)))
+            .collect();
+        let mut seen = HashSet::new();
+        let mut current = parent_id;
+        while let Some(&next) = parents.get(current) {
+            if next == issue_id {
+                return Err(HierarchyError::CycleDetected(parent_id.to_string()));
+            }
+            if !seen.insert(next) {
+                break;
+            }
+            current = next;
+        }
+    }
+
+    let metadata_path = get_centy_path(project_path)
+        .join("issues")
+        .join(issue_id)
+        .join("metadata.json");
+    let content = fs::read_to_string(&metadata_path).await?;
+    let mut metadata: IssueMetadata = serde_json::from_str(&content)?;
+    metadata.parent_id = parent_id.map(str::to_string);
+    metadata.updated_at = now_iso();
+    write_atomic(&metadata_path, serde_json::to_string_pretty(&metadata)?).await?;
+    notify_change(project_path, ChangeEntity::Issue, ChangeKind::Updated, issue_id);
+
+    update_manifest_timestamp(&mut manifest);
+    write_manifest(project_path, &manifest).await?;
+
+    Ok(SetParentResult {
+        issue: get_issue(project_path, issue_id).await?,
+        manifest,
+    })
+}
+
+/// List the children of an issue, or all descendants when `recursive` is set
+pub async fn list_children(
+    project_path: &Path,
+    issue_id: &str,
+    recursive: bool,
+) -> Result<Vec<Issue>, HierarchyError> {
+    let issues = list_issues(project_path, None, None).await?;
+    if !issues.iter().any(|i| i.id == issue_id) {
+        return Err(HierarchyError::IssueNotFound(issue_id.to_string()));
+    }
+
+    if !recursive {
+        return Ok(issues
+            .into_iter()
+            .filter(|i| i.metadata.parent_id.as_deref() == Some(issue_id))
+            .collect());
+    }
+
+    let mut children_of = group_by_parent(issues);
+    let mut descendants = Vec::new();
+    let mut stack = vec![issue_id.to_string()];
+    while let Some(id) = stack.pop() {
+        for child in children_of.remove(&id).unwrap_or_default() {
+            stack.push(child.id.clone());
+            descendants.push(child);
+        }
+    }
+    descendants.sort_by_key(|i| i.metadata.display_number);
+
+    Ok(descendants)
+}
+
+/// Build the issue tree below `root_id`, or the whole project forest
+/// (every top-level issue and its descendants) when `root_id` is `None`.
+/// Issues caught in a parent cycle appear in the forest under one of the
+/// cycle's members.
+pub async fn get_issue_tree(
+    project_path: &Path,
+    root_id: Option<&str>,
+) -> Result<Vec<IssueTreeNode>, HierarchyError> {
+    let issues = list_issues(project_path, None, None).await?;
+    let ids: HashSet<String> = issues.iter().map(|i| i.id.clone()).collect();
+
+    let roots: Vec<Issue> = match root_id {
+        Some(root_id) => vec![issues
+            .iter()
+            .find(|i| i.id == root_id)
+            .cloned()
+            .ok_or_else(|| HierarchyError::IssueNotFound(root_id.to_string()))?],
+        // Issues whose parent no longer exists are treated as top-level
+        None => issues
+            .iter()
+            .filter(|i| !i.metadata.parent_id.as_ref().is_some_and(|p| ids.contains(p)))
+            .cloned()
+            .collect(),
+    };
+
+    let parent_of: HashMap<String, String> = issues
+        .iter()
+        .filter_map(|i| i.metadata.parent_id.clone().map(|p| (i.id.clone(), p)))
+        .collect();
+    let mut children_of = group_by_parent(issues);
+    let mut forest: Vec<IssueTreeNode> = roots
+        .into_iter()
+        .map(|root| build_tree(root, &mut children_of))
+        .collect();
+
+    // Issues in a parent cycle (e.g. left behind by a merge) are never reached
+    // from a top-level issue. Root each such cycle at one of its members.
+    if root_id.is_none() {
+        // Orphans were rooted above but are still listed under their missing parent
+        children_of.retain(|parent_id, _| ids.contains(parent_id));
+        while let Some(start) = children_of
+            .values()
+            .flatten()
+            .min_by_key(|i| i.metadata.display_number)
+            .map(|i| i.id.clone())
+        {
+            let root_id = cycle_member(&start, &parent_of);
+            let Some(root) = children_of.values_mut().find_map(|children| {
+                let position = children.iter().position(|i| i.id == root_id)?;
+                Some(children.remove(position))
+            }) else {
+                break;
+            };
+            forest.push(build_tree(root, &mut children_of));
+            children_of.retain(|_, children| !children.is_empty());
+        }
+    }
+
+    Ok(forest)
+}
+
+/// Follow parent links from an issue that is not reachable from any
+/// top-level issue until one repeats, returning an issue on the cycle.
+fn cycle_member(issue_id: &str, parent_of: &HashMap<String, String>) -> String {
+    let mut seen = HashSet::new();
+    let mut id = issue_id.to_string();
+    while seen.insert(id.clone()) {
+        match parent_of.get(&id) {
+            Some(parent_id) => id = parent_id.clone(),
+            None => break,
+        }
+    }
+    id
+}
+
+/// Count the direct children of an issue by status. Archived children
+/// count too, so archiving closed work does not make the parent look less done.
+pub async fn child_status_counts(
+    project_path: &Path,
+    issue_id: &str,
+) -> Result<BTreeMap<String, u32>, IssueCrudError> {
+    let filter = IssueFilter {
+        include_archived: true,
+        ..Default::default()
+    };
+    let issues = list_issues_filtered(project_path, &filter).await?;
+    let mut counts = BTreeMap::new();
+    for child in issues
+        .iter()
+        .filter(|i| i.metadata.parent_id.as_deref() == Some(issue_id))
+    {
+        *counts.entry(child.metadata.status.clone()).or_insert(0) += 1;
+    }
+    Ok(counts)
+}
+
+/// Direct children of an issue that are not in one of the closed states
+pub(crate) async fn open_children(
+    project_path: &Path,
+    issue_id: &str,
+    closed_states: &[String],
+) -> Result<Vec<Issue>, IssueCrudError> {
+    let issues = list_issues(project_path, None, None).await?;
+    Ok(issues
+        .into_iter()
+        .filter(|i| i.metadata.parent_id.as_deref() == Some(issue_id))
+        .filter(|i| !closed_states.contains(&i.metadata.status))
+        .collect())
+}
+
+/// Clear the parent of every child of an issue. Returns the detached child IDs.
+pub(crate) async fn detach_children(
+    project_path: &Path,
+    issue_id: &str,
+) -> Result<Vec<String>, IssueCrudError> {
+    let issues_path = get_centy_path(project_path).join("issues");
+    let issues = list_issues(project_path, None, None).await?;
+    let mut detached = Vec::new();
+
+    for child in issues
+        .iter()
+        .filter(|i| i.metadata.parent_id.as_deref() == Some(issue_id))
+    {
+        let metadata_path = issues_path.join(&child.id).join("metadata.json");
+        let content = fs::read_to_string(&metadata_path).await?;
+        let mut metadata: IssueMetadata = serde_json::from_str(&content)?;
+        metadata.parent_id = None;
+        metadata.updated_at = now_iso();
+        write_atomic(&metadata_path, serde_json::to_string_pretty(&metadata)?).await?;
+        notify_change(project_path, ChangeEntity::Issue, ChangeKind::Updated, &child.id);
+        detached.push(child.id.clone());
+    }
+
+    Ok(detached)
+}
+
+/// Make the given issues children of `parent_id` again, e.g. when it is
+/// restored from the trash. Issues that no longer exist, have been given
+/// another parent, or have become its ancestor are skipped. Returns the
+/// reattached child IDs.
+pub(crate) async fn reattach_children(
+    project_path: &Path,
+    parent_id: &str,
+    child_ids: &[String],
+) -> Result<Vec<String>, IssueCrudError> {
+    if child_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let issues_path = get_centy_path(project_path).join("issues");
+    let issues = list_issues(project_path, None, None).await?;
+
+    // Ancestors of the parent, walking up from its own metadata on disk
+    let parents: HashMap<&str, &str> = issues
+        .iter()
+        .filter(|i| i.id != parent_id)
+        .filter_map(|i| i.metadata.parent_id.as_deref().map(|p| (i.id.as_str(), p)))
+        .collect();
+    let content = fs::read_to_string(issues_path.join(parent_id).join("metadata.json")).await?;
+    let parent: IssueMetadata = serde_json::from_str(&content)?;
+    let mut ancestors = HashSet::new();
+    let mut current = parent.parent_id.as_deref();
+    while let Some(id) = current {
+        if !ancestors.insert(id) {
+            break;
+        }
+        current = parents.get(id).copied();
+    }
+
+    let mut reattached = Vec::new();
+    for child in issues.iter().filter(|i| {
+        child_ids.contains(&i.id)
+            && i.metadata.parent_id.is_none()
+            && !ancestors.contains(i.id.as_str())
+    }) {
+        let metadata_path = issues_path.join(&child.id).join("metadata.json");
+        let content = fs::read_to_string(&metadata_path).await?;
+        let mut metadata: IssueMetadata = serde_json::from_str(&content)?;
+        metadata.parent_id = Some(parent_id.to_string());
+        metadata.updated_at = now_iso();
+        write_atomic(&metadata_path, serde_json::to_string_pretty(&metadata)?).await?;
+        notify_change(project_path, ChangeEntity::Issue, ChangeKind::Updated, &child.id);
+        reattached.push(child.id.clone());
+    }
+
+    Ok(reattached)
+}
+
+fn group_by_parent(issues: Vec<Issue>) -> HashMap<String, Vec<Issue>> {
+    let mut children_of: HashMap<String, Vec<Issue>> = HashMap::new();
+    for issue in issues {
+        if let Some(parent_id) = issue.metadata.parent_id.clone() {
+            children_of.entry(parent_id).or_default().push(issue);
+        }
+    }
+    children_of
+}
+
+fn build_tree(issue: Issue, children_of: &mut HashMap<String, Vec<Issue>>) -> IssueTreeNode {
+    // Removing the entry guarantees termination even if metadata contains a cycle
+    let children = children_of.remove(&issue.id).unwrap_or_default();
+    IssueTreeNode {
+        issue,
+        children: children
+            .into_iter()
+            .map(|child| build_tree(child, children_of))
+            .collect(),
+    }
+}
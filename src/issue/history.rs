@@ -0,0 +1,294 @@
+//! Per-issue change history
+//!
+//! Every mutation of an issue appends one event per changed field to
+//! `.centy/issues/{id}/history.jsonl`. The file is append-only: events are
+//! never rewritten, so concurrent edits on different machines merge as
+//! plain line additions.
+
+use crate::pr::git::get_git_user_email;
+use crate::utils::{get_centy_path, now_iso};
+use super::archive::archived_issues_path;
+use super::crud::Issue;
+use super::id::is_valid_issue_folder;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use thiserror::Error;
+use tokio::fs::{self, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tracing::warn;
+
+const HISTORY_FILE: &str = "history.jsonl";
+
+#[derive(Error, Debug)]
+pub enum HistoryError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("Issue not found: {0}")]
+    IssueNotFound(String),
+}
+
+/// A single field change on an issue
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEvent {
+    pub timestamp: String,
+    /// Who made the change (None = unknown)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    /// Changed field, e.g. "status" or "custom_fields.component"
+    pub field: String,
+    /// Value before the change (None = not set)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old_value: Option<String>,
+    /// Value after the change (None = removed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_value: Option<String>,
+}
+
+impl HistoryEvent {
+    pub fn new(
+        timestamp: &str,
+        actor: Option<&str>,
+        field: &str,
+        old_value: Option<String>,
+        new_value: Option<String>,
+    ) -> Self {
+        Self {
+            timestamp: timestamp.to_string(),
+            actor: actor.map(str::to_string),
+            field: field.to_string(),
+            old_value,
+            new_value,
+        }
+    }
+}
+
+/// Get the change history of an issue, oldest first
+pub async fn get_issue_history(
+    project_path: &Path,
+    issue_id: &str,
+) -> Result<Vec<HistoryEvent>, HistoryError> {
+    if !is_valid_issue_folder(issue_id) {
+        return Err(HistoryError::IssueNotFound(issue_id.to_string()));
+    }
+    // Archived issues keep their history
+    let Some(issue_path) = [
+        get_centy_path(project_path).join("issues").join(issue_id),
+        archived_issues_path(project_path).join(issue_id),
+    ]
+    .into_iter()
+    .find(|path| path.exists()) else {
+        return Err(HistoryError::IssueNotFound(issue_id.to_string()));
+    };
+
+    let history_path = issue_path.join(HISTORY_FILE);
+    if !history_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&history_path).await?;
+    let mut events = Vec::new();
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        match serde_json::from_str::<HistoryEvent>(line) {
+            Ok(event) => events.push(event),
+            // A bad line (e.g. from a botched merge) shouldn't hide the rest
+            Err(e) => warn!(issue_id = %issue_id, error = %e, "Skipping malformed history event"),
+        }
+    }
+
+    Ok(events)
+}
+
+/// Append events to an issue's history file, next to the issue whether it
+/// is active or archived
+pub(crate) async fn append_history(
+    project_path: &Path,
+    issue_id: &str,
+    events: &[HistoryEvent],
+) -> Result<(), HistoryError> {
+    if events.is_empty() {
+        return Ok(());
+    }
+
+    let active_path = get_centy_path(project_path).join("issues").join(issue_id);
+    let archived_path = archived_issues_path(project_path).join(issue_id);
+    let issue_path = if !active_path.exists() && archived_path.exists() {
+        archived_path
+    } else {
+        active_path
+    };
+    let history_path = issue_path.join(HISTORY_FILE);
+
+    let mut lines = String::new();
+    for event in events {
+        lines.push_str(&serde_json::to_string(event)?);
+        lines.push('\n');
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&history_path)
+        .await?;
+    file.write_all(lines.as_bytes()).await?;
+    file.flush().await?;
+
+    Ok(())
+}
+
+/// Append events, logging instead of failing. The mutation they describe has
+/// already been written, so a history failure must not turn it into an error.
+pub(crate) async fn record_history(project_path: &Path, issue_id: &str, events: &[HistoryEvent]) {
+    if let Err(e) = append_history(project_path, issue_id, events).await {
+        warn!(issue_id = %issue_id, error = %e, "Failed to record issue history");
+    }
+}
+
+/// Actor to record when none is given: the local git user, if configured
+pub(crate) fn default_actor(project_path: &Path) -> Option<String> {
+    get_git_user_email(project_path).ok().flatten()
+}
+
+/// Build events for every field that differs between two versions of an
+/// issue. With no previous version, every set field is reported as new.
+pub(crate) fn diff_issue(old: Option<&Issue>, new: &Issue, actor: Option<&str>) -> Vec<HistoryEvent> {
+    let timestamp = &new.metadata.updated_at;
+    let mut events = Vec::new();
+
+    let mut push = |field: &str, old_value: Option<String>, new_value: Option<String>| {
+        if old_value != new_value {
+            events.push(HistoryEvent::new(timestamp, actor, field, old_value, new_value));
+        }
+    };
+
+    let non_empty = |s: &str| (!s.is_empty()).then(|| s.to_string());
+    let join = |list: &[String]| (!list.is_empty()).then(|| list.join(", "));
+
+    push("title", old.map(|i| i.title.clone()), Some(new.title.clone()));
+    push(
+        "description",
+        old.and_then(|i| non_empty(&i.description)),
+        non_empty(&new.description),
+    );
+    push(
+        "status",
+        old.map(|i| i.metadata.status.clone()),
+        Some(new.metadata.status.clone()),
+    );
+    push(
+        "priority",
+        old.map(|i| i.metadata.priority.to_string()),
+        Some(new.metadata.priority.to_string()),
+    );
+    push(
+        "labels",
+        old.and_then(|i| join(&i.metadata.labels)),
+        join(&new.metadata.labels),
+    );
+    push(
+        "assignees",
+        old.and_then(|i| join(&i.metadata.assignees)),
+        join(&new.metadata.assignees),
+    );
+
+    push(
+        "start_date",
+        old.and_then(|i| i.metadata.start_date.clone()),
+        new.metadata.start_date.clone(),
+    );
+    push(
+        "due_date",
+        old.and_then(|i| i.metadata.due_date.clone()),
+        new.metadata.due_date.clone(),
+    );
+    push(
+        "milestone",
+        old.and_then(|i| i.metadata.milestone.clone()),
+        new.metadata.milestone.clone(),
+    );
+
+    let mut keys: Vec<&String> = new.metadata.custom_fields.keys().collect();
+    if let Some(old) = old {
+        keys.extend(old.metadata.custom_fields.keys());
+    }
+    keys.sort();
+    keys.dedup();
+    for key in keys {
+        push(
+            &format!("custom_fields.{}", key),
+            old.and_then(|i| i.metadata.custom_fields.get(key).cloned()),
+            new.metadata.custom_fields.get(key).cloned(),
+        );
+    }
+
+    events
+}
+
+/// Build the event for an asset added to or removed from an issue
+pub(crate) fn asset_event(actor: Option<&str>, old_value: Option<String>, new_value: Option<String>) -> HistoryEvent {
+    HistoryEvent::new(&now_iso(), actor, "assets", old_value, new_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issue::crud::IssueMetadataFlat;
+    use std::collections::HashMap;
+
+    #[allow(deprecated)]
+    fn issue(title: &str, status: &str, labels: &[&str]) -> Issue {
+        Issue {
+            id: "id".to_string(),
+            issue_number: "id".to_string(),
+            title: title.to_string(),
+            description: String::new(),
+            metadata: IssueMetadataFlat {
+                display_number: 1,
+                status: status.to_string(),
+                priority: 2,
+                created_at: "2024-01-01T00:00:00Z".to_string(),
+                updated_at: "2024-01-02T00:00:00Z".to_string(),
+                custom_fields: HashMap::new(),
+                custom_field_values: HashMap::new(),
+                labels: labels.iter().map(|l| l.to_string()).collect(),
+                assignees: Vec::new(),
+                relations: Vec::new(),
+                parent_id: None,
+                start_date: None,
+                due_date: None,
+                milestone: None,
+                archived: false,
+                previous_display_numbers: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_diff_issue_reports_changed_fields_only() {
+        let old = issue("Title", "open", &["bug"]);
+        let new = issue("Title", "closed", &["bug", "ui"]);
+
+        let events = diff_issue(Some(&old), &new, Some("alice"));
+        let fields: Vec<_> = events.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["status", "labels"]);
+        assert_eq!(events[0].old_value.as_deref(), Some("open"));
+        assert_eq!(events[0].new_value.as_deref(), Some("closed"));
+        assert_eq!(events[0].actor.as_deref(), Some("alice"));
+        assert_eq!(events[0].timestamp, "2024-01-02T00:00:00Z");
+        assert_eq!(events[1].new_value.as_deref(), Some("bug, ui"));
+    }
+
+    #[test]
+    fn test_diff_issue_without_previous_version() {
+        let new = issue("Title", "open", &[]);
+
+        let events = diff_issue(None, &new, None);
+        let fields: Vec<_> = events.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["title", "status", "priority"]);
+        assert!(events.iter().all(|e| e.old_value.is_none()));
+    }
+}
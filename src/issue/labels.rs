@@ -0,0 +1,314 @@
+//! Issue labels
+//!
+//! Labels are defined in the `labels` section of `config.json` and stored on
+//! each issue as a list of names in `metadata.json`. Renaming or deleting a
+//! label rewrites every issue that carries it.
+
+use crate::feed::{notify_change, ChangeEntity, ChangeKind};
+use crate::config::{read_config, write_config, CentyConfig, ConfigError, LabelDefinition};
+use crate::manifest::{read_manifest, write_manifest, update_manifest_timestamp, CentyManifest};
+use crate::utils::{get_centy_path, lock_project, now_iso, write_atomic};
+use super::archive::archived_issues_path;
+use super::history::{default_actor, record_history, HistoryEvent};
+use super::id::is_valid_issue_folder;
+use super::metadata::IssueMetadata;
+use std::collections::HashSet;
+use std::path::Path;
+use thiserror::Error;
+use tokio::fs;
+
+#[derive(Error, Debug)]
+pub enum LabelError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Manifest error: {0}")]
+    ManifestError(#[from] crate::manifest::ManifestError),
+
+    #[error("Config error: {0}")]
+    ConfigError(#[from] ConfigError),
+
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("Centy not initialized. Run 'centy init' first.")]
+    NotInitialized,
+
+    #[error("Label name is required")]
+    NameRequired,
+
+    #[error("Unknown label: {0}")]
+    UnknownLabel(String),
+
+    #[error("Label already exists: {0}")]
+    LabelAlreadyExists(String),
+}
+
+/// Result of renaming a label
+#[derive(Debug, Clone)]
+pub struct RenameLabelResult {
+    pub config: CentyConfig,
+    /// IDs of issues whose labels were rewritten
+    pub updated_issue_ids: Vec<String>,
+    pub manifest: CentyManifest,
+}
+
+/// Result of deleting a label
+#[derive(Debug, Clone)]
+pub struct DeleteLabelResult {
+    pub config: CentyConfig,
+    /// IDs of issues the label was removed from
+    pub updated_issue_ids: Vec<String>,
+    pub manifest: CentyManifest,
+}
+
+/// Trim label names, drop empty ones and remove duplicates (keeping the first occurrence)
+pub fn normalize_labels(labels: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    labels
+        .into_iter()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty() && seen.insert(l.clone()))
+        .collect()
+}
+
+/// Check that every label is defined in config.
+/// When the config defines no labels at all, any label is accepted.
+pub fn validate_labels(labels: &[String], definitions: &[LabelDefinition]) -> Result<(), LabelError> {
+    if definitions.is_empty() {
+        return Ok(());
+    }
+
+    for label in labels {
+        if !definitions.iter().any(|d| &d.name == label) {
+            return Err(LabelError::UnknownLabel(label.clone()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Rename a label in config and on every issue that carries it
+pub async fn rename_label(
+    project_path: &Path,
+    old_name: &str,
+    new_name: &str,
+) -> Result<RenameLabelResult, LabelError> {
+    let new_name = new_name.trim();
+    if new_name.is_empty() {
+        return Err(LabelError::NameRequired);
+    }
+
+    // Check if centy is initialized
+    read_manifest(project_path)
+        .await?
+        .ok_or(LabelError::NotInitialized)?;
+    let _lock = lock_project(project_path).await?;
+
+    let mut config = read_config(project_path).await?.unwrap_or_default();
+
+    if new_name != old_name && config.labels.iter().any(|d| d.name == new_name) {
+        return Err(LabelError::LabelAlreadyExists(new_name.to_string()));
+    }
+
+    let definition = config
+        .labels
+        .iter_mut()
+        .find(|d| d.name == old_name)
+        .ok_or_else(|| LabelError::UnknownLabel(old_name.to_string()))?;
+    definition.name = new_name.to_string();
+
+    write_config(project_path, &config).await?;
+
+    let (updated_issue_ids, manifest) = rewrite_issue_labels(project_path, |labels| {
+        let mut renamed: Vec<String> = labels
+            .iter()
+            .map(|l| if l == old_name { new_name.to_string() } else { l.clone() })
+            .collect();
+        renamed = normalize_labels(renamed);
+        if renamed == *labels {
+            return false;
+        }
+        *labels = renamed;
+        true
+    })
+    .await?;
+
+    Ok(RenameLabelResult {
+        config,
+        updated_issue_ids,
+        manifest,
+    })
+}
+
+/// Remove a label from config and from every issue that carries it
+pub async fn delete_label(project_path: &Path, name: &str) -> Result<DeleteLabelResult, LabelError> {
+    // Check if centy is initialized
+    read_manifest(project_path)
+        .await?
+        .ok_or(LabelError::NotInitialized)?;
+    let _lock = lock_project(project_path).await?;
+
+    let mut config = read_config(project_path).await?.unwrap_or_default();
+
+    let before = config.labels.len();
+    config.labels.retain(|d| d.name != name);
+    if config.labels.len() == before {
+        return Err(LabelError::UnknownLabel(name.to_string()));
+    }
+
+    write_config(project_path, &config).await?;
+
+    let removed: HashSet<String> = [name.to_string()].into_iter().collect();
+    let (updated_issue_ids, manifest) = strip_labels(project_path, &removed).await?;
+
+    Ok(DeleteLabelResult {
+        config,
+        updated_issue_ids,
+        manifest,
+    })
+}
+
+/// Remove labels that were dropped from config (e.g. through `UpdateConfig`)
+/// from every issue. Returns the IDs of the issues that changed. Labels are
+/// matched by name, so a label renamed in the definitions counts as dropped.
+/// The caller must hold the project lock.
+pub(crate) async fn remove_dropped_labels(
+    project_path: &Path,
+    old_definitions: &[LabelDefinition],
+    new_definitions: &[LabelDefinition],
+) -> Result<Vec<String>, LabelError> {
+    let removed: HashSet<String> = old_definitions
+        .iter()
+        .filter(|old| !new_definitions.iter().any(|new| new.name == old.name))
+        .map(|d| d.name.clone())
+        .collect();
+
+    if removed.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let (updated_issue_ids, _) = strip_labels(project_path, &removed).await?;
+    Ok(updated_issue_ids)
+}
+
+async fn strip_labels(
+    project_path: &Path,
+    removed: &HashSet<String>,
+) -> Result<(Vec<String>, CentyManifest), LabelError> {
+    rewrite_issue_labels(project_path, |labels| {
+        let before = labels.len();
+        labels.retain(|l| !removed.contains(l));
+        labels.len() != before
+    })
+    .await
+}
+
+/// Apply `update` to the labels of every issue, writing back the ones it changed
+/// with a `labels` history event. `update` returns true when it modified the labels.
+async fn rewrite_issue_labels(
+    project_path: &Path,
+    update: impl Fn(&mut Vec<String>) -> bool,
+) -> Result<(Vec<String>, CentyManifest), LabelError> {
+    let mut manifest = read_manifest(project_path)
+        .await?
+        .ok_or(LabelError::NotInitialized)?;
+
+    // Archived issues too, or unarchiving would bring back stale labels
+    let issue_dirs = [
+        get_centy_path(project_path).join("issues"),
+        archived_issues_path(project_path),
+    ];
+    let actor = default_actor(project_path);
+    let join = |list: &[String]| (!list.is_empty()).then(|| list.join(", "));
+    let mut updated_issue_ids = Vec::new();
+
+    for issues_path in &issue_dirs {
+        if !issues_path.exists() {
+            continue;
+        }
+        let mut entries = fs::read_dir(issues_path).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            if !entry.file_type().await?.is_dir() {
+                continue;
+            }
+            let Some(folder_name) = entry.file_name().to_str().map(String::from) else {
+                continue;
+            };
+            if !is_valid_issue_folder(&folder_name) {
+                continue;
+            }
+
+            let metadata_path = entry.path().join("metadata.json");
+            let Ok(content) = fs::read_to_string(&metadata_path).await else {
+                continue;
+            };
+            let Ok(mut metadata) = serde_json::from_str::<IssueMetadata>(&content) else {
+                continue;
+            };
+
+            let old_labels = metadata.labels.clone();
+            if update(&mut metadata.labels) {
+                metadata.updated_at = now_iso();
+                write_atomic(&metadata_path, serde_json::to_string_pretty(&metadata)?).await?;
+                let event = HistoryEvent::new(
+                    &metadata.updated_at,
+                    actor.as_deref(),
+                    "labels",
+                    join(&old_labels),
+                    join(&metadata.labels),
+                );
+                record_history(project_path, &folder_name, &[event]).await;
+                notify_change(project_path, ChangeEntity::Issue, ChangeKind::Updated, &folder_name);
+                updated_issue_ids.push(folder_name);
+            }
+        }
+    }
+
+    if !updated_issue_ids.is_empty() {
+        update_manifest_timestamp(&mut manifest);
+        write_manifest(project_path, &manifest).await?;
+    }
+
+    updated_issue_ids.sort();
+    Ok((updated_issue_ids, manifest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definition(name: &str) -> LabelDefinition {
+        LabelDefinition {
+            name: name.to_string(),
+            color: None,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_normalize_labels() {
+        let labels = vec![
+            " bug ".to_string(),
+            "ui".to_string(),
+            "".to_string(),
+            "bug".to_string(),
+        ];
+        assert_eq!(normalize_labels(labels), vec!["bug", "ui"]);
+    }
+
+    #[test]
+    fn test_validate_labels_against_config() {
+        let definitions = vec![definition("bug"), definition("ui")];
+        assert!(validate_labels(&["bug".to_string()], &definitions).is_ok());
+        assert!(matches!(
+            validate_labels(&["feature".to_string()], &definitions),
+            Err(LabelError::UnknownLabel(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_labels_without_definitions_accepts_anything() {
+        assert!(validate_labels(&["anything".to_string()], &[]).is_ok());
+    }
+}
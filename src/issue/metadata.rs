@@ -2,6 +2,7 @@ use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
 
 use super::priority::migrate_string_priority;
+use super::relations::IssueRelation;
 
 /// Default priority levels for migration when config is not available
 const DEFAULT_PRIORITY_LEVELS: u32 = 3;
@@ -23,6 +24,31 @@ pub struct IssueMetadata {
     pub updated_at: String,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub custom_fields: HashMap<String, serde_json::Value>,
+    /// Label names (defined in config)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /// Assigned member handles
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assignees: Vec<String>,
+    /// Links to other issues (both directions are stored)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relations: Vec<IssueRelation>,
+    /// Parent issue ID (folder name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+    /// Day work is planned to start (YYYY-MM-DD)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    /// Day the issue is due (YYYY-MM-DD)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<String>,
+    /// Milestone slug
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<String>,
+    /// Numbers the issue had before being renumbered, oldest first. Old
+    /// references to these numbers still find the issue.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub previous_display_numbers: Vec<u32>,
 }
 
 impl IssueMetadata {
@@ -40,6 +66,14 @@ impl IssueMetadata {
             created_at: now.clone(),
             updated_at: now,
             custom_fields,
+            labels: Vec::new(),
+            assignees: Vec::new(),
+            relations: Vec::new(),
+            parent_id: None,
+            start_date: None,
+            due_date: None,
+            milestone: None,
+            previous_display_numbers: Vec::new(),
         }
     }
 }
@@ -149,6 +183,17 @@ mod tests {
         assert_eq!(metadata.display_number, 0);
     }
 
+    #[test]
+    fn test_labels_default_to_empty_and_are_omitted() {
+        let json = r#"{"status":"open","priority":1,"createdAt":"2024-01-01","updatedAt":"2024-01-01"}"#;
+        let mut metadata: IssueMetadata = serde_json::from_str(json).unwrap();
+        assert!(metadata.labels.is_empty());
+        assert!(!serde_json::to_string(&metadata).unwrap().contains("labels"));
+
+        metadata.labels = vec!["bug".to_string()];
+        assert!(serde_json::to_string(&metadata).unwrap().contains(r#""labels":["bug"]"#));
+    }
+
     #[test]
     fn test_serialize_display_number() {
         let metadata = IssueMetadata::new(42, "open".to_string(), 1, HashMap::new());
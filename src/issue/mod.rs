@@ -1,26 +1,81 @@
+pub mod archive;
 pub mod assets;
+pub mod assignees;
+pub mod bulk;
+mod cache;
+pub mod comments;
+pub mod config_update;
 pub mod create;
 pub mod crud;
+pub mod custom_fields;
+pub mod dates;
+pub mod hierarchy;
+pub mod history;
 pub mod id;
+pub mod labels;
 mod metadata;
 pub mod priority;
 pub mod reconcile;
+pub mod relations;
+pub mod rescale;
 pub mod status;
+pub mod transfer;
 
 #[allow(deprecated)]
 pub use create::{create_issue, get_next_issue_number, CreateIssueOptions, CreateIssueResult, IssueError};
+pub use archive::{archive_issues, unarchive_issue, ArchiveIssuesResult, UnarchiveIssueResult};
+pub(crate) use cache::{invalidate_issue, invalidate_issues};
+pub use bulk::{
+    bulk_delete_issues, bulk_update_issues, BulkIssuePatch, BulkItemResult, BulkResult, BulkTarget,
+};
+pub use config_update::{update_config, ConfigUpdateError, ConfigUpdateResult};
 pub use crud::{
-    delete_issue, get_issue, get_issue_by_display_number, list_issues, update_issue,
-    DeleteIssueResult, Issue, IssueCrudError, IssueMetadataFlat, UpdateIssueOptions, UpdateIssueResult,
+    delete_issue, get_issue, get_issue_by_display_number, list_issues, list_issues_filtered,
+    resolve_display_number, update_issue, CustomFieldFilter, DeleteIssueResult,
+    DisplayNumberLookup, FilterOp, Issue, IssueCrudError, IssueFilter, IssueMetadataFlat,
+    UpdateIssueOptions, UpdateIssueResult,
+};
+pub use custom_fields::{
+    apply_custom_fields, parse_field_value, value_to_string, CustomFieldError, CustomFieldType,
+    FieldError,
+};
+pub use hierarchy::{
+    child_status_counts, get_issue_tree, list_children, set_parent, HierarchyError,
+    IssueTreeNode, SetParentResult,
 };
+pub use dates::{format_date, is_past_due, parse_date, today, validate_dates};
+pub use history::{get_issue_history, HistoryError, HistoryEvent};
 pub use id::{generate_issue_id, is_legacy_number, is_uuid, is_valid_issue_folder, short_id};
+pub use labels::{
+    delete_label, normalize_labels, rename_label, validate_labels,
+    DeleteLabelResult, LabelError, RenameLabelResult,
+};
 pub use metadata::IssueMetadata;
 pub use priority::{
     default_priority, label_to_priority, migrate_string_priority, priority_label,
-    validate_priority, PriorityError,
+    rescale_priority, validate_priority, PriorityError,
 };
 pub use reconcile::{get_next_display_number, reconcile_display_numbers, ReconcileError};
+pub use relations::{
+    get_issue_graph, link_issues, unlink_issues, IssueGraph, IssueGraphEdge, IssueGraphNode,
+    IssueRelation, LinkIssuesResult, RelationError, RelationKind,
+};
+pub use rescale::{
+    preview_priority_rescale, rescale_priorities, PriorityChange, PriorityItemKind,
+    RescaleError, RescalePrioritiesResult,
+};
 pub use status::validate_status;
+pub use transfer::{
+    find_redirect, transfer_issue, IssueRedirect, TransferError, TransferIssueOptions,
+    TransferIssueResult, TransferMode,
+};
+pub use assignees::{
+    find_member, git_default_assignee, resolve_assignee, resolve_assignees, AssigneeError,
+};
+pub use comments::{
+    add_comment, delete_comment, list_comments, update_comment, AddCommentOptions,
+    AddCommentResult, Comment, CommentError, DeleteCommentResult, UpdateCommentResult,
+};
 pub use assets::{
     add_asset, delete_asset, get_asset, list_assets, list_shared_assets,
     AddAssetResult, AssetError, AssetInfo, AssetScope, DeleteAssetResult,
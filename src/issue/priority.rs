@@ -96,6 +96,35 @@ pub fn migrate_string_priority(priority_str: &str, max_levels: u32) -> u32 {
     label_to_priority(priority_str, max_levels).unwrap_or_else(|| default_priority(max_levels))
 }
 
+/// Map a priority onto a scale with a different number of levels, e.g.
+/// after `priority_levels` changes in the config.
+///
+/// Named levels keep their meaning where the new scale has them ("high"
+/// stays "high", "low" stays the lowest) and fall back to
+/// `label_to_priority` otherwise. Numbered levels (P1, P2, ...) keep their
+/// relative position. Priorities outside the old scale are clamped first.
+pub fn rescale_priority(priority: u32, old_levels: u32, new_levels: u32) -> u32 {
+    let old_levels = old_levels.max(1);
+    let new_levels = new_levels.max(1);
+    let priority = priority.clamp(1, old_levels);
+    if old_levels == new_levels {
+        return priority;
+    }
+
+    if old_levels <= 4 {
+        let label = priority_label(priority, old_levels);
+        if let Some(p) = (1..=new_levels).find(|&p| priority_label(p, new_levels) == label) {
+            return p;
+        }
+        if let Some(p) = label_to_priority(&label, new_levels).filter(|p| (1..=new_levels).contains(p)) {
+            return p;
+        }
+    }
+
+    // Rounded to the nearest level, so P1 and the last level map onto the ends
+    ((priority - 1) * (new_levels - 1) + (old_levels - 1) / 2) / (old_levels - 1) + 1
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -239,4 +268,41 @@ mod tests {
         assert_eq!(migrate_string_priority("2", 3), 2);
         assert_eq!(migrate_string_priority("P1", 5), 1);
     }
+
+    #[test]
+    fn test_rescale_priority_named_levels() {
+        // 3 -> 4: high/medium/low keep their labels
+        assert_eq!(rescale_priority(1, 3, 4), 2);
+        assert_eq!(rescale_priority(2, 3, 4), 3);
+        assert_eq!(rescale_priority(3, 3, 4), 4);
+        // 4 -> 3: critical has no match and becomes the highest
+        assert_eq!(rescale_priority(1, 4, 3), 1);
+        assert_eq!(rescale_priority(2, 4, 3), 1);
+        assert_eq!(rescale_priority(4, 4, 3), 3);
+        // 1 level -> default priority
+        assert_eq!(rescale_priority(1, 1, 5), 3);
+    }
+
+    #[test]
+    fn test_rescale_priority_to_numbered_levels() {
+        assert_eq!(rescale_priority(1, 3, 5), 2);
+        assert_eq!(rescale_priority(2, 3, 5), 3);
+        assert_eq!(rescale_priority(3, 3, 5), 5);
+    }
+
+    #[test]
+    fn test_rescale_priority_from_numbered_levels() {
+        assert_eq!(rescale_priority(1, 5, 3), 1);
+        assert_eq!(rescale_priority(3, 5, 3), 2);
+        assert_eq!(rescale_priority(5, 5, 3), 3);
+        assert_eq!(rescale_priority(5, 5, 10), 10);
+        assert_eq!(rescale_priority(4, 5, 1), 1);
+    }
+
+    #[test]
+    fn test_rescale_priority_same_levels_clamps() {
+        assert_eq!(rescale_priority(2, 3, 3), 2);
+        assert_eq!(rescale_priority(7, 3, 3), 3);
+        assert_eq!(rescale_priority(7, 3, 5), 5);
+    }
 }
@@ -5,6 +5,8 @@
 //! 1. Keeping the oldest issue's display number (by created_at)
 //! 2. Reassigning newer issues to the next available number
 
+use crate::utils::write_atomic;
+use super::archive::archive_for_issues_dir;
 use super::id::is_valid_issue_folder;
 use super::metadata::IssueMetadata;
 use std::collections::HashMap;
@@ -86,16 +88,19 @@ pub async fn reconcile_display_numbers(issues_path: &Path) -> Result<u32, Reconc
             .push(issue);
     }
 
-    // Step 3: Find max display number for reassignment
-    let max_display_number = issues
+    // Step 3: Find max display number for reassignment (archived issues keep theirs)
+    let mut max_number = issues
         .iter()
         .map(|i| i.display_number)
         .max()
         .unwrap_or(0);
+    if let Some(archive_path) = archive_for_issues_dir(issues_path) {
+        max_number = max_number.max(max_display_number(&archive_path).await?);
+    }
 
     // Step 4: Process duplicates
     let mut reassignments: Vec<(String, u32)> = Vec::new(); // (folder_name, new_display_number)
-    let mut next_available = max_display_number + 1;
+    let mut next_available = max_number + 1;
 
     for (display_number, mut group) in by_display_number {
         if group.len() <= 1 {
@@ -130,11 +135,15 @@ pub async fn reconcile_display_numbers(issues_path: &Path) -> Result<u32, Reconc
         let content = fs::read_to_string(&metadata_path).await?;
         let mut metadata: IssueMetadata = serde_json::from_str(&content)?;
 
+        // Keep the old number so links that used it still find this issue
+        if metadata.display_number != 0 {
+            metadata.previous_display_numbers.push(metadata.display_number);
+        }
         metadata.display_number = new_display_number;
         metadata.updated_at = crate::utils::now_iso();
 
         let new_content = serde_json::to_string_pretty(&metadata)?;
-        fs::write(&metadata_path, new_content).await?;
+        write_atomic(&metadata_path, new_content).await?;
     }
 
     Ok(reassignment_count)
@@ -142,14 +151,24 @@ pub async fn reconcile_display_numbers(issues_path: &Path) -> Result<u32, Reconc
 
 /// Get the next available display number.
 ///
-/// Scans all existing issues and returns max + 1.
+/// Scans all existing issues, including archived ones, and returns max + 1.
 pub async fn get_next_display_number(issues_path: &Path) -> Result<u32, ReconcileError> {
-    if !issues_path.exists() {
-        return Ok(1);
+    let mut max_number = max_display_number(issues_path).await?;
+    if let Some(archive_path) = archive_for_issues_dir(issues_path) {
+        max_number = max_number.max(max_display_number(&archive_path).await?);
+    }
+
+    Ok(max_number + 1)
+}
+
+/// Highest display number among the issue folders in `dir` (0 if none)
+async fn max_display_number(dir: &Path) -> Result<u32, ReconcileError> {
+    if !dir.exists() {
+        return Ok(0);
     }
 
     let mut max_number: u32 = 0;
-    let mut entries = fs::read_dir(issues_path).await?;
+    let mut entries = fs::read_dir(dir).await?;
 
     while let Some(entry) = entries.next_entry().await? {
         if !entry.file_type().await?.is_dir() {
@@ -177,7 +196,7 @@ pub async fn get_next_display_number(issues_path: &Path) -> Result<u32, Reconcil
         }
     }
 
-    Ok(max_number + 1)
+    Ok(max_number)
 }
 
 #[cfg(test)]
@@ -283,6 +302,7 @@ mod tests {
         )
         .unwrap();
         assert_eq!(metadata1.display_number, 4);
+        assert!(metadata1.previous_display_numbers.is_empty());
 
         // Check the newer one was reassigned to 6 (max was 5, so next is 6)
         let metadata2: IssueMetadata = serde_json::from_str(
@@ -296,6 +316,7 @@ mod tests {
         )
         .unwrap();
         assert_eq!(metadata2.display_number, 6);
+        assert_eq!(metadata2.previous_display_numbers, vec![4]);
     }
 
     #[tokio::test]
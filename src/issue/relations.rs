@@ -0,0 +1,539 @@
+//! Typed links between issues
+//!
+//! Relations are stored on both issues: linking A `blocks` B records
+//! `blocks → B` on A and `blocked-by → A` on B. `relates-to` is its own
+//! inverse. Every write updates both sides so the two directions never
+//! drift apart, and `blocks` links that would close a cycle are rejected.
+
+use crate::feed::{notify_change, ChangeEntity, ChangeKind};
+use crate::manifest::{read_manifest, write_manifest, update_manifest_timestamp, CentyManifest};
+use crate::utils::{get_centy_path, lock_project, now_iso, write_atomic};
+use super::crud::{get_issue, list_issues, Issue, IssueCrudError};
+use super::id::is_valid_issue_folder;
+use super::metadata::IssueMetadata;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use tokio::fs;
+use tracing::warn;
+
+#[derive(Error, Debug)]
+pub enum RelationError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Manifest error: {0}")]
+    ManifestError(#[from] crate::manifest::ManifestError),
+
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("Issue error: {0}")]
+    IssueError(#[from] IssueCrudError),
+
+    #[error("Centy not initialized. Run 'centy init' first.")]
+    NotInitialized,
+
+    #[error("Issue {0} not found")]
+    IssueNotFound(String),
+
+    #[error("Invalid relation type: {0}")]
+    InvalidRelationType(String),
+
+    #[error("An issue cannot be linked to itself")]
+    SelfLink,
+
+    #[error("Issues are already linked with '{0}'")]
+    AlreadyLinked(String),
+
+    #[error("Issues are not linked with '{0}'")]
+    NotLinked(String),
+
+    #[error("Linking would create a cycle: {0}")]
+    CycleDetected(String),
+}
+
+/// Kind of link between two issues
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RelationKind {
+    Blocks,
+    BlockedBy,
+    Duplicates,
+    DuplicatedBy,
+    RelatesTo,
+}
+
+impl RelationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RelationKind::Blocks => "blocks",
+            RelationKind::BlockedBy => "blocked-by",
+            RelationKind::Duplicates => "duplicates",
+            RelationKind::DuplicatedBy => "duplicated-by",
+            RelationKind::RelatesTo => "relates-to",
+        }
+    }
+
+    /// Parse a relation type name (e.g. "blocks", "blocked-by")
+    pub fn parse(name: &str) -> Result<Self, RelationError> {
+        match name.trim().to_lowercase().replace('_', "-").as_str() {
+            "blocks" => Ok(RelationKind::Blocks),
+            "blocked-by" => Ok(RelationKind::BlockedBy),
+            "duplicates" => Ok(RelationKind::Duplicates),
+            "duplicated-by" => Ok(RelationKind::DuplicatedBy),
+            "relates-to" => Ok(RelationKind::RelatesTo),
+            _ => Err(RelationError::InvalidRelationType(name.to_string())),
+        }
+    }
+
+    /// The relation recorded on the other issue
+    pub fn inverse(&self) -> Self {
+        match self {
+            RelationKind::Blocks => RelationKind::BlockedBy,
+            RelationKind::BlockedBy => RelationKind::Blocks,
+            RelationKind::Duplicates => RelationKind::DuplicatedBy,
+            RelationKind::DuplicatedBy => RelationKind::Duplicates,
+            RelationKind::RelatesTo => RelationKind::RelatesTo,
+        }
+    }
+}
+
+/// A link from one issue to another, stored in the source issue's metadata
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueRelation {
+    #[serde(rename = "type")]
+    pub kind: RelationKind,
+    /// Target issue ID (folder name)
+    pub issue_id: String,
+}
+
+/// Result of linking or unlinking two issues
+#[derive(Debug, Clone)]
+pub struct LinkIssuesResult {
+    pub source: Issue,
+    pub target: Issue,
+    pub manifest: CentyManifest,
+}
+
+/// Node in an issue graph
+#[derive(Debug, Clone)]
+pub struct IssueGraphNode {
+    pub id: String,
+    pub display_number: u32,
+    pub title: String,
+    pub status: String,
+}
+
+/// Directed edge in an issue graph. Only the forward direction of each
+/// link is included (`blocks`, `duplicates`, `relates-to`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueGraphEdge {
+    pub source: String,
+    pub target: String,
+    pub kind: RelationKind,
+}
+
+/// Issues and the links between them
+#[derive(Debug, Clone, Default)]
+pub struct IssueGraph {
+    pub nodes: Vec<IssueGraphNode>,
+    pub edges: Vec<IssueGraphEdge>,
+}
+
+fn metadata_path(project_path: &Path, issue_id: &str) -> PathBuf {
+    get_centy_path(project_path)
+        .join("issues")
+        .join(issue_id)
+        .join("metadata.json")
+}
+
+async fn read_metadata(project_path: &Path, issue_id: &str) -> Result<IssueMetadata, RelationError> {
+    // Only a real issue folder name may be joined into a path
+    if !is_valid_issue_folder(issue_id) {
+        return Err(RelationError::IssueNotFound(issue_id.to_string()));
+    }
+    let path = metadata_path(project_path, issue_id);
+    if !path.exists() {
+        return Err(RelationError::IssueNotFound(issue_id.to_string()));
+    }
+    let content = fs::read_to_string(&path).await?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+async fn write_metadata(
+    project_path: &Path,
+    issue_id: &str,
+    metadata: &mut IssueMetadata,
+) -> Result<(), RelationError> {
+    metadata.updated_at = now_iso();
+    let path = metadata_path(project_path, issue_id);
+    write_atomic(&path, serde_json::to_string_pretty(metadata)?).await?;
+    Ok(())
+}
+
+/// Link two issues. The inverse relation is recorded on the target.
+pub async fn link_issues(
+    project_path: &Path,
+    source_id: &str,
+    target_id: &str,
+    kind: RelationKind,
+) -> Result<LinkIssuesResult, RelationError> {
+    let mut manifest = read_manifest(project_path)
+        .await?
+        .ok_or(RelationError::NotInitialized)?;
+    let _lock = lock_project(project_path).await?;
+
+    if source_id == target_id {
+        return Err(RelationError::SelfLink);
+    }
+
+    let mut source = read_metadata(project_path, source_id).await?;
+    let mut target = read_metadata(project_path, target_id).await?;
+
+    if source
+        .relations
+        .iter()
+        .any(|r| r.kind == kind && r.issue_id == target_id)
+    {
+        return Err(RelationError::AlreadyLinked(kind.as_str().to_string()));
+    }
+
+    // Normalize to "blocker blocks blocked" for the cycle check
+    let blocking_pair = match kind {
+        RelationKind::Blocks => Some((source_id, target_id)),
+        RelationKind::BlockedBy => Some((target_id, source_id)),
+        _ => None,
+    };
+    if let Some((blocker, blocked)) = blocking_pair {
+        let issues = list_issues(project_path, None, None).await?;
+        if let Some(path) = find_blocking_path(&issues, blocked, blocker) {
+            return Err(RelationError::CycleDetected(format_cycle(&issues, blocker, &path)));
+        }
+    }
+
+    source.relations.push(IssueRelation {
+        kind,
+        issue_id: target_id.to_string(),
+    });
+    let inverse = IssueRelation {
+        kind: kind.inverse(),
+        issue_id: source_id.to_string(),
+    };
+    if !target.relations.contains(&inverse) {
+        target.relations.push(inverse);
+    }
+
+    write_metadata(project_path, source_id, &mut source).await?;
+    write_metadata(project_path, target_id, &mut target).await?;
+    notify_change(project_path, ChangeEntity::Issue, ChangeKind::Updated, source_id);
+    notify_change(project_path, ChangeEntity::Issue, ChangeKind::Updated, target_id);
+
+    update_manifest_timestamp(&mut manifest);
+    write_manifest(project_path, &manifest).await?;
+
+    Ok(LinkIssuesResult {
+        source: get_issue(project_path, source_id).await?,
+        target: get_issue(project_path, target_id).await?,
+        manifest,
+    })
+}
+
+/// Remove a link between two issues, along with its inverse on the target
+pub async fn unlink_issues(
+    project_path: &Path,
+    source_id: &str,
+    target_id: &str,
+    kind: RelationKind,
+) -> Result<LinkIssuesResult, RelationError> {
+    let mut manifest = read_manifest(project_path)
+        .await?
+        .ok_or(RelationError::NotInitialized)?;
+    let _lock = lock_project(project_path).await?;
+
+    let mut source = read_metadata(project_path, source_id).await?;
+    let mut target = read_metadata(project_path, target_id).await?;
+
+    let before = source.relations.len();
+    source
+        .relations
+        .retain(|r| !(r.kind == kind && r.issue_id == target_id));
+    if source.relations.len() == before {
+        return Err(RelationError::NotLinked(kind.as_str().to_string()));
+    }
+    target
+        .relations
+        .retain(|r| !(r.kind == kind.inverse() && r.issue_id == source_id));
+
+    write_metadata(project_path, source_id, &mut source).await?;
+    write_metadata(project_path, target_id, &mut target).await?;
+    notify_change(project_path, ChangeEntity::Issue, ChangeKind::Updated, source_id);
+    notify_change(project_path, ChangeEntity::Issue, ChangeKind::Updated, target_id);
+
+    update_manifest_timestamp(&mut manifest);
+    write_manifest(project_path, &manifest).await?;
+
+    Ok(LinkIssuesResult {
+        source: get_issue(project_path, source_id).await?,
+        target: get_issue(project_path, target_id).await?,
+        manifest,
+    })
+}
+
+/// Drop every link pointing at an issue from the issues on the other side.
+/// Called before an issue is deleted so no dangling inverses remain.
+pub(crate) async fn remove_issue_relations(project_path: &Path, issue_id: &str) -> Result<(), RelationError> {
+    // An issue with unreadable metadata has no links to clean up
+    let Ok(metadata) = read_metadata(project_path, issue_id).await else {
+        return Ok(());
+    };
+
+    let mut linked: Vec<&str> = metadata.relations.iter().map(|r| r.issue_id.as_str()).collect();
+    linked.sort_unstable();
+    linked.dedup();
+
+    for other_id in linked {
+        // Skip targets that no longer exist
+        let Ok(mut other) = read_metadata(project_path, other_id).await else {
+            continue;
+        };
+        let before = other.relations.len();
+        other.relations.retain(|r| r.issue_id != issue_id);
+        if other.relations.len() != before {
+            write_metadata(project_path, other_id, &mut other).await?;
+            notify_change(project_path, ChangeEntity::Issue, ChangeKind::Updated, other_id);
+        }
+    }
+
+    Ok(())
+}
+
+/// Put back the links of a restored issue whose other side still exists,
+/// recording the inverse on each of those issues. Blocking links that would
+/// now close a cycle are dropped. Returns the links to keep on the issue.
+pub(crate) async fn restore_issue_relations(
+    project_path: &Path,
+    issue_id: &str,
+    relations: &[IssueRelation],
+) -> Result<Vec<IssueRelation>, RelationError> {
+    let mut others: Vec<Issue> = list_issues(project_path, None, None)
+        .await?
+        .into_iter()
+        .filter(|i| i.id != issue_id)
+        .collect();
+    let mut kept: Vec<IssueRelation> = Vec::new();
+
+    for relation in relations {
+        let Some(index) = others.iter().position(|i| i.id == relation.issue_id) else {
+            continue;
+        };
+        if kept.contains(relation) {
+            continue;
+        }
+
+        let blocking_pair = match relation.kind {
+            RelationKind::Blocks => Some((issue_id, relation.issue_id.as_str())),
+            RelationKind::BlockedBy => Some((relation.issue_id.as_str(), issue_id)),
+            _ => None,
+        };
+        if let Some((blocker, blocked)) = blocking_pair {
+            if find_blocking_path(&others, blocked, blocker).is_some() {
+                warn!(
+                    issue_id = %issue_id,
+                    other_id = %relation.issue_id,
+                    "Dropping restored blocking link that would create a cycle"
+                );
+                continue;
+            }
+        }
+
+        let inverse = IssueRelation {
+            kind: relation.kind.inverse(),
+            issue_id: issue_id.to_string(),
+        };
+        let mut other = read_metadata(project_path, &relation.issue_id).await?;
+        if !other.relations.contains(&inverse) {
+            other.relations.push(inverse.clone());
+            write_metadata(project_path, &relation.issue_id, &mut other).await?;
+            notify_change(project_path, ChangeEntity::Issue, ChangeKind::Updated, &relation.issue_id);
+        }
+        // Later cycle checks must see this link
+        others[index].metadata.relations.push(inverse);
+        kept.push(relation.clone());
+    }
+
+    Ok(kept)
+}
+
+/// Build the graph of linked issues.
+///
+/// With `root_id`, returns the connected component containing that issue.
+/// Without it, returns every issue that has at least one link.
+pub async fn get_issue_graph(
+    project_path: &Path,
+    root_id: Option<&str>,
+) -> Result<IssueGraph, RelationError> {
+    let issues = list_issues(project_path, None, None).await?;
+    let by_id: HashMap<&str, &Issue> = issues.iter().map(|i| (i.id.as_str(), i)).collect();
+
+    let included: HashSet<&str> = match root_id {
+        Some(root) => {
+            if !by_id.contains_key(root) {
+                return Err(RelationError::IssueNotFound(root.to_string()));
+            }
+            // Breadth-first walk over links in both directions
+            let mut seen: HashSet<&str> = HashSet::from([root]);
+            let mut queue = VecDeque::from([root]);
+            while let Some(id) = queue.pop_front() {
+                for relation in &by_id[id].metadata.relations {
+                    let next = relation.issue_id.as_str();
+                    if by_id.contains_key(next) && seen.insert(next) {
+                        queue.push_back(next);
+                    }
+                }
+            }
+            seen
+        }
+        None => issues
+            .iter()
+            .filter(|i| {
+                i.metadata
+                    .relations
+                    .iter()
+                    .any(|r| by_id.contains_key(r.issue_id.as_str()))
+            })
+            .map(|i| i.id.as_str())
+            .collect(),
+    };
+
+    let mut graph = IssueGraph::default();
+    for issue in &issues {
+        if !included.contains(issue.id.as_str()) {
+            continue;
+        }
+
+        graph.nodes.push(IssueGraphNode {
+            id: issue.id.clone(),
+            display_number: issue.metadata.display_number,
+            title: issue.title.clone(),
+            status: issue.metadata.status.clone(),
+        });
+
+        for relation in &issue.metadata.relations {
+            let forward = match relation.kind {
+                RelationKind::Blocks | RelationKind::Duplicates => true,
+                // Symmetric: emit once per pair
+                RelationKind::RelatesTo => issue.id < relation.issue_id,
+                RelationKind::BlockedBy | RelationKind::DuplicatedBy => false,
+            };
+            if forward && included.contains(relation.issue_id.as_str()) {
+                graph.edges.push(IssueGraphEdge {
+                    source: issue.id.clone(),
+                    target: relation.issue_id.clone(),
+                    kind: relation.kind,
+                });
+            }
+        }
+    }
+
+    Ok(graph)
+}
+
+/// Find a chain of `blocks` links from `from` to `to`.
+/// Returns the issue IDs along the chain (starting with `from`), if any.
+fn find_blocking_path(issues: &[Issue], from: &str, to: &str) -> Option<Vec<String>> {
+    let mut blocks: HashMap<&str, Vec<&str>> = HashMap::new();
+    for issue in issues {
+        for relation in &issue.metadata.relations {
+            match relation.kind {
+                RelationKind::Blocks => blocks
+                    .entry(issue.id.as_str())
+                    .or_default()
+                    .push(relation.issue_id.as_str()),
+                RelationKind::BlockedBy => blocks
+                    .entry(relation.issue_id.as_str())
+                    .or_default()
+                    .push(issue.id.as_str()),
+                _ => {}
+            }
+        }
+    }
+
+    let mut parents: HashMap<&str, &str> = HashMap::new();
+    let mut seen: HashSet<&str> = HashSet::from([from]);
+    let mut queue = VecDeque::from([from]);
+
+    while let Some(id) = queue.pop_front() {
+        if id == to {
+            let mut path = vec![to.to_string()];
+            let mut current = to;
+            while let Some(parent) = parents.get(current) {
+                path.push(parent.to_string());
+                current = parent;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for &next in blocks.get(id).into_iter().flatten() {
+            if seen.insert(next) {
+                parents.insert(next, id);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Describe a would-be cycle using display numbers, e.g. "#1 → #2 → #3 → #1"
+fn format_cycle(issues: &[Issue], blocker: &str, path: &[String]) -> String {
+    let label = |id: &str| {
+        issues
+            .iter()
+            .find(|i| i.id == id)
+            .map(|i| format!("#{}", i.metadata.display_number))
+            .unwrap_or_else(|| id.to_string())
+    };
+
+    std::iter::once(blocker)
+        .chain(path.iter().map(String::as_str))
+        .map(label)
+        .collect::<Vec<_>>()
+        .join(" → ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relation_kind_parse_and_inverse() {
+        assert_eq!(RelationKind::parse("blocks").unwrap(), RelationKind::Blocks);
+        assert_eq!(RelationKind::parse("Blocked_By").unwrap(), RelationKind::BlockedBy);
+        assert!(RelationKind::parse("parent").is_err());
+
+        for kind in [
+            RelationKind::Blocks,
+            RelationKind::BlockedBy,
+            RelationKind::Duplicates,
+            RelationKind::DuplicatedBy,
+            RelationKind::RelatesTo,
+        ] {
+            assert_eq!(kind.inverse().inverse(), kind);
+            assert_eq!(RelationKind::parse(kind.as_str()).unwrap(), kind);
+        }
+    }
+
+    #[test]
+    fn test_relation_serialization() {
+        let relation = IssueRelation {
+            kind: RelationKind::BlockedBy,
+            issue_id: "abc".to_string(),
+        };
+        let json = serde_json::to_string(&relation).unwrap();
+        assert_eq!(json, r#"{"type":"blocked-by","issueId":"abc"}"#);
+        assert_eq!(serde_json::from_str::<IssueRelation>(&json).unwrap(), relation);
+    }
+}
@@ -0,0 +1,271 @@
+//! Re-scaling priorities when `priorityLevels` changes
+//!
+//! Issues and PRs store their priority as a number, so changing the number
+//! of levels changes what those numbers mean. This maps every stored
+//! priority onto the new scale with `rescale_priority`, either as a preview
+//! or by rewriting every `metadata.json` in one atomic batch. Rewritten
+//! issues get a `priority` event in their history.
+
+use crate::feed::{notify_change, ChangeEntity, ChangeKind};
+use crate::manifest::{read_manifest, write_manifest, update_manifest_timestamp, CentyManifest};
+use crate::pr::crud::read_pr_from_disk;
+use crate::pr::is_valid_pr_folder;
+use crate::utils::{get_centy_path, lock_project, now_iso, write_files_atomic};
+use super::archive::archived_issues_path;
+use super::crud::read_issue_from_disk;
+use super::history::{default_actor, record_history, HistoryEvent};
+use super::id::is_valid_issue_folder;
+use super::priority::rescale_priority;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use tokio::fs;
+
+/// Highest number of priority levels a config may have
+const MAX_PRIORITY_LEVELS: u32 = 10;
+
+#[derive(Error, Debug)]
+pub enum RescaleError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Manifest error: {0}")]
+    ManifestError(#[from] crate::manifest::ManifestError),
+
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("Centy not initialized. Run 'centy init' first.")]
+    NotInitialized,
+
+    #[error("Priority levels must be between 1 and {MAX_PRIORITY_LEVELS}, got {0}")]
+    InvalidLevels(u32),
+}
+
+/// Kind of item whose priority is re-scaled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PriorityItemKind {
+    Issue,
+    Pr,
+}
+
+impl PriorityItemKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PriorityItemKind::Issue => "issue",
+            PriorityItemKind::Pr => "pr",
+        }
+    }
+}
+
+/// An issue or PR whose priority changes
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriorityChange {
+    pub kind: PriorityItemKind,
+    /// Issue or PR ID (folder name)
+    pub id: String,
+    pub display_number: u32,
+    pub title: String,
+    pub old_priority: u32,
+    pub new_priority: u32,
+}
+
+/// Result of previewing or applying a re-scale
+#[derive(Debug, Clone)]
+pub struct RescalePrioritiesResult {
+    pub old_levels: u32,
+    pub new_levels: u32,
+    /// Issues first, then PRs, each by display number. Items whose priority
+    /// stays the same are not listed.
+    pub changes: Vec<PriorityChange>,
+    /// False for a preview
+    pub applied: bool,
+    pub manifest: CentyManifest,
+}
+
+/// Show how every issue and PR priority would map from `old_levels` to
+/// `new_levels`, without writing anything
+pub async fn preview_priority_rescale(
+    project_path: &Path,
+    old_levels: u32,
+    new_levels: u32,
+) -> Result<RescalePrioritiesResult, RescaleError> {
+    validate_levels(old_levels)?;
+    validate_levels(new_levels)?;
+    let manifest = read_manifest(project_path)
+        .await?
+        .ok_or(RescaleError::NotInitialized)?;
+
+    let planned = plan_changes(project_path, old_levels, new_levels).await?;
+    Ok(RescalePrioritiesResult {
+        old_levels,
+        new_levels,
+        changes: planned.into_iter().map(|(change, _)| change).collect(),
+        applied: false,
+        manifest,
+    })
+}
+
+/// Map every issue and PR priority from `old_levels` to `new_levels` and
+/// write the ones that change
+pub async fn rescale_priorities(
+    project_path: &Path,
+    old_levels: u32,
+    new_levels: u32,
+) -> Result<RescalePrioritiesResult, RescaleError> {
+    validate_levels(old_levels)?;
+    validate_levels(new_levels)?;
+    let mut manifest = read_manifest(project_path)
+        .await?
+        .ok_or(RescaleError::NotInitialized)?;
+
+    let _lock = lock_project(project_path).await?;
+    let changes = apply_priority_rescale(project_path, &mut manifest, old_levels, new_levels).await?;
+
+    Ok(RescalePrioritiesResult {
+        old_levels,
+        new_levels,
+        changes,
+        applied: true,
+        manifest,
+    })
+}
+
+/// `rescale_priorities` for a caller that already holds the project lock.
+/// Levels must already be validated.
+pub(crate) async fn apply_priority_rescale(
+    project_path: &Path,
+    manifest: &mut CentyManifest,
+    old_levels: u32,
+    new_levels: u32,
+) -> Result<Vec<PriorityChange>, RescaleError> {
+    let planned = plan_changes(project_path, old_levels, new_levels).await?;
+    let actor = default_actor(project_path);
+    let now = now_iso();
+
+    // Every file is rewritten or none is, so a failure never leaves some
+    // priorities on the new scale
+    let mut payloads = Vec::with_capacity(planned.len());
+    for (change, metadata_path) in &planned {
+        let mut metadata: serde_json::Value =
+            serde_json::from_slice(&fs::read(metadata_path).await?)?;
+        metadata["priority"] = change.new_priority.into();
+        metadata["updatedAt"] = now.clone().into();
+        payloads.push(serde_json::to_string_pretty(&metadata)?);
+    }
+    let files: Vec<(&Path, &[u8])> = planned
+        .iter()
+        .zip(&payloads)
+        .map(|((_, path), payload)| (path.as_path(), payload.as_bytes()))
+        .collect();
+    write_files_atomic(&files).await?;
+
+    for (change, _) in &planned {
+        let entity = match change.kind {
+            PriorityItemKind::Issue => ChangeEntity::Issue,
+            PriorityItemKind::Pr => ChangeEntity::Pr,
+        };
+        if change.kind == PriorityItemKind::Issue {
+            let event = HistoryEvent::new(
+                &now,
+                actor.as_deref(),
+                "priority",
+                Some(change.old_priority.to_string()),
+                Some(change.new_priority.to_string()),
+            );
+            record_history(project_path, &change.id, &[event]).await;
+        }
+        notify_change(project_path, entity, ChangeKind::Updated, &change.id);
+    }
+
+    if !planned.is_empty() {
+        update_manifest_timestamp(manifest);
+        write_manifest(project_path, manifest).await?;
+    }
+
+    Ok(planned.into_iter().map(|(change, _)| change).collect())
+}
+
+pub(crate) fn validate_levels(levels: u32) -> Result<(), RescaleError> {
+    if !(1..=MAX_PRIORITY_LEVELS).contains(&levels) {
+        return Err(RescaleError::InvalidLevels(levels));
+    }
+    Ok(())
+}
+
+/// Every priority that changes, with the `metadata.json` that holds it.
+/// Items that cannot be read are skipped; `CheckProject` reports them.
+async fn plan_changes(
+    project_path: &Path,
+    old_levels: u32,
+    new_levels: u32,
+) -> Result<Vec<(PriorityChange, PathBuf)>, RescaleError> {
+    let centy_path = get_centy_path(project_path);
+    let mut planned = Vec::new();
+
+    let issue_dirs = [centy_path.join("issues"), archived_issues_path(project_path)];
+    for dir in &issue_dirs {
+        for (folder, id) in item_folders(dir, is_valid_issue_folder).await? {
+            let Ok(issue) = read_issue_from_disk(&folder, &id).await else {
+                continue;
+            };
+            let new_priority = rescale_priority(issue.metadata.priority, old_levels, new_levels);
+            if new_priority != issue.metadata.priority {
+                let change = PriorityChange {
+                    kind: PriorityItemKind::Issue,
+                    id,
+                    display_number: issue.metadata.display_number,
+                    title: issue.title,
+                    old_priority: issue.metadata.priority,
+                    new_priority,
+                };
+                planned.push((change, folder.join("metadata.json")));
+            }
+        }
+    }
+
+    for (folder, id) in item_folders(&centy_path.join("prs"), is_valid_pr_folder).await? {
+        let Ok(pr) = read_pr_from_disk(&folder, &id).await else {
+            continue;
+        };
+        let new_priority = rescale_priority(pr.metadata.priority, old_levels, new_levels);
+        if new_priority != pr.metadata.priority {
+            let change = PriorityChange {
+                kind: PriorityItemKind::Pr,
+                id,
+                display_number: pr.metadata.display_number,
+                title: pr.title,
+                old_priority: pr.metadata.priority,
+                new_priority,
+            };
+            planned.push((change, folder.join("metadata.json")));
+        }
+    }
+
+    planned.sort_by_key(|(change, _)| (change.kind, change.display_number));
+    Ok(planned)
+}
+
+/// Issue or PR folders in `dir` with their IDs
+async fn item_folders(
+    dir: &Path,
+    is_valid_folder: fn(&str) -> bool,
+) -> Result<Vec<(PathBuf, String)>, RescaleError> {
+    let mut folders = Vec::new();
+    if !dir.exists() {
+        return Ok(folders);
+    }
+
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        if !entry.file_type().await?.is_dir() {
+            continue;
+        }
+        let Some(name) = entry.file_name().to_str().map(String::from) else {
+            continue;
+        };
+        if is_valid_folder(&name) {
+            folders.push((entry.path(), name));
+        }
+    }
+    Ok(folders)
+}
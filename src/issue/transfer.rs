@@ -0,0 +1,290 @@
+//! Moving and copying issues between projects
+//!
+//! The whole issue folder (description, metadata, comments, assets and
+//! history) is copied into a hidden staging folder in the destination, given
+//! a new ID and display number, and renamed into place. A move then removes
+//! the source folder and records a redirect in `.centy/redirects.json`, so
+//! lookups of the old ID point at the new home. Both projects stay locked
+//! for the whole transfer.
+
+use crate::feed::{notify_change, ChangeEntity, ChangeKind};
+use crate::manifest::{read_manifest, update_manifest_timestamp, write_manifest, CentyManifest};
+use crate::milestone::milestone_exists;
+use crate::search::index_issue;
+use crate::utils::{get_centy_path, lock_project, now_iso, write_atomic, ProjectLock, TEMP_SUFFIX};
+use super::crud::{read_issue_from_disk, remove_issue_files, Issue, IssueCrudError};
+use super::history::{default_actor, record_history, HistoryEvent};
+use super::id::{generate_issue_id, is_valid_issue_folder};
+use super::metadata::IssueMetadata;
+use super::reconcile::{get_next_display_number, ReconcileError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use tokio::fs;
+
+const REDIRECTS_FILE: &str = "redirects.json";
+
+#[derive(Error, Debug)]
+pub enum TransferError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Manifest error: {0}")]
+    ManifestError(#[from] crate::manifest::ManifestError),
+
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("Centy not initialized in source project. Run 'centy init' first.")]
+    NotInitialized,
+
+    #[error("Centy not initialized in destination project {0}")]
+    DestinationNotInitialized(String),
+
+    #[error("Source and destination are the same project")]
+    SameProject,
+
+    #[error("Issue error: {0}")]
+    IssueError(#[from] IssueCrudError),
+
+    #[error("Reconcile error: {0}")]
+    ReconcileError(#[from] ReconcileError),
+}
+
+/// Whether the source issue is kept after the transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransferMode {
+    /// Remove the source issue and leave a redirect behind
+    #[default]
+    Move,
+    /// Keep the source issue untouched
+    Copy,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TransferIssueOptions {
+    pub mode: TransferMode,
+    /// Who is making the change, for history. None = local git user.
+    pub actor: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TransferIssueResult {
+    /// The issue as it now exists in the destination project
+    pub issue: Issue,
+    pub destination_manifest: CentyManifest,
+    /// Updated source manifest (moves only)
+    pub source_manifest: Option<CentyManifest>,
+}
+
+/// Where a moved issue went
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueRedirect {
+    /// Display number the issue had in this project
+    pub display_number: u32,
+    pub project_path: String,
+    pub issue_id: String,
+    /// Display number in the destination project
+    pub new_display_number: u32,
+    pub moved_at: String,
+}
+
+/// Move or copy an issue to another project.
+///
+/// Relations and the parent link refer to issues in the source project and
+/// are dropped; the milestone is kept only if the destination has one with
+/// the same slug.
+pub async fn transfer_issue(
+    source_path: &Path,
+    issue_id: &str,
+    destination_path: &Path,
+    options: TransferIssueOptions,
+) -> Result<TransferIssueResult, TransferError> {
+    // The ID is joined into paths that a move deletes from
+    if !is_valid_issue_folder(issue_id) {
+        return Err(IssueCrudError::IssueNotFound(issue_id.to_string()).into());
+    }
+    let mut source_manifest = read_manifest(source_path)
+        .await?
+        .ok_or(TransferError::NotInitialized)?;
+    let mut destination_manifest = read_manifest(destination_path)
+        .await?
+        .ok_or_else(|| {
+            TransferError::DestinationNotInitialized(destination_path.display().to_string())
+        })?;
+
+    if same_project(source_path, destination_path) {
+        return Err(TransferError::SameProject);
+    }
+    let _locks = lock_both(source_path, destination_path).await?;
+
+    let source_issue_path = get_centy_path(source_path).join("issues").join(issue_id);
+    if !source_issue_path.exists() {
+        return Err(IssueCrudError::IssueNotFound(issue_id.to_string()).into());
+    }
+    let source_issue = read_issue_from_disk(&source_issue_path, issue_id).await?;
+
+    let destination_centy = get_centy_path(destination_path);
+    let destination_issues = destination_centy.join("issues");
+    fs::create_dir_all(&destination_issues).await?;
+    let new_id = generate_issue_id();
+    let display_number = get_next_display_number(&destination_issues).await?;
+    let new_issue_path = destination_issues.join(&new_id);
+
+    // Copy everything into a hidden folder and rewrite the metadata there,
+    // so the issue only shows up in the destination once it is complete
+    let staging_path = destination_centy.join(format!(".transfer-{}{}", new_id, TEMP_SUFFIX));
+    let staged = stage_issue(&source_issue_path, &staging_path, destination_path, display_number).await;
+    let metadata = match staged {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&staging_path).await;
+            return Err(e);
+        }
+    };
+    fs::rename(&staging_path, &new_issue_path).await?;
+
+    let issue = read_issue_from_disk(&new_issue_path, &new_id).await?;
+    index_issue(destination_path, &issue);
+    notify_change(destination_path, ChangeEntity::Issue, ChangeKind::Created, &new_id);
+
+    let actor = options.actor.or_else(|| default_actor(source_path));
+    let event = HistoryEvent::new(
+        &metadata.updated_at,
+        actor.as_deref(),
+        "project",
+        Some(source_path.display().to_string()),
+        Some(destination_path.display().to_string()),
+    );
+    record_history(destination_path, &new_id, &[event]).await;
+
+    update_manifest_timestamp(&mut destination_manifest);
+    write_manifest(destination_path, &destination_manifest).await?;
+
+    let source_manifest = match options.mode {
+        TransferMode::Copy => None,
+        TransferMode::Move => {
+            remove_issue_files(source_path, issue_id, false).await?;
+            let redirect = IssueRedirect {
+                display_number: source_issue.metadata.display_number,
+                project_path: destination_path.display().to_string(),
+                issue_id: new_id,
+                new_display_number: display_number,
+                moved_at: metadata.updated_at.clone(),
+            };
+            add_redirect(source_path, issue_id, redirect).await?;
+
+            update_manifest_timestamp(&mut source_manifest);
+            write_manifest(source_path, &source_manifest).await?;
+            Some(source_manifest)
+        }
+    };
+
+    Ok(TransferIssueResult {
+        issue,
+        destination_manifest,
+        source_manifest,
+    })
+}
+
+/// Look up where an issue that no longer exists in this project was moved to
+pub async fn find_redirect(project_path: &Path, issue_id: &str) -> Option<IssueRedirect> {
+    read_redirects(project_path).await.ok()?.remove(issue_id)
+}
+
+/// Look up a moved issue by the display number it had in this project
+pub async fn find_redirect_by_display_number(
+    project_path: &Path,
+    display_number: u32,
+) -> Option<IssueRedirect> {
+    read_redirects(project_path)
+        .await
+        .ok()?
+        .into_values()
+        .find(|r| r.display_number == display_number)
+}
+
+async fn read_redirects(
+    project_path: &Path,
+) -> Result<BTreeMap<String, IssueRedirect>, TransferError> {
+    let path = get_centy_path(project_path).join(REDIRECTS_FILE);
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    Ok(serde_json::from_str(&fs::read_to_string(&path).await?)?)
+}
+
+async fn add_redirect(
+    project_path: &Path,
+    issue_id: &str,
+    redirect: IssueRedirect,
+) -> Result<(), TransferError> {
+    let mut redirects = read_redirects(project_path).await?;
+    redirects.insert(issue_id.to_string(), redirect);
+    let path = get_centy_path(project_path).join(REDIRECTS_FILE);
+    write_atomic(&path, serde_json::to_string_pretty(&redirects)?).await?;
+    Ok(())
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn same_project(a: &Path, b: &Path) -> bool {
+    canonical(a) == canonical(b)
+}
+
+/// Lock two different projects. They are always locked in the same order,
+/// so transfers in opposite directions cannot deadlock.
+async fn lock_both(a: &Path, b: &Path) -> Result<(ProjectLock, ProjectLock), std::io::Error> {
+    let (first, second) = if canonical(a) <= canonical(b) { (a, b) } else { (b, a) };
+    let first = lock_project(first).await?;
+    let second = lock_project(second).await?;
+    Ok((first, second))
+}
+
+/// Copy an issue folder to `staging_path` and rewrite its metadata for the
+/// destination project
+async fn stage_issue(
+    source_issue_path: &Path,
+    staging_path: &Path,
+    destination_path: &Path,
+    display_number: u32,
+) -> Result<IssueMetadata, TransferError> {
+    copy_dir(source_issue_path, staging_path).await?;
+
+    let metadata_path = staging_path.join("metadata.json");
+    let mut metadata: IssueMetadata =
+        serde_json::from_str(&fs::read_to_string(&metadata_path).await?)?;
+    metadata.display_number = display_number;
+    // Old numbers belong to the source project, which keeps a redirect
+    metadata.previous_display_numbers.clear();
+    metadata.relations.clear();
+    metadata.parent_id = None;
+    metadata.milestone = metadata
+        .milestone
+        .filter(|slug| milestone_exists(destination_path, slug));
+    metadata.updated_at = now_iso();
+    write_atomic(&metadata_path, serde_json::to_string_pretty(&metadata)?).await?;
+    Ok(metadata)
+}
+
+/// Recursively copy a directory
+async fn copy_dir(from: &Path, to: &Path) -> Result<(), std::io::Error> {
+    let mut pending: Vec<(PathBuf, PathBuf)> = vec![(from.to_path_buf(), to.to_path_buf())];
+    while let Some((from, to)) = pending.pop() {
+        fs::create_dir_all(&to).await?;
+        let mut entries = fs::read_dir(&from).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let target = to.join(entry.file_name());
+            if entry.file_type().await?.is_dir() {
+                pending.push((entry.path(), target));
+            } else {
+                fs::copy(entry.path(), &target).await?;
+            }
+        }
+    }
+    Ok(())
+}
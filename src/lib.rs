@@ -1,18 +1,26 @@
+pub mod calendar;
 pub mod config;
 pub mod docs;
+pub mod feed;
+pub mod integrity;
 pub mod issue;
 pub mod manifest;
+pub mod milestone;
 pub mod migration;
 pub mod pr;
+pub mod query;
 pub mod reconciliation;
 pub mod registry;
+pub mod search;
 pub mod server;
 pub mod template;
+pub mod trash;
 pub mod utils;
 pub mod version;
+pub mod view;
 
 // Re-export commonly used types
-pub use config::{CentyConfig, CustomFieldDefinition};
+pub use config::{CentyConfig, CustomFieldDefinition, LabelDefinition, MemberDefinition};
 pub use docs::{
     create_doc, delete_doc, get_doc, list_docs, update_doc,
     CreateDocOptions, CreateDocResult, DeleteDocResult, Doc, DocError, DocMetadata,
@@ -37,6 +45,7 @@ pub use registry::{
     get_project_info, list_projects, track_project, untrack_project, ProjectInfo, ProjectRegistry,
     RegistryError, TrackedProject,
 };
+pub use search::{search, SearchEntityType, SearchError, SearchHit, SearchOptions};
 pub use server::CentyDaemonService;
 pub use template::{DocTemplateContext, IssueTemplateContext, TemplateEngine, TemplateError, TemplateType};
 pub use migration::{
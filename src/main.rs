@@ -1,15 +1,23 @@
+mod calendar;
 mod config;
 mod docs;
+mod feed;
+mod integrity;
 mod issue;
 mod manifest;
+mod milestone;
 mod migration;
 mod pr;
+mod query;
 mod reconciliation;
 mod registry;
+mod search;
 mod server;
 mod template;
+mod trash;
 mod utils;
 mod version;
+mod view;
 
 use clap::Parser;
 use http::Method;
@@ -2,7 +2,7 @@ mod types;
 
 pub use types::{CentyManifest, ManagedFileType};
 
-use crate::utils::{get_manifest_path, now_iso, CENTY_VERSION};
+use crate::utils::{get_manifest_path, now_iso, write_atomic, CENTY_VERSION};
 use std::path::Path;
 use thiserror::Error;
 use tokio::fs;
@@ -39,7 +39,7 @@ pub async fn write_manifest(
 ) -> Result<(), ManifestError> {
     let manifest_path = get_manifest_path(project_path);
     let content = serde_json::to_string_pretty(manifest)?;
-    fs::write(&manifest_path, content).await?;
+    write_atomic(&manifest_path, content).await?;
     Ok(())
 }
 
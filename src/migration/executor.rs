@@ -3,6 +3,7 @@
 use super::registry::MigrationRegistry;
 use super::types::{Migration, MigrationDirection, MigrationError, MigrationResult};
 use crate::config::{read_config, write_config};
+use crate::issue::invalidate_issues;
 use crate::version::SemVer;
 use std::path::Path;
 use std::sync::Arc;
@@ -109,6 +110,7 @@ impl MigrationExecutor {
                         );
                     }
                 }
+                invalidate_issues(project_path);
 
                 return Ok(MigrationResult {
                     success: false,
@@ -121,6 +123,8 @@ impl MigrationExecutor {
 
             applied.push(Arc::clone(migration));
         }
+        // Migrations may have rewritten any issue
+        invalidate_issues(project_path);
 
         // Update config with new version
         let mut config = config.unwrap_or_default();
@@ -0,0 +1,557 @@
+use crate::config::{default_closed_states, read_config};
+use crate::docs::slugify;
+use crate::feed::{notify_change, ChangeEntity, ChangeKind};
+use crate::issue::archive::archived_issues_path;
+use crate::issue::history::{default_actor, record_history, HistoryEvent};
+use crate::issue::{
+    list_issues_filtered, parse_date, Issue, IssueCrudError, IssueFilter, IssueMetadata,
+};
+use crate::manifest::{
+    read_manifest, write_manifest, update_manifest_timestamp, CentyManifest,
+};
+use crate::pr::{list_prs, PrCrudError, PrMetadata};
+use crate::utils::{get_centy_path, lock_project, now_iso, write_atomic};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use tokio::fs;
+
+/// PR statuses that count as done for milestone progress
+const COMPLETED_PR_STATUSES: &[&str] = &["merged", "closed"];
+
+#[derive(Error, Debug)]
+pub enum MilestoneError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Manifest error: {0}")]
+    ManifestError(#[from] crate::manifest::ManifestError),
+
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("Centy not initialized. Run 'centy init' first.")]
+    NotInitialized,
+
+    #[error("Milestone '{0}' not found")]
+    MilestoneNotFound(String),
+
+    #[error("Title is required")]
+    TitleRequired,
+
+    #[error("Milestone with slug '{0}' already exists")]
+    SlugAlreadyExists(String),
+
+    #[error("Invalid slug: {0}")]
+    InvalidSlug(String),
+
+    #[error("Invalid target date: {0}")]
+    InvalidTargetDate(String),
+
+    #[error("Invalid milestone state '{0}' (expected 'open' or 'closed')")]
+    InvalidState(String),
+
+    #[error("Issue error: {0}")]
+    IssueError(#[from] IssueCrudError),
+
+    #[error("PR error: {0}")]
+    PrError(#[from] PrCrudError),
+}
+
+/// Milestone state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MilestoneState {
+    #[default]
+    Open,
+    Closed,
+}
+
+impl MilestoneState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MilestoneState::Open => "open",
+            MilestoneState::Closed => "closed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, MilestoneError> {
+        match value.trim().to_lowercase().as_str() {
+            "open" => Ok(MilestoneState::Open),
+            "closed" => Ok(MilestoneState::Closed),
+            _ => Err(MilestoneError::InvalidState(value.to_string())),
+        }
+    }
+}
+
+/// Full milestone data
+#[derive(Debug, Clone)]
+pub struct Milestone {
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    pub metadata: MilestoneMetadata,
+}
+
+/// Milestone metadata
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MilestoneMetadata {
+    /// Target day (YYYY-MM-DD)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_date: Option<String>,
+    #[serde(default)]
+    pub state: MilestoneState,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// On-disk form of a milestone: `.centy/milestones/{slug}.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MilestoneFile {
+    title: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    description: String,
+    #[serde(flatten)]
+    metadata: MilestoneMetadata,
+}
+
+/// Completion of the issues and PRs assigned to a milestone
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MilestoneProgress {
+    pub total_issues: u32,
+    /// Issues in one of the configured closed states
+    pub completed_issues: u32,
+    pub total_prs: u32,
+    /// PRs that are merged or closed
+    pub completed_prs: u32,
+    /// Completed issues and PRs as a percentage of all of them (0 when empty)
+    pub completion_percentage: f64,
+    pub issue_status_counts: BTreeMap<String, u32>,
+    pub pr_status_counts: BTreeMap<String, u32>,
+}
+
+/// Options for creating a milestone
+#[derive(Debug, Clone, Default)]
+pub struct CreateMilestoneOptions {
+    pub title: String,
+    pub description: String,
+    /// Slug to use. None = derived from the title.
+    pub slug: Option<String>,
+    /// Target day (YYYY-MM-DD)
+    pub target_date: Option<String>,
+}
+
+/// Result of milestone creation
+#[derive(Debug, Clone)]
+pub struct CreateMilestoneResult {
+    pub milestone: Milestone,
+    pub created_file: String,
+    pub manifest: CentyManifest,
+}
+
+/// Options for updating a milestone
+#[derive(Debug, Clone, Default)]
+pub struct UpdateMilestoneOptions {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// Target day (YYYY-MM-DD). None = don't update, Some(empty) = clear.
+    pub target_date: Option<String>,
+    pub state: Option<MilestoneState>,
+}
+
+/// Result of milestone update
+#[derive(Debug, Clone)]
+pub struct UpdateMilestoneResult {
+    pub milestone: Milestone,
+    pub manifest: CentyManifest,
+}
+
+/// Result of milestone deletion
+#[derive(Debug, Clone)]
+pub struct DeleteMilestoneResult {
+    pub manifest: CentyManifest,
+    /// Issue IDs that were unassigned from the milestone
+    pub unassigned_issues: Vec<String>,
+    /// PR IDs that were unassigned from the milestone
+    pub unassigned_prs: Vec<String>,
+}
+
+fn milestones_path(project_path: &Path) -> PathBuf {
+    get_centy_path(project_path).join("milestones")
+}
+
+fn milestone_path(project_path: &Path, slug: &str) -> PathBuf {
+    milestones_path(project_path).join(format!("{}.json", slug))
+}
+
+/// Check whether a milestone exists
+pub fn milestone_exists(project_path: &Path, slug: &str) -> bool {
+    !slug.is_empty() && slugify(slug) == slug && milestone_path(project_path, slug).exists()
+}
+
+/// Create a new milestone
+pub async fn create_milestone(
+    project_path: &Path,
+    options: CreateMilestoneOptions,
+) -> Result<CreateMilestoneResult, MilestoneError> {
+    if options.title.trim().is_empty() {
+        return Err(MilestoneError::TitleRequired);
+    }
+
+    let mut manifest = read_manifest(project_path)
+        .await?
+        .ok_or(MilestoneError::NotInitialized)?;
+    let _lock = lock_project(project_path).await?;
+
+    let slug = match options.slug {
+        Some(s) if !s.trim().is_empty() => slugify(&s),
+        _ => slugify(&options.title),
+    };
+    if slug.is_empty() {
+        return Err(MilestoneError::InvalidSlug(
+            "Slug must contain at least one alphanumeric character".to_string(),
+        ));
+    }
+
+    let path = milestone_path(project_path, &slug);
+    if path.exists() {
+        return Err(MilestoneError::SlugAlreadyExists(slug));
+    }
+
+    let now = now_iso();
+    let file = MilestoneFile {
+        title: options.title.trim().to_string(),
+        description: options.description,
+        metadata: MilestoneMetadata {
+            target_date: normalize_target_date(options.target_date.as_deref())?,
+            state: MilestoneState::Open,
+            created_at: now.clone(),
+            updated_at: now,
+        },
+    };
+
+    fs::create_dir_all(milestones_path(project_path)).await?;
+    write_atomic(&path, serde_json::to_string_pretty(&file)?).await?;
+
+    update_manifest_timestamp(&mut manifest);
+    write_manifest(project_path, &manifest).await?;
+
+    Ok(CreateMilestoneResult {
+        milestone: to_milestone(&slug, file),
+        created_file: format!(".centy/milestones/{}.json", slug),
+        manifest,
+    })
+}
+
+/// Get a single milestone by its slug
+pub async fn get_milestone(project_path: &Path, slug: &str) -> Result<Milestone, MilestoneError> {
+    read_manifest(project_path)
+        .await?
+        .ok_or(MilestoneError::NotInitialized)?;
+
+    if !milestone_exists(project_path, slug) {
+        return Err(MilestoneError::MilestoneNotFound(slug.to_string()));
+    }
+
+    read_milestone_from_disk(project_path, slug).await
+}
+
+/// List all milestones, open ones first, then by target date and slug
+pub async fn list_milestones(
+    project_path: &Path,
+    state_filter: Option<MilestoneState>,
+) -> Result<Vec<Milestone>, MilestoneError> {
+    read_manifest(project_path)
+        .await?
+        .ok_or(MilestoneError::NotInitialized)?;
+
+    let path = milestones_path(project_path);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut milestones = Vec::new();
+    let mut entries = fs::read_dir(&path).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let file_path = entry.path();
+        if file_path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let Some(slug) = file_path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        match read_milestone_from_disk(project_path, slug).await {
+            Ok(milestone) => milestones.push(milestone),
+            Err(_) => continue, // Skip milestones that can't be read
+        }
+    }
+
+    if let Some(state) = state_filter {
+        milestones.retain(|m| m.metadata.state == state);
+    }
+
+    // Undated milestones sort after dated ones
+    milestones.sort_by(|a, b| {
+        (a.metadata.state, a.metadata.target_date.is_none(), &a.metadata.target_date, &a.slug).cmp(&(
+            b.metadata.state,
+            b.metadata.target_date.is_none(),
+            &b.metadata.target_date,
+            &b.slug,
+        ))
+    });
+
+    Ok(milestones)
+}
+
+/// Update an existing milestone
+pub async fn update_milestone(
+    project_path: &Path,
+    slug: &str,
+    options: UpdateMilestoneOptions,
+) -> Result<UpdateMilestoneResult, MilestoneError> {
+    let mut manifest = read_manifest(project_path)
+        .await?
+        .ok_or(MilestoneError::NotInitialized)?;
+    let _lock = lock_project(project_path).await?;
+
+    if !milestone_exists(project_path, slug) {
+        return Err(MilestoneError::MilestoneNotFound(slug.to_string()));
+    }
+
+    let current = read_milestone_from_disk(project_path, slug).await?;
+
+    let title = match options.title {
+        Some(title) if title.trim().is_empty() => return Err(MilestoneError::TitleRequired),
+        Some(title) => title.trim().to_string(),
+        None => current.title,
+    };
+    let target_date = match options.target_date {
+        Some(date) => normalize_target_date(Some(&date))?,
+        None => current.metadata.target_date,
+    };
+
+    let file = MilestoneFile {
+        title,
+        description: options.description.unwrap_or(current.description),
+        metadata: MilestoneMetadata {
+            target_date,
+            state: options.state.unwrap_or(current.metadata.state),
+            created_at: current.metadata.created_at,
+            updated_at: now_iso(),
+        },
+    };
+
+    write_atomic(milestone_path(project_path, slug), serde_json::to_string_pretty(&file)?).await?;
+
+    update_manifest_timestamp(&mut manifest);
+    write_manifest(project_path, &manifest).await?;
+
+    Ok(UpdateMilestoneResult {
+        milestone: to_milestone(slug, file),
+        manifest,
+    })
+}
+
+/// Delete a milestone. Issues and PRs assigned to it are unassigned.
+pub async fn delete_milestone(
+    project_path: &Path,
+    slug: &str,
+) -> Result<DeleteMilestoneResult, MilestoneError> {
+    let mut manifest = read_manifest(project_path)
+        .await?
+        .ok_or(MilestoneError::NotInitialized)?;
+    let _lock = lock_project(project_path).await?;
+
+    if !milestone_exists(project_path, slug) {
+        return Err(MilestoneError::MilestoneNotFound(slug.to_string()));
+    }
+
+    let centy_path = get_centy_path(project_path);
+    let actor = default_actor(project_path);
+
+    let mut unassigned_issues = Vec::new();
+    for issue in issues_with_archived(project_path).await? {
+        if issue.metadata.milestone.as_deref() != Some(slug) {
+            continue;
+        }
+        let issues_dir = if issue.metadata.archived {
+            archived_issues_path(project_path)
+        } else {
+            centy_path.join("issues")
+        };
+        let metadata_path = issues_dir.join(&issue.id).join("metadata.json");
+        let mut metadata: IssueMetadata =
+            serde_json::from_str(&fs::read_to_string(&metadata_path).await?)?;
+        metadata.milestone = None;
+        metadata.updated_at = now_iso();
+        write_atomic(&metadata_path, serde_json::to_string_pretty(&metadata)?).await?;
+        let event = HistoryEvent::new(
+            &metadata.updated_at,
+            actor.as_deref(),
+            "milestone",
+            Some(slug.to_string()),
+            None,
+        );
+        record_history(project_path, &issue.id, &[event]).await;
+        notify_change(project_path, ChangeEntity::Issue, ChangeKind::Updated, &issue.id);
+        unassigned_issues.push(issue.id);
+    }
+
+    let mut unassigned_prs = Vec::new();
+    for pr in list_prs(project_path, None, None, None, None).await? {
+        if pr.metadata.milestone.as_deref() != Some(slug) {
+            continue;
+        }
+        let metadata_path = centy_path.join("prs").join(&pr.id).join("metadata.json");
+        let mut metadata: PrMetadata =
+            serde_json::from_str(&fs::read_to_string(&metadata_path).await?)?;
+        metadata.milestone = None;
+        metadata.updated_at = now_iso();
+        write_atomic(&metadata_path, serde_json::to_string_pretty(&metadata)?).await?;
+        notify_change(project_path, ChangeEntity::Pr, ChangeKind::Updated, &pr.id);
+        unassigned_prs.push(pr.id);
+    }
+
+    fs::remove_file(milestone_path(project_path, slug)).await?;
+
+    update_manifest_timestamp(&mut manifest);
+    write_manifest(project_path, &manifest).await?;
+
+    Ok(DeleteMilestoneResult {
+        manifest,
+        unassigned_issues,
+        unassigned_prs,
+    })
+}
+
+/// Compute completion of the issues and PRs assigned to a milestone
+pub async fn milestone_progress(
+    project_path: &Path,
+    slug: &str,
+) -> Result<MilestoneProgress, MilestoneError> {
+    if !milestone_exists(project_path, slug) {
+        return Err(MilestoneError::MilestoneNotFound(slug.to_string()));
+    }
+
+    let closed_states = read_config(project_path)
+        .await
+        .ok()
+        .flatten()
+        .map(|c| c.closed_states)
+        .unwrap_or_else(default_closed_states);
+
+    let mut progress = MilestoneProgress::default();
+
+    for issue in issues_with_archived(project_path).await? {
+        if issue.metadata.milestone.as_deref() != Some(slug) {
+            continue;
+        }
+        progress.total_issues += 1;
+        if closed_states.contains(&issue.metadata.status) {
+            progress.completed_issues += 1;
+        }
+        *progress
+            .issue_status_counts
+            .entry(issue.metadata.status)
+            .or_insert(0) += 1;
+    }
+
+    for pr in list_prs(project_path, None, None, None, None).await? {
+        if pr.metadata.milestone.as_deref() != Some(slug) {
+            continue;
+        }
+        progress.total_prs += 1;
+        if COMPLETED_PR_STATUSES.contains(&pr.metadata.status.as_str()) {
+            progress.completed_prs += 1;
+        }
+        *progress.pr_status_counts.entry(pr.metadata.status).or_insert(0) += 1;
+    }
+
+    progress.completion_percentage = percentage(
+        progress.completed_issues + progress.completed_prs,
+        progress.total_issues + progress.total_prs,
+    );
+
+    Ok(progress)
+}
+
+/// Every issue, archived ones included: archiving a closed issue must not
+/// take it out of its milestone
+async fn issues_with_archived(project_path: &Path) -> Result<Vec<Issue>, IssueCrudError> {
+    let filter = IssueFilter {
+        include_archived: true,
+        ..Default::default()
+    };
+    list_issues_filtered(project_path, &filter).await
+}
+
+fn percentage(completed: u32, total: u32) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        f64::from(completed) * 100.0 / f64::from(total)
+    }
+}
+
+fn normalize_target_date(value: Option<&str>) -> Result<Option<String>, MilestoneError> {
+    match value.map(str::trim).filter(|v| !v.is_empty()) {
+        Some(date) => parse_date(date)
+            .map(|d| Some(d.format("%Y-%m-%d").to_string()))
+            .map_err(MilestoneError::InvalidTargetDate),
+        None => Ok(None),
+    }
+}
+
+async fn read_milestone_from_disk(project_path: &Path, slug: &str) -> Result<Milestone, MilestoneError> {
+    let content = fs::read_to_string(milestone_path(project_path, slug)).await?;
+    let file: MilestoneFile = serde_json::from_str(&content)?;
+    Ok(to_milestone(slug, file))
+}
+
+fn to_milestone(slug: &str, file: MilestoneFile) -> Milestone {
+    Milestone {
+        slug: slug.to_string(),
+        title: file.title,
+        description: file.description,
+        metadata: file.metadata,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_parse() {
+        assert_eq!(MilestoneState::parse("Open").unwrap(), MilestoneState::Open);
+        assert_eq!(MilestoneState::parse("closed").unwrap(), MilestoneState::Closed);
+        assert!(MilestoneState::parse("done").is_err());
+    }
+
+    #[test]
+    fn test_percentage() {
+        assert_eq!(percentage(0, 0), 0.0);
+        assert_eq!(percentage(1, 4), 25.0);
+    }
+
+    #[test]
+    fn test_milestone_file_format() {
+        let file = MilestoneFile {
+            title: "v1.0".to_string(),
+            description: String::new(),
+            metadata: MilestoneMetadata {
+                target_date: Some("2024-06-01".to_string()),
+                state: MilestoneState::Open,
+                created_at: "2024-01-01T00:00:00Z".to_string(),
+                updated_at: "2024-01-01T00:00:00Z".to_string(),
+            },
+        };
+        let json = serde_json::to_string(&file).unwrap();
+        assert!(json.contains(r#""targetDate":"2024-06-01""#));
+        assert!(json.contains(r#""state":"open""#));
+        assert!(!json.contains("description"));
+    }
+}
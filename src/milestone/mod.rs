@@ -0,0 +1,8 @@
+mod crud;
+
+pub use crud::{
+    create_milestone, delete_milestone, get_milestone, list_milestones, milestone_exists,
+    milestone_progress, update_milestone, CreateMilestoneOptions, CreateMilestoneResult,
+    DeleteMilestoneResult, Milestone, MilestoneError, MilestoneMetadata, MilestoneProgress,
+    MilestoneState, UpdateMilestoneOptions, UpdateMilestoneResult,
+};
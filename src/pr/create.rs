@@ -1,9 +1,13 @@
-use crate::config::read_config;
+use crate::feed::{notify_change, ChangeEntity, ChangeKind};
+use crate::config::{read_config, WorkflowError};
+use crate::milestone::milestone_exists;
 use crate::manifest::{
     read_manifest, write_manifest, update_manifest_timestamp, CentyManifest,
 };
-use crate::utils::get_centy_path;
+use crate::search::index_pr;
+use crate::utils::{get_centy_path, lock_project, write_files_atomic};
 use crate::issue::priority::{default_priority, validate_priority, PriorityError};
+use super::crud::read_pr_from_disk;
 use super::git::{detect_current_branch, get_default_branch, is_git_repository, validate_branch_exists, GitError};
 use super::id::generate_pr_id;
 use super::metadata::PrMetadata;
@@ -47,11 +51,17 @@ pub enum PrError {
     #[error("Not a git repository")]
     NotGitRepository,
 
+    #[error("Workflow error: {0}")]
+    WorkflowError(#[from] WorkflowError),
+
     #[error("Source branch '{0}' does not exist")]
     SourceBranchNotFound(String),
 
     #[error("Target branch '{0}' does not exist")]
     TargetBranchNotFound(String),
+
+    #[error("Milestone '{0}' not found")]
+    MilestoneNotFound(String),
 }
 
 /// Options for creating a PR
@@ -74,6 +84,8 @@ pub struct CreatePrOptions {
     pub custom_fields: HashMap<String, String>,
     /// Optional template name (without .md extension)
     pub template: Option<String>,
+    /// Milestone slug. None = no milestone.
+    pub milestone: Option<String>,
 }
 
 /// Result of PR creation
@@ -120,7 +132,9 @@ pub async fn create_pr(
     // Generate UUID for folder name (prevents git conflicts)
     let pr_id = generate_pr_id();
 
-    // Get next display number for human-readable reference
+    // Get next display number for human-readable reference. The lock is held
+    // until the PR is on disk, so no other writer can take the same number.
+    let _lock = lock_project(project_path).await?;
     let display_number = get_next_pr_display_number(&prs_path).await?;
 
     // Determine source branch
@@ -191,7 +205,10 @@ pub async fn create_pr(
     // Get allowed PR statuses from config or use defaults
     let allowed_statuses = default_pr_statuses();
 
-    // Lenient validation: log warning if status is not in allowed_states
+    // Lenient validation: log warning if status is not in allowed_states.
+    // A strict PR workflow rejects it instead.
+    let workflow = config.as_ref().map(|c| c.pr_workflow.clone()).unwrap_or_default();
+    workflow.enforce(workflow.state_violations(&status, &allowed_statuses))?;
     validate_pr_status(&status, &allowed_statuses);
 
     // Build custom fields with defaults from config
@@ -214,8 +231,15 @@ pub async fn create_pr(
         custom_field_values.insert(key.clone(), serde_json::Value::String(value.clone()));
     }
 
+    let milestone = options.milestone.clone().filter(|m| !m.is_empty());
+    if let Some(ref milestone) = milestone {
+        if !milestone_exists(project_path, milestone) {
+            return Err(PrError::MilestoneNotFound(milestone.clone()));
+        }
+    }
+
     // Create metadata
-    let metadata = PrMetadata::new(
+    let mut metadata = PrMetadata::new(
         display_number,
         status,
         source_branch.clone(),
@@ -225,6 +249,7 @@ pub async fn create_pr(
         priority,
         custom_field_values,
     );
+    metadata.milestone = milestone;
 
     // Create PR content
     let pr_md = generate_pr_md(&options.title, &options.description);
@@ -237,8 +262,13 @@ pub async fn create_pr(
     let metadata_path = pr_folder.join("metadata.json");
     let assets_path = pr_folder.join("assets");
 
-    fs::write(&pr_md_path, &pr_md).await?;
-    fs::write(&metadata_path, serde_json::to_string_pretty(&metadata)?).await?;
+    // Metadata last: without it the folder is not read as a PR
+    let metadata_json = serde_json::to_string_pretty(&metadata)?;
+    write_files_atomic(&[
+        (pr_md_path.as_path(), pr_md.as_bytes()),
+        (metadata_path.as_path(), metadata_json.as_bytes()),
+    ])
+    .await?;
     fs::create_dir_all(&assets_path).await?;
 
     // Update manifest timestamp
@@ -246,6 +276,11 @@ pub async fn create_pr(
     update_manifest_timestamp(&mut manifest);
     write_manifest(project_path, &manifest).await?;
 
+    if let Ok(pr) = read_pr_from_disk(&pr_folder, &pr_id).await {
+        index_pr(project_path, &pr);
+    }
+    notify_change(project_path, ChangeEntity::Pr, ChangeKind::Created, &pr_id);
+
     let created_files = vec![
         format!(".centy/prs/{}/pr.md", pr_id),
         format!(".centy/prs/{}/metadata.json", pr_id),
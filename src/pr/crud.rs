@@ -1,14 +1,18 @@
-use crate::config::read_config;
+use crate::feed::{notify_change, ChangeEntity, ChangeKind};
+use crate::config::{read_config, WorkflowError};
+use crate::milestone::milestone_exists;
 use crate::manifest::{
     read_manifest, write_manifest, update_manifest_timestamp, CentyManifest,
 };
-use crate::utils::{get_centy_path, now_iso};
+use crate::search::{index_pr, invalidate_project, remove_pr};
+use crate::trash::{move_to_trash, RemovedLinks, TrashKind};
+use crate::utils::{get_centy_path, lock_project, now_iso, try_lock_project, write_files_atomic};
 use crate::issue::priority::{validate_priority, PriorityError};
 use super::id::is_valid_pr_folder;
 use super::metadata::PrMetadata;
 use super::reconcile::{reconcile_pr_display_numbers, ReconcileError};
 use super::status::{default_pr_statuses, validate_pr_status};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use thiserror::Error;
 use tokio::fs;
@@ -41,6 +45,15 @@ pub enum PrCrudError {
 
     #[error("Reconcile error: {0}")]
     ReconcileError(#[from] ReconcileError),
+
+    #[error("Workflow error: {0}")]
+    WorkflowError(#[from] WorkflowError),
+
+    #[error("Milestone '{0}' not found")]
+    MilestoneNotFound(String),
+
+    #[error("PR {} was changed by someone else (last updated {})", .0.id, .0.metadata.updated_at)]
+    Conflict(Box<PullRequest>),
 }
 
 /// Full PR data
@@ -72,6 +85,10 @@ pub struct PrMetadataFlat {
     /// Timestamp when PR was closed (empty if not closed)
     pub closed_at: String,
     pub custom_fields: HashMap<String, String>,
+    /// Milestone slug
+    pub milestone: Option<String>,
+    /// Numbers the PR had before being renumbered, oldest first
+    pub previous_display_numbers: Vec<u32>,
 }
 
 /// Options for updating a PR
@@ -87,6 +104,11 @@ pub struct UpdatePrOptions {
     /// Priority as a number (1 = highest). None = don't update.
     pub priority: Option<u32>,
     pub custom_fields: HashMap<String, String>,
+    /// Milestone slug. None = don't update, Some(empty) = clear.
+    pub milestone: Option<String>,
+    /// Fail with `Conflict` unless the PR's `updated_at` still has this
+    /// value. None = overwrite whatever is stored.
+    pub expected_updated_at: Option<String>,
 }
 
 /// Result of PR update
@@ -94,6 +116,8 @@ pub struct UpdatePrOptions {
 pub struct UpdatePrResult {
     pub pr: PullRequest,
     pub manifest: CentyManifest,
+    /// Workflow rule violations accepted because the workflow is not strict
+    pub warnings: Vec<String>,
 }
 
 /// Result of PR deletion
@@ -142,47 +166,16 @@ pub async fn list_prs(
         return Ok(Vec::new());
     }
 
-    // Reconcile display numbers to resolve any conflicts from concurrent creation
-    reconcile_pr_display_numbers(&prs_path).await?;
-
-    let mut prs = Vec::new();
-    let mut entries = fs::read_dir(&prs_path).await?;
-
-    while let Some(entry) = entries.next_entry().await? {
-        if entry.file_type().await?.is_dir() {
-            if let Some(folder_name) = entry.file_name().to_str() {
-                if !is_valid_pr_folder(folder_name) {
-                    continue;
-                }
-
-                match read_pr_from_disk(&entry.path(), folder_name).await {
-                    Ok(pr) => {
-                        // Apply filters
-                        let status_match = status_filter
-                            .map(|s| pr.metadata.status == s)
-                            .unwrap_or(true);
-                        let source_match = source_branch_filter
-                            .map(|s| pr.metadata.source_branch == s)
-                            .unwrap_or(true);
-                        let target_match = target_branch_filter
-                            .map(|t| pr.metadata.target_branch == t)
-                            .unwrap_or(true);
-                        let priority_match = priority_filter
-                            .map(|p| pr.metadata.priority == p)
-                            .unwrap_or(true);
-
-                        if status_match && source_match && target_match && priority_match {
-                            prs.push(pr);
-                        }
-                    }
-                    Err(_) => {
-                        // Skip PRs that can't be read
-                        continue;
-                    }
-                }
-            }
-        }
-    }
+    let mut prs: Vec<PullRequest> = read_prs_reconciled(project_path, &prs_path)
+        .await?
+        .into_iter()
+        .filter(|pr| {
+            status_filter.is_none_or(|s| pr.metadata.status == s)
+                && source_branch_filter.is_none_or(|s| pr.metadata.source_branch == s)
+                && target_branch_filter.is_none_or(|t| pr.metadata.target_branch == t)
+                && priority_filter.is_none_or(|p| pr.metadata.priority == p)
+        })
+        .collect();
 
     // Sort by display number (human-readable ordering)
     prs.sort_by_key(|p| p.metadata.display_number);
@@ -207,35 +200,73 @@ pub async fn get_pr_by_display_number(
         return Err(PrCrudError::PrDisplayNumberNotFound(display_number));
     }
 
-    // Reconcile first to ensure display numbers are unique
-    reconcile_pr_display_numbers(&prs_path).await?;
+    let mut prs = read_prs_reconciled(project_path, &prs_path).await?;
+    // Oldest first: while duplicates await reconciliation the oldest PR is
+    // the one that keeps the number
+    prs.sort_by(|a, b| a.metadata.created_at.cmp(&b.metadata.created_at));
+
+    if let Some(index) = prs
+        .iter()
+        .position(|pr| pr.metadata.display_number == display_number)
+    {
+        return Ok(prs.swap_remove(index));
+    }
+    // Oldest PR that had the number before reconciliation renumbered it
+    if let Some(index) = prs
+        .iter()
+        .position(|pr| pr.metadata.previous_display_numbers.contains(&display_number))
+    {
+        return Ok(prs.swap_remove(index));
+    }
+    Err(PrCrudError::PrDisplayNumberNotFound(display_number))
+}
+
+/// Read every PR, reconciling display numbers first if two PRs share one.
+///
+/// Reconciling rewrites other PRs' metadata, so it only runs when the project
+/// lock is free; otherwise the duplicates are returned and a later read
+/// reconciles them.
+async fn read_prs_reconciled(
+    project_path: &Path,
+    prs_path: &Path,
+) -> Result<Vec<PullRequest>, PrCrudError> {
+    let prs = read_prs(prs_path).await?;
+    let mut seen = HashSet::new();
+    if prs.iter().all(|pr| seen.insert(pr.metadata.display_number)) {
+        return Ok(prs);
+    }
+
+    let Some(_lock) = try_lock_project(project_path).await? else {
+        return Ok(prs);
+    };
+    if reconcile_pr_display_numbers(prs_path).await? == 0 {
+        return Ok(prs);
+    }
+    invalidate_project(project_path);
+    read_prs(prs_path).await
+}
 
-    let mut entries = fs::read_dir(&prs_path).await?;
+/// Read every PR folder, skipping PRs that can't be read
+async fn read_prs(prs_path: &Path) -> Result<Vec<PullRequest>, PrCrudError> {
+    let mut prs = Vec::new();
+    let mut entries = fs::read_dir(prs_path).await?;
 
     while let Some(entry) = entries.next_entry().await? {
-        if entry.file_type().await?.is_dir() {
-            if let Some(folder_name) = entry.file_name().to_str() {
-                if !is_valid_pr_folder(folder_name) {
-                    continue;
-                }
-
-                let metadata_path = entry.path().join("metadata.json");
-                if !metadata_path.exists() {
-                    continue;
-                }
-
-                if let Ok(content) = fs::read_to_string(&metadata_path).await {
-                    if let Ok(metadata) = serde_json::from_str::<PrMetadata>(&content) {
-                        if metadata.display_number == display_number {
-                            return read_pr_from_disk(&entry.path(), folder_name).await;
-                        }
-                    }
-                }
-            }
+        if !entry.file_type().await?.is_dir() {
+            continue;
+        }
+        let Some(folder_name) = entry.file_name().to_str().map(String::from) else {
+            continue;
+        };
+        if !is_valid_pr_folder(&folder_name) {
+            continue;
+        }
+        if let Ok(pr) = read_pr_from_disk(&entry.path(), &folder_name).await {
+            prs.push(pr);
         }
     }
 
-    Err(PrCrudError::PrDisplayNumberNotFound(display_number))
+    Ok(prs)
 }
 
 /// Update an existing PR
@@ -260,13 +291,21 @@ pub async fn update_pr(
     let config = read_config(project_path).await.ok().flatten();
     let priority_levels = config.as_ref().map(|c| c.priority_levels).unwrap_or(3);
 
-    // Read current PR
+    // Read current PR. Nobody may write until it is written back.
+    let _lock = lock_project(project_path).await?;
     let current = read_pr_from_disk(&pr_path, pr_id).await?;
+    if options
+        .expected_updated_at
+        .as_ref()
+        .is_some_and(|expected| *expected != current.metadata.updated_at)
+    {
+        return Err(PrCrudError::Conflict(Box::new(current)));
+    }
 
     // Apply updates
     let new_title = options.title.unwrap_or(current.title);
     let new_description = options.description.unwrap_or(current.description);
-    let new_status = options.status.unwrap_or(current.metadata.status);
+    let new_status = options.status.unwrap_or(current.metadata.status.clone());
     let new_source_branch = options.source_branch.unwrap_or(current.metadata.source_branch);
     let new_target_branch = options.target_branch.unwrap_or(current.metadata.target_branch);
     let new_linked_issues = options.linked_issues.unwrap_or(current.metadata.linked_issues);
@@ -275,6 +314,14 @@ pub async fn update_pr(
     // Get allowed PR statuses from config or use defaults
     let allowed_statuses = default_pr_statuses();
 
+    // Check the status change against the PR workflow (warns, or fails in strict mode)
+    let workflow = config.as_ref().map(|c| c.pr_workflow.clone()).unwrap_or_default();
+    let warnings = workflow.enforce(workflow.transition_violations(
+        &current.metadata.status,
+        &new_status,
+        &allowed_statuses,
+    ))?;
+
     // Lenient validation: log warning if status is not in allowed_states
     validate_pr_status(&new_status, &allowed_statuses);
 
@@ -298,6 +345,18 @@ pub async fn update_pr(
         None => current.metadata.priority,
     };
 
+    // Move to another milestone if requested
+    let new_milestone = match options.milestone {
+        Some(milestone) if milestone.is_empty() => None,
+        Some(milestone) => {
+            if !milestone_exists(project_path, &milestone) {
+                return Err(PrCrudError::MilestoneNotFound(milestone));
+            }
+            Some(milestone)
+        }
+        None => current.metadata.milestone.clone(),
+    };
+
     // Merge custom fields
     let mut new_custom_fields = current.metadata.custom_fields;
     for (key, value) in options.custom_fields {
@@ -321,6 +380,8 @@ pub async fn update_pr(
             .iter()
             .map(|(k, v)| (k.clone(), serde_json::Value::String(v.clone())))
             .collect(),
+        milestone: new_milestone.clone(),
+        previous_display_numbers: current.metadata.previous_display_numbers.clone(),
     };
 
     // Generate updated content
@@ -330,8 +391,12 @@ pub async fn update_pr(
     let pr_md_path = pr_path.join("pr.md");
     let metadata_path = pr_path.join("metadata.json");
 
-    fs::write(&pr_md_path, &pr_md).await?;
-    fs::write(&metadata_path, serde_json::to_string_pretty(&updated_metadata)?).await?;
+    let metadata_json = serde_json::to_string_pretty(&updated_metadata)?;
+    write_files_atomic(&[
+        (pr_md_path.as_path(), pr_md.as_bytes()),
+        (metadata_path.as_path(), metadata_json.as_bytes()),
+    ])
+    .await?;
 
     // Update manifest timestamp
     update_manifest_timestamp(&mut manifest);
@@ -354,10 +419,15 @@ pub async fn update_pr(
             merged_at: new_merged_at,
             closed_at: new_closed_at,
             custom_fields: new_custom_fields,
+            milestone: new_milestone,
+            previous_display_numbers: current.metadata.previous_display_numbers,
         },
     };
 
-    Ok(UpdatePrResult { pr, manifest })
+    index_pr(project_path, &pr);
+    notify_change(project_path, ChangeEntity::Pr, ChangeKind::Updated, &pr.id);
+
+    Ok(UpdatePrResult { pr, manifest, warnings })
 }
 
 /// Delete a PR
@@ -369,6 +439,7 @@ pub async fn delete_pr(
     let mut manifest = read_manifest(project_path)
         .await?
         .ok_or(PrCrudError::NotInitialized)?;
+    let _lock = lock_project(project_path).await?;
 
     let centy_path = get_centy_path(project_path);
     let pr_path = centy_path.join("prs").join(pr_id);
@@ -377,8 +448,20 @@ pub async fn delete_pr(
         return Err(PrCrudError::PrNotFound(pr_id.to_string()));
     }
 
-    // Remove the PR directory
-    fs::remove_dir_all(&pr_path).await?;
+    // Move the PR directory to the trash
+    let pr = read_pr_from_disk(&pr_path, pr_id).await?;
+    move_to_trash(
+        project_path,
+        TrashKind::Pr,
+        pr_id,
+        &pr.title,
+        Some(pr.metadata.display_number),
+        &pr_path,
+        RemovedLinks::default(),
+    )
+    .await?;
+    remove_pr(project_path, pr_id);
+    notify_change(project_path, ChangeEntity::Pr, ChangeKind::Deleted, pr_id);
 
     // Update manifest timestamp
     update_manifest_timestamp(&mut manifest);
@@ -388,7 +471,7 @@ pub async fn delete_pr(
 }
 
 /// Read a PR from disk
-async fn read_pr_from_disk(pr_path: &Path, pr_id: &str) -> Result<PullRequest, PrCrudError> {
+pub(crate) async fn read_pr_from_disk(pr_path: &Path, pr_id: &str) -> Result<PullRequest, PrCrudError> {
     let pr_md_path = pr_path.join("pr.md");
     let metadata_path = pr_path.join("metadata.json");
 
@@ -437,6 +520,8 @@ async fn read_pr_from_disk(pr_path: &Path, pr_id: &str) -> Result<PullRequest, P
             merged_at: metadata.merged_at,
             closed_at: metadata.closed_at,
             custom_fields,
+            milestone: metadata.milestone,
+            previous_display_numbers: metadata.previous_display_numbers,
         },
     })
 }
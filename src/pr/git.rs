@@ -92,6 +92,31 @@ pub fn is_git_repository(project_path: &Path) -> bool {
         .unwrap_or(false)
 }
 
+/// Get the email of the local git identity.
+///
+/// Runs `git config user.email` in the given project path, so repository
+/// settings take precedence over global ones. Returns `None` when no email
+/// is configured.
+pub fn get_git_user_email(project_path: &Path) -> Result<Option<String>, GitError> {
+    let output = Command::new("git")
+        .args(["config", "user.email"])
+        .current_dir(project_path)
+        .output()
+        .map_err(|e| GitError::CommandError(e.to_string()))?;
+
+    // `git config` exits with status 1 when the key is not set
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let email = String::from_utf8(output.stdout)
+        .map_err(|_| GitError::InvalidUtf8)?
+        .trim()
+        .to_string();
+
+    Ok(if email.is_empty() { None } else { Some(email) })
+}
+
 /// Get the default branch name (main or master).
 ///
 /// Checks if 'main' exists first, then falls back to 'master'.
@@ -34,6 +34,12 @@ pub struct PrMetadata {
     pub closed_at: String,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub custom_fields: HashMap<String, serde_json::Value>,
+    /// Milestone slug
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<String>,
+    /// Numbers the PR had before being renumbered, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub previous_display_numbers: Vec<u32>,
 }
 
 impl PrMetadata {
@@ -61,6 +67,8 @@ impl PrMetadata {
             merged_at: String::new(),
             closed_at: String::new(),
             custom_fields,
+            milestone: None,
+            previous_display_numbers: Vec::new(),
         }
     }
 }
@@ -11,8 +11,8 @@ pub use crud::{
     delete_pr, get_pr, get_pr_by_display_number, list_prs, update_pr,
     DeletePrResult, PrCrudError, PrMetadataFlat, PullRequest, UpdatePrOptions, UpdatePrResult,
 };
-pub use git::{detect_current_branch, validate_branch_exists, GitError};
+pub use git::{detect_current_branch, get_git_user_email, validate_branch_exists, GitError};
 pub use id::{generate_pr_id, is_uuid, is_valid_pr_folder, short_id};
 pub use metadata::PrMetadata;
 pub use reconcile::{get_next_pr_display_number, reconcile_pr_display_numbers, ReconcileError};
-pub use status::validate_pr_status;
+pub use status::{default_pr_statuses, validate_pr_status};
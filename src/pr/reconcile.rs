@@ -5,6 +5,7 @@
 //! 1. Keeping the oldest PR's display number (by created_at)
 //! 2. Reassigning newer PRs to the next available number
 
+use crate::utils::write_atomic;
 use super::id::is_valid_pr_folder;
 use super::metadata::PrMetadata;
 use std::collections::HashMap;
@@ -130,11 +131,15 @@ pub async fn reconcile_pr_display_numbers(prs_path: &Path) -> Result<u32, Reconc
         let content = fs::read_to_string(&metadata_path).await?;
         let mut metadata: PrMetadata = serde_json::from_str(&content)?;
 
+        // Keep the old number so links that used it still find this PR
+        if metadata.display_number != 0 {
+            metadata.previous_display_numbers.push(metadata.display_number);
+        }
         metadata.display_number = new_display_number;
         metadata.updated_at = crate::utils::now_iso();
 
         let new_content = serde_json::to_string_pretty(&metadata)?;
-        fs::write(&metadata_path, new_content).await?;
+        write_atomic(&metadata_path, new_content).await?;
     }
 
     Ok(reassignment_count)
@@ -298,6 +303,7 @@ mod tests {
         )
         .unwrap();
         assert_eq!(metadata2.display_number, 6);
+        assert_eq!(metadata2.previous_display_numbers, vec![4]);
     }
 
     #[tokio::test]
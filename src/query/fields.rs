@@ -0,0 +1,163 @@
+use crate::docs::Doc;
+use crate::issue::Issue;
+use crate::pr::PullRequest;
+
+/// An item that list queries can filter and sort
+pub trait Queryable {
+    /// Field used to order items when the sort spec leaves a tie
+    const DEFAULT_SORT: &'static str;
+
+    /// Whether `field` (lowercase) can be used in a query or sort spec
+    fn has_field(field: &str) -> bool;
+
+    /// Stable identity, used as the last tie-breaker and in page tokens
+    fn query_id(&self) -> &str;
+
+    /// Values of a field. An empty list means the field is unset.
+    fn field_values(&self, field: &str) -> Vec<String>;
+
+    /// Whether the item's text contains `needle`, which is already lowercase
+    fn contains_text(&self, needle: &str) -> bool;
+}
+
+/// Custom fields are addressed as `cf.<name>`
+fn custom_field_name(field: &str) -> Option<&str> {
+    field.strip_prefix("cf.").filter(|name| !name.is_empty())
+}
+
+fn non_empty(value: &str) -> Vec<String> {
+    if value.is_empty() {
+        Vec::new()
+    } else {
+        vec![value.to_string()]
+    }
+}
+
+fn contains_lowercase(haystack: &str, needle: &str) -> bool {
+    haystack.to_lowercase().contains(needle)
+}
+
+const ISSUE_FIELDS: &[&str] = &[
+    "id", "number", "title", "status", "priority", "label", "labels", "assignee", "assignees",
+    "milestone", "parent", "created", "updated", "start", "due", "archived",
+];
+
+impl Queryable for Issue {
+    const DEFAULT_SORT: &'static str = "number";
+
+    fn has_field(field: &str) -> bool {
+        ISSUE_FIELDS.contains(&field) || custom_field_name(field).is_some()
+    }
+
+    fn query_id(&self) -> &str {
+        &self.id
+    }
+
+    fn field_values(&self, field: &str) -> Vec<String> {
+        let m = &self.metadata;
+        match field {
+            "id" => vec![self.id.clone()],
+            "number" => vec![m.display_number.to_string()],
+            "title" => vec![self.title.clone()],
+            "status" => vec![m.status.clone()],
+            "priority" => vec![m.priority.to_string()],
+            "label" | "labels" => m.labels.clone(),
+            "assignee" | "assignees" => m.assignees.clone(),
+            "milestone" => m.milestone.iter().cloned().collect(),
+            "parent" => m.parent_id.iter().cloned().collect(),
+            "created" => non_empty(&m.created_at),
+            "updated" => non_empty(&m.updated_at),
+            "start" => m.start_date.iter().cloned().collect(),
+            "due" => m.due_date.iter().cloned().collect(),
+            "archived" => vec![m.archived.to_string()],
+            _ => match custom_field_name(field) {
+                // List fields match on each element
+                Some(name) => match m.custom_field_values.get(name) {
+                    Some(serde_json::Value::Array(items)) => items
+                        .iter()
+                        .map(|v| v.as_str().map_or_else(|| v.to_string(), str::to_string))
+                        .collect(),
+                    _ => m.custom_fields.get(name).map(|v| non_empty(v)).unwrap_or_default(),
+                },
+                None => Vec::new(),
+            },
+        }
+    }
+
+    fn contains_text(&self, needle: &str) -> bool {
+        contains_lowercase(&self.title, needle) || contains_lowercase(&self.description, needle)
+    }
+}
+
+const PR_FIELDS: &[&str] = &[
+    "id", "number", "title", "status", "priority", "source", "target", "reviewer", "reviewers",
+    "issue", "issues", "milestone", "created", "updated", "merged", "closed",
+];
+
+impl Queryable for PullRequest {
+    const DEFAULT_SORT: &'static str = "number";
+
+    fn has_field(field: &str) -> bool {
+        PR_FIELDS.contains(&field) || custom_field_name(field).is_some()
+    }
+
+    fn query_id(&self) -> &str {
+        &self.id
+    }
+
+    fn field_values(&self, field: &str) -> Vec<String> {
+        let m = &self.metadata;
+        match field {
+            "id" => vec![self.id.clone()],
+            "number" => vec![m.display_number.to_string()],
+            "title" => vec![self.title.clone()],
+            "status" => vec![m.status.clone()],
+            "priority" => vec![m.priority.to_string()],
+            "source" => non_empty(&m.source_branch),
+            "target" => non_empty(&m.target_branch),
+            "reviewer" | "reviewers" => m.reviewers.clone(),
+            "issue" | "issues" => m.linked_issues.clone(),
+            "milestone" => m.milestone.iter().cloned().collect(),
+            "created" => non_empty(&m.created_at),
+            "updated" => non_empty(&m.updated_at),
+            "merged" => non_empty(&m.merged_at),
+            "closed" => non_empty(&m.closed_at),
+            _ => custom_field_name(field)
+                .and_then(|name| m.custom_fields.get(name))
+                .map(|value| non_empty(value))
+                .unwrap_or_default(),
+        }
+    }
+
+    fn contains_text(&self, needle: &str) -> bool {
+        contains_lowercase(&self.title, needle) || contains_lowercase(&self.description, needle)
+    }
+}
+
+const DOC_FIELDS: &[&str] = &["slug", "title", "created", "updated"];
+
+impl Queryable for Doc {
+    const DEFAULT_SORT: &'static str = "slug";
+
+    fn has_field(field: &str) -> bool {
+        DOC_FIELDS.contains(&field)
+    }
+
+    fn query_id(&self) -> &str {
+        &self.slug
+    }
+
+    fn field_values(&self, field: &str) -> Vec<String> {
+        match field {
+            "slug" => vec![self.slug.clone()],
+            "title" => vec![self.title.clone()],
+            "created" => non_empty(&self.metadata.created_at),
+            "updated" => non_empty(&self.metadata.updated_at),
+            _ => Vec::new(),
+        }
+    }
+
+    fn contains_text(&self, needle: &str) -> bool {
+        contains_lowercase(&self.title, needle) || contains_lowercase(&self.content, needle)
+    }
+}
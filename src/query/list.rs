@@ -0,0 +1,299 @@
+use super::fields::Queryable;
+use super::parse::{parse_query, parse_sort, CompareOp, Condition, Query, QueryError, SortKey, Term};
+use crate::config::read_config;
+use crate::issue::find_member;
+use crate::pr::git::get_git_user_email;
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::path::Path;
+
+/// Filtering, sorting and paging applied to a list
+#[derive(Debug, Clone, Default)]
+pub struct ListQuery {
+    /// Query string, e.g. `status:open priority:<=2 "login"`. Empty = everything.
+    pub query: String,
+    /// Sort spec, e.g. `priority,-updated`. Empty = the list's default order.
+    pub sort: String,
+    /// Maximum items per page. 0 = no limit.
+    pub page_size: u32,
+    /// Token from a previous page's `next_page_token`. Empty = first page.
+    pub page_token: String,
+}
+
+/// Per-request values that query terms can refer to
+#[derive(Debug, Clone, Default)]
+pub struct QueryContext {
+    /// What `me` stands for: the local git user's member handle, or their
+    /// email when no members are configured
+    pub me: Option<String>,
+}
+
+impl QueryContext {
+    /// Build the context a query needs. Git is only consulted when the query
+    /// mentions `me`.
+    pub async fn for_query(project_path: &Path, query: &ListQuery) -> Self {
+        let mentions_me = parse_query(&query.query).is_ok_and(|parsed| {
+            parsed.terms.iter().any(|term| match &term.condition {
+                Condition::Field { values, .. } => values.iter().any(|v| v.eq_ignore_ascii_case("me")),
+                Condition::Text(_) => false,
+            })
+        });
+        if !mentions_me {
+            return Self::default();
+        }
+
+        let members = read_config(project_path)
+            .await
+            .ok()
+            .flatten()
+            .map(|c| c.members)
+            .unwrap_or_default();
+        let me = get_git_user_email(project_path)
+            .ok()
+            .flatten()
+            .map(|email| match find_member(&members, &email) {
+                Some(member) => member.handle.clone(),
+                None => email,
+            });
+        Self { me }
+    }
+}
+
+/// One page of query results
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Number of items matching the query across all pages
+    pub total_count: usize,
+    /// Token for the next page, or None on the last page
+    pub next_page_token: Option<String>,
+}
+
+/// Position after the last item of a page. Keyed on sort values rather than
+/// an offset, so items created or deleted meanwhile don't shift later pages.
+#[derive(Debug, Serialize, Deserialize)]
+struct PageCursor {
+    keys: Vec<Option<String>>,
+    id: String,
+}
+
+/// Filter, sort and page a list of items
+pub fn apply_list_query<T: Queryable>(
+    items: Vec<T>,
+    query: &ListQuery,
+    context: &QueryContext,
+) -> Result<Page<T>, QueryError> {
+    let (parsed, mut sort) = parse_list_query::<T>(&query.query, &query.sort)?;
+    sort.push(SortKey {
+        field: T::DEFAULT_SORT.to_string(),
+        descending: false,
+    });
+
+    let mut matching: Vec<(Vec<Option<String>>, T)> = items
+        .into_iter()
+        .filter(|item| parsed.terms.iter().all(|term| term_matches(item, term, context)))
+        .map(|item| (sort_values(&item, &sort), item))
+        .collect();
+    matching.sort_by(|(a_keys, a), (b_keys, b)| {
+        compare_sort_values(a_keys, b_keys, &sort).then_with(|| a.query_id().cmp(b.query_id()))
+    });
+    let total_count = matching.len();
+
+    let start = if query.page_token.is_empty() {
+        0
+    } else {
+        let cursor = decode_page_token(&query.page_token)?;
+        if cursor.keys.len() != sort.len() {
+            return Err(QueryError::InvalidPageToken);
+        }
+        matching.partition_point(|(keys, item)| {
+            compare_sort_values(keys, &cursor.keys, &sort)
+                .then_with(|| item.query_id().cmp(&cursor.id))
+                != Ordering::Greater
+        })
+    };
+
+    let mut page: Vec<_> = matching.into_iter().skip(start).collect();
+    let next_page_token = if query.page_size > 0 && page.len() > query.page_size as usize {
+        page.truncate(query.page_size as usize);
+        page.last().map(|(keys, item)| {
+            encode_page_token(&PageCursor {
+                keys: keys.clone(),
+                id: item.query_id().to_string(),
+            })
+        })
+    } else {
+        None
+    };
+
+    Ok(Page {
+        items: page.into_iter().map(|(_, item)| item).collect(),
+        total_count,
+        next_page_token,
+    })
+}
+
+/// Check that a query and sort spec parse and only use fields of `T`
+pub fn validate_list_query<T: Queryable>(query: &str, sort: &str) -> Result<(), QueryError> {
+    parse_list_query::<T>(query, sort).map(|_| ())
+}
+
+fn parse_list_query<T: Queryable>(query: &str, sort: &str) -> Result<(Query, Vec<SortKey>), QueryError> {
+    let parsed = parse_query(query)?;
+    let sort = parse_sort(sort);
+    for term in &parsed.terms {
+        if let Condition::Field { field, .. } = &term.condition {
+            check_field::<T>(field)?;
+        }
+    }
+    for key in &sort {
+        check_field::<T>(&key.field)?;
+    }
+    Ok((parsed, sort))
+}
+
+fn check_field<T: Queryable>(field: &str) -> Result<(), QueryError> {
+    if T::has_field(field) {
+        Ok(())
+    } else {
+        Err(QueryError::UnknownField(field.to_string()))
+    }
+}
+
+fn term_matches<T: Queryable>(item: &T, term: &Term, context: &QueryContext) -> bool {
+    let matched = match &term.condition {
+        Condition::Text(text) => item.contains_text(&text.to_lowercase()),
+        Condition::Field { field, op, values } => {
+            let actual = item.field_values(field);
+            values.iter().any(|value| {
+                if value.eq_ignore_ascii_case("none") {
+                    return actual.is_empty();
+                }
+                let value = match &context.me {
+                    Some(me) if value.eq_ignore_ascii_case("me") => me,
+                    _ => value,
+                };
+                actual.iter().any(|a| op_matches(*op, compare_to_query(a, value)))
+            })
+        }
+    };
+    matched != term.negated
+}
+
+fn op_matches(op: CompareOp, ordering: Ordering) -> bool {
+    match op {
+        CompareOp::Eq => ordering == Ordering::Equal,
+        CompareOp::Lt => ordering == Ordering::Less,
+        CompareOp::Lte => ordering != Ordering::Greater,
+        CompareOp::Gt => ordering == Ordering::Greater,
+        CompareOp::Gte => ordering != Ordering::Less,
+    }
+}
+
+/// Compare a field value against a query value. A `YYYY-MM-DD` query value
+/// compares by day, so `updated:2026-01-01` matches any time that day.
+fn compare_to_query(actual: &str, query: &str) -> Ordering {
+    if let Ok(day) = NaiveDate::parse_from_str(query, "%Y-%m-%d") {
+        if let Some(actual_day) = parse_day(actual) {
+            return actual_day.cmp(&day);
+        }
+    }
+    compare_values(actual, query)
+}
+
+/// Compare two field values: numerically, then as timestamps, then as
+/// case-insensitive text
+fn compare_values(a: &str, b: &str) -> Ordering {
+    if let (Ok(a), Ok(b)) = (a.parse::<f64>(), b.parse::<f64>()) {
+        return a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+    }
+    if let (Ok(a), Ok(b)) = (DateTime::parse_from_rfc3339(a), DateTime::parse_from_rfc3339(b)) {
+        return a.cmp(&b);
+    }
+    a.to_lowercase().cmp(&b.to_lowercase())
+}
+
+/// A field value as it orders in a sort. Kinds are never compared with each
+/// other: numbers sort before timestamps, which sort before text, so a field
+/// mixing kinds (e.g. titles "9" and "1a") still has a total order.
+enum SortValue {
+    Number(f64),
+    Time(DateTime<FixedOffset>),
+    Text(String),
+}
+
+impl SortValue {
+    fn parse(value: &str) -> Self {
+        if let Ok(number) = value.parse::<f64>() {
+            return SortValue::Number(number);
+        }
+        if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+            return SortValue::Time(time);
+        }
+        SortValue::Text(value.to_lowercase())
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            SortValue::Number(_) => 0,
+            SortValue::Time(_) => 1,
+            SortValue::Text(_) => 2,
+        }
+    }
+
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (SortValue::Number(a), SortValue::Number(b)) => a.total_cmp(b),
+            (SortValue::Time(a), SortValue::Time(b)) => a.cmp(b),
+            (SortValue::Text(a), SortValue::Text(b)) => a.cmp(b),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+fn compare_sort_value(a: &str, b: &str) -> Ordering {
+    SortValue::parse(a).cmp(&SortValue::parse(b))
+}
+
+fn parse_day(value: &str) -> Option<NaiveDate> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|t| t.date_naive())
+        .or_else(|_| NaiveDate::parse_from_str(value, "%Y-%m-%d"))
+        .ok()
+}
+
+/// The value each sort key orders by: the first value of the field
+fn sort_values<T: Queryable>(item: &T, sort: &[SortKey]) -> Vec<Option<String>> {
+    sort.iter()
+        .map(|key| item.field_values(&key.field).into_iter().next())
+        .collect()
+}
+
+/// Unset values sort last in either direction
+fn compare_sort_values(a: &[Option<String>], b: &[Option<String>], sort: &[SortKey]) -> Ordering {
+    for ((a, b), key) in a.iter().zip(b).zip(sort) {
+        let ordering = match (a, b) {
+            (Some(a), Some(b)) if key.descending => compare_sort_value(b, a),
+            (Some(a), Some(b)) => compare_sort_value(a, b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+fn encode_page_token(cursor: &PageCursor) -> String {
+    hex::encode(serde_json::to_vec(cursor).unwrap_or_default())
+}
+
+fn decode_page_token(token: &str) -> Result<PageCursor, QueryError> {
+    hex::decode(token)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .ok_or(QueryError::InvalidPageToken)
+}
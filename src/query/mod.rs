@@ -0,0 +1,15 @@
+//! Query language, sorting and pagination shared by the list RPCs.
+//!
+//! A query is a list of whitespace-separated terms that must all match:
+//! `field:value` (with `,` for alternatives and `<`, `<=`, `>`, `>=` for
+//! ranges), a leading `-` to negate a term, and bare words or quoted
+//! phrases for free text. `none` matches an unset field and `me` stands for
+//! the local git user.
+
+mod fields;
+mod list;
+mod parse;
+
+pub use fields::Queryable;
+pub use list::{apply_list_query, validate_list_query, ListQuery, Page, QueryContext};
+pub use parse::{parse_query, parse_sort, CompareOp, Condition, Query, QueryError, SortKey, Term};
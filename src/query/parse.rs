@@ -0,0 +1,248 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    #[error("Unterminated quote in query")]
+    UnterminatedQuote,
+
+    #[error("Missing value for '{0}'")]
+    EmptyValue(String),
+
+    #[error("Unknown field '{0}'")]
+    UnknownField(String),
+
+    #[error("Invalid page token")]
+    InvalidPageToken,
+}
+
+/// How a field term compares values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+}
+
+/// What a single query term tests
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Condition {
+    /// `field:[op]value[,value...]` - matches when any value matches
+    Field {
+        field: String,
+        op: CompareOp,
+        values: Vec<String>,
+    },
+    /// Bare word or quoted phrase, matched case-insensitively against the item's text
+    Text(String),
+}
+
+/// One whitespace-separated term. A leading `-` negates it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Term {
+    pub negated: bool,
+    pub condition: Condition,
+}
+
+/// A parsed query: every term must match
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Query {
+    pub terms: Vec<Term>,
+}
+
+/// One key of a sort spec such as `priority,-updated`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortKey {
+    pub field: String,
+    pub descending: bool,
+}
+
+/// Parse a query string such as
+/// `status:open,in-progress priority:<=2 -label:wontfix "login page"`.
+///
+/// Field names are case-insensitive. Values may be quoted to include spaces
+/// or commas.
+pub fn parse_query(input: &str) -> Result<Query, QueryError> {
+    let terms = split_terms(input)?
+        .into_iter()
+        .map(|token| parse_term(&token))
+        .collect::<Result<_, _>>()?;
+    Ok(Query { terms })
+}
+
+/// Parse a comma-separated sort spec. A leading `-` sorts that key descending.
+pub fn parse_sort(input: &str) -> Vec<SortKey> {
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(|key| match key.strip_prefix('-') {
+            Some(field) => SortKey {
+                field: field.trim().to_lowercase(),
+                descending: true,
+            },
+            None => SortKey {
+                field: key.trim_start_matches('+').trim().to_lowercase(),
+                descending: false,
+            },
+        })
+        .collect()
+}
+
+/// Split on whitespace outside of double quotes, keeping the quotes
+fn split_terms(input: &str) -> Result<Vec<String>, QueryError> {
+    let mut terms = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in input.chars() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+            current.push(c);
+        } else if c.is_whitespace() && !in_quotes {
+            if !current.is_empty() {
+                terms.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(c);
+        }
+    }
+
+    if in_quotes {
+        return Err(QueryError::UnterminatedQuote);
+    }
+    if !current.is_empty() {
+        terms.push(current);
+    }
+    Ok(terms)
+}
+
+fn parse_term(token: &str) -> Result<Term, QueryError> {
+    let (negated, body) = match token.strip_prefix('-') {
+        Some(rest) if !rest.is_empty() => (true, rest),
+        _ => (false, token),
+    };
+
+    // A colon only separates a field when it comes before any quote
+    let field_split = body
+        .find(':')
+        .filter(|&i| i > 0 && !body[..i].contains('"'));
+
+    let condition = match field_split {
+        Some(i) => {
+            let field = body[..i].to_lowercase();
+            let (op, rest) = parse_op(&body[i + 1..]);
+            let values = split_values(rest);
+            if values.is_empty() || values.iter().any(String::is_empty) {
+                return Err(QueryError::EmptyValue(field));
+            }
+            Condition::Field { field, op, values }
+        }
+        None => Condition::Text(unquote(body).to_string()),
+    };
+
+    Ok(Term { negated, condition })
+}
+
+fn parse_op(value: &str) -> (CompareOp, &str) {
+    for (prefix, op) in [
+        ("<=", CompareOp::Lte),
+        (">=", CompareOp::Gte),
+        ("<", CompareOp::Lt),
+        (">", CompareOp::Gt),
+        ("=", CompareOp::Eq),
+    ] {
+        if let Some(rest) = value.strip_prefix(prefix) {
+            return (op, rest);
+        }
+    }
+    (CompareOp::Eq, value)
+}
+
+/// Split on commas outside of double quotes and strip the quotes
+fn split_values(input: &str) -> Vec<String> {
+    if input.is_empty() {
+        return Vec::new();
+    }
+
+    let mut values = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    for c in input.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => values.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    values.push(current);
+    values
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(field: &str, op: CompareOp, values: &[&str]) -> Condition {
+        Condition::Field {
+            field: field.to_string(),
+            op,
+            values: values.iter().map(|v| v.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_query_terms() {
+        let query = parse_query(
+            r#"status:open,in-progress Priority:<=2 -label:wontfix updated:>2026-01-01 "login page" crash"#,
+        )
+        .unwrap();
+        let conditions: Vec<_> = query.terms.iter().map(|t| (t.negated, t.condition.clone())).collect();
+        assert_eq!(
+            conditions,
+            vec![
+                (false, field("status", CompareOp::Eq, &["open", "in-progress"])),
+                (false, field("priority", CompareOp::Lte, &["2"])),
+                (true, field("label", CompareOp::Eq, &["wontfix"])),
+                (false, field("updated", CompareOp::Gt, &["2026-01-01"])),
+                (false, Condition::Text("login page".to_string())),
+                (false, Condition::Text("crash".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_query_quoted_values() {
+        let query = parse_query(r#"milestone:"v1, beta",v2 "a:b""#).unwrap();
+        assert_eq!(query.terms[0].condition, field("milestone", CompareOp::Eq, &["v1, beta", "v2"]));
+        assert_eq!(query.terms[1].condition, Condition::Text("a:b".to_string()));
+    }
+
+    #[test]
+    fn test_parse_query_errors() {
+        assert_eq!(parse_query(r#"title:"open"#), Err(QueryError::UnterminatedQuote));
+        assert_eq!(parse_query("status:"), Err(QueryError::EmptyValue("status".to_string())));
+        assert_eq!(parse_query("label:a,,b"), Err(QueryError::EmptyValue("label".to_string())));
+        assert!(parse_query("   ").unwrap().terms.is_empty());
+    }
+
+    #[test]
+    fn test_parse_sort() {
+        assert_eq!(
+            parse_sort("priority, -Updated,,+title"),
+            vec![
+                SortKey { field: "priority".to_string(), descending: false },
+                SortKey { field: "updated".to_string(), descending: true },
+                SortKey { field: "title".to_string(), descending: false },
+            ]
+        );
+    }
+}
@@ -2,7 +2,7 @@ use crate::manifest::{
     create_manifest, read_manifest, write_manifest, update_manifest_timestamp,
     CentyManifest, ManagedFileType,
 };
-use crate::utils::get_centy_path;
+use crate::utils::{get_centy_path, write_atomic};
 use super::managed_files::get_managed_files;
 use super::plan::build_reconciliation_plan;
 use std::collections::HashSet;
@@ -101,6 +101,14 @@ pub async fn execute_reconciliation(
     Ok(result)
 }
 
+/// Recreate one managed file or directory from its template
+pub(crate) async fn restore_managed_file(
+    project_path: &Path,
+    relative_path: &str,
+) -> Result<(), ExecuteError> {
+    create_file(&get_centy_path(project_path), relative_path, &get_managed_files()).await
+}
+
 /// Create a file or directory from template
 async fn create_file(
     centy_path: &Path,
@@ -124,7 +132,7 @@ async fn create_file(
             }
 
             let content = template.content.as_deref().unwrap_or("");
-            fs::write(&full_path, content).await?;
+            write_atomic(&full_path, content).await?;
         }
     }
 
@@ -165,6 +165,9 @@ slug: "{{slug}}"
 ```
 "#;
 
+/// Keeps runtime files out of git
+const GITIGNORE_CONTENT: &str = "# Lock taken by Centy while it writes to this folder\n.lock\n";
+
 /// Get the list of managed files with their templates
 pub fn get_managed_files() -> HashMap<String, ManagedFileTemplate> {
     let mut files = HashMap::new();
@@ -241,5 +244,13 @@ pub fn get_managed_files() -> HashMap<String, ManagedFileTemplate> {
         },
     );
 
+    files.insert(
+        ".gitignore".to_string(),
+        ManagedFileTemplate {
+            file_type: ManagedFileType::File,
+            content: Some(GITIGNORE_CONTENT.to_string()),
+        },
+    );
+
     files
 }
@@ -3,5 +3,6 @@ mod plan;
 mod execute;
 
 pub use managed_files::{ManagedFileTemplate, get_managed_files};
-pub use plan::{ReconciliationPlan, FileInfo, build_reconciliation_plan};
-pub use execute::{ReconciliationDecisions, execute_reconciliation, ReconciliationResult};
+pub use plan::{ReconciliationPlan, FileInfo, PlanError, build_reconciliation_plan};
+pub use execute::{ReconciliationDecisions, execute_reconciliation, ExecuteError, ReconciliationResult};
+pub(crate) use execute::restore_managed_file;
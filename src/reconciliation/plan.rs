@@ -1,5 +1,5 @@
 use crate::manifest::ManagedFileType;
-use crate::utils::{compute_file_hash, compute_hash, get_centy_path};
+use crate::utils::{compute_file_hash, compute_hash, get_centy_path, LOCK_FILE};
 use super::managed_files::get_managed_files;
 use std::collections::HashSet;
 use std::path::Path;
@@ -154,10 +154,13 @@ async fn scan_centy_folder(centy_path: &Path) -> HashSet<String> {
     {
         let path = entry.path();
 
-        // Skip the manifest file
+        // Skip the manifest and the write lock
         if path.file_name().map(|f| f.to_str()) == Some(Some(".centy-manifest.json")) {
             continue;
         }
+        if path.file_name().map(|f| f.to_str()) == Some(Some(LOCK_FILE)) {
+            continue;
+        }
 
         if let Ok(relative) = path.strip_prefix(centy_path) {
             let mut relative_str = relative.to_string_lossy().to_string();
@@ -1,5 +1,6 @@
 use super::types::ProjectRegistry;
 use super::RegistryError;
+use crate::utils::write_atomic;
 use std::path::PathBuf;
 use std::sync::OnceLock;
 use tokio::fs;
@@ -54,11 +55,8 @@ pub async fn write_registry_unlocked(registry: &ProjectRegistry) -> Result<(), R
         fs::create_dir_all(parent).await?;
     }
 
-    // Write atomically using temp file + rename
-    let temp_path = path.with_extension("json.tmp");
     let content = serde_json::to_string_pretty(registry)?;
-    fs::write(&temp_path, &content).await?;
-    fs::rename(&temp_path, &path).await?;
+    write_atomic(&path, &content).await?;
 
     Ok(())
 }
@@ -0,0 +1,479 @@
+use crate::docs::{list_docs, Doc, DocError};
+use crate::issue::{list_issues, Issue, IssueCrudError};
+use crate::manifest::read_manifest;
+use crate::pr::{list_prs, PrCrudError, PullRequest};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use thiserror::Error;
+
+/// Default maximum number of hits returned by a search
+pub const DEFAULT_SEARCH_LIMIT: usize = 50;
+
+/// Weight of a term occurrence in the title relative to one in the body
+const TITLE_WEIGHT: f64 = 5.0;
+
+/// Bonus for entries whose title contains the whole query as a phrase
+const TITLE_PHRASE_BONUS: f64 = 10.0;
+
+/// Maximum snippet length in characters
+const SNIPPET_MAX_CHARS: usize = 160;
+
+#[derive(Error, Debug)]
+pub enum SearchError {
+    #[error("Manifest error: {0}")]
+    ManifestError(#[from] crate::manifest::ManifestError),
+
+    #[error("Centy not initialized. Run 'centy init' first.")]
+    NotInitialized,
+
+    #[error("Search query is empty")]
+    EmptyQuery,
+
+    #[error("Unknown entity type: {0}")]
+    UnknownEntityType(String),
+
+    #[error("Issue error: {0}")]
+    IssueError(#[from] IssueCrudError),
+
+    #[error("Doc error: {0}")]
+    DocError(#[from] DocError),
+
+    #[error("PR error: {0}")]
+    PrError(#[from] PrCrudError),
+}
+
+/// Kind of entity a search hit refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SearchEntityType {
+    Issue,
+    Doc,
+    Pr,
+}
+
+impl SearchEntityType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SearchEntityType::Issue => "issue",
+            SearchEntityType::Doc => "doc",
+            SearchEntityType::Pr => "pr",
+        }
+    }
+
+    /// Parse an entity type name ("issue", "doc" or "pr")
+    pub fn parse(name: &str) -> Result<Self, SearchError> {
+        match name.to_lowercase().as_str() {
+            "issue" | "issues" => Ok(SearchEntityType::Issue),
+            "doc" | "docs" => Ok(SearchEntityType::Doc),
+            "pr" | "prs" => Ok(SearchEntityType::Pr),
+            _ => Err(SearchError::UnknownEntityType(name.to_string())),
+        }
+    }
+}
+
+/// Options for a search
+#[derive(Debug, Clone, Default)]
+pub struct SearchOptions {
+    /// Free-text query. Every term must appear in the title or body.
+    pub query: String,
+    /// Entity types to search. Empty = all.
+    pub entity_types: Vec<SearchEntityType>,
+    /// Maximum number of hits. None = `DEFAULT_SEARCH_LIMIT`.
+    pub limit: Option<usize>,
+}
+
+/// A single ranked search result
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub entity_type: SearchEntityType,
+    /// Issue/PR UUID or doc slug
+    pub id: String,
+    /// Human-readable display number (0 for docs)
+    pub display_number: u32,
+    pub title: String,
+    /// Excerpt of the body around the first match
+    pub snippet: String,
+    pub score: f64,
+}
+
+/// Hits of a search, best first
+#[derive(Debug, Clone, Default)]
+pub struct SearchResults {
+    /// At most `limit` hits
+    pub hits: Vec<SearchHit>,
+    /// Number of entities matching the query, including those past the limit
+    pub total_count: usize,
+}
+
+/// Searchable text of one entity
+#[derive(Debug, Clone)]
+struct IndexedEntry {
+    display_number: u32,
+    title: String,
+    body: String,
+    title_lower: String,
+    body_lower: String,
+}
+
+impl IndexedEntry {
+    fn new(display_number: u32, title: &str, body: &str) -> Self {
+        Self {
+            display_number,
+            title: title.to_string(),
+            body: body.to_string(),
+            title_lower: title.to_lowercase(),
+            body_lower: body.to_lowercase(),
+        }
+    }
+}
+
+type EntryKey = (SearchEntityType, String);
+
+/// In-memory index for a single project
+#[derive(Debug, Default)]
+struct ProjectIndex {
+    entries: HashMap<EntryKey, IndexedEntry>,
+}
+
+impl ProjectIndex {
+    fn insert_issue(&mut self, issue: &Issue) {
+        self.entries.insert(
+            (SearchEntityType::Issue, issue.id.clone()),
+            IndexedEntry::new(issue.metadata.display_number, &issue.title, &issue.description),
+        );
+    }
+
+    fn insert_doc(&mut self, doc: &Doc) {
+        self.entries.insert(
+            (SearchEntityType::Doc, doc.slug.clone()),
+            IndexedEntry::new(0, &doc.title, &doc.content),
+        );
+    }
+
+    fn insert_pr(&mut self, pr: &PullRequest) {
+        self.entries.insert(
+            (SearchEntityType::Pr, pr.id.clone()),
+            IndexedEntry::new(pr.metadata.display_number, &pr.title, &pr.description),
+        );
+    }
+
+    fn remove(&mut self, entity_type: SearchEntityType, id: &str) {
+        self.entries.remove(&(entity_type, id.to_string()));
+    }
+}
+
+#[derive(Default)]
+struct SearchState {
+    /// Built indexes keyed by canonical project path
+    indexes: HashMap<PathBuf, ProjectIndex>,
+    /// Per-project write counter. An index built while a write landed is
+    /// answered from once but not cached.
+    generations: HashMap<PathBuf, u64>,
+}
+
+/// Global search state shared by all projects
+static SEARCH_STATE: OnceLock<Mutex<SearchState>> = OnceLock::new();
+
+fn with_state<R>(f: impl FnOnce(&mut SearchState) -> R) -> R {
+    let mut state = SEARCH_STATE
+        .get_or_init(|| Mutex::new(SearchState::default()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    f(&mut state)
+}
+
+/// Canonicalize the project path so different spellings share one index
+fn project_key(project_path: &Path) -> PathBuf {
+    project_path
+        .canonicalize()
+        .unwrap_or_else(|_| project_path.to_path_buf())
+}
+
+/// Record a write and apply it to the project's index if one has been built
+fn apply_write(project_path: &Path, f: impl FnOnce(&mut ProjectIndex)) {
+    let key = project_key(project_path);
+    with_state(|state| {
+        *state.generations.entry(key.clone()).or_default() += 1;
+        if let Some(index) = state.indexes.get_mut(&key) {
+            f(index);
+        }
+    });
+}
+
+/// Add or refresh an issue in the search index
+pub fn index_issue(project_path: &Path, issue: &Issue) {
+    apply_write(project_path, |index| index.insert_issue(issue));
+}
+
+/// Remove an issue from the search index
+pub fn remove_issue(project_path: &Path, issue_id: &str) {
+    apply_write(project_path, |index| index.remove(SearchEntityType::Issue, issue_id));
+}
+
+/// Add or refresh a doc in the search index
+pub fn index_doc(project_path: &Path, doc: &Doc) {
+    apply_write(project_path, |index| index.insert_doc(doc));
+}
+
+/// Remove a doc from the search index
+pub fn remove_doc(project_path: &Path, slug: &str) {
+    apply_write(project_path, |index| index.remove(SearchEntityType::Doc, slug));
+}
+
+/// Add or refresh a PR in the search index
+pub fn index_pr(project_path: &Path, pr: &PullRequest) {
+    apply_write(project_path, |index| index.insert_pr(pr));
+}
+
+/// Remove a PR from the search index
+pub fn remove_pr(project_path: &Path, pr_id: &str) {
+    apply_write(project_path, |index| index.remove(SearchEntityType::Pr, pr_id));
+}
+
+/// Drop the project's index so the next search rebuilds it from disk.
+/// Used when many entities change at once (e.g. display number reconciliation).
+pub fn invalidate_project(project_path: &Path) {
+    let key = project_key(project_path);
+    with_state(|state| {
+        *state.generations.entry(key.clone()).or_default() += 1;
+        state.indexes.remove(&key);
+    });
+}
+
+/// Search titles and bodies of issues, docs and PRs in a project
+pub async fn search(
+    project_path: &Path,
+    options: SearchOptions,
+) -> Result<SearchResults, SearchError> {
+    let terms = tokenize(&options.query);
+    if terms.is_empty() {
+        return Err(SearchError::EmptyQuery);
+    }
+
+    // Check if centy is initialized
+    read_manifest(project_path)
+        .await?
+        .ok_or(SearchError::NotInitialized)?;
+
+    let key = project_key(project_path);
+
+    let cached = with_state(|state| {
+        state
+            .indexes
+            .get(&key)
+            .map(|index| run_query(index, &terms, &options))
+    });
+    if let Some(results) = cached {
+        return Ok(results);
+    }
+
+    let generation = with_state(|state| state.generations.get(&key).copied().unwrap_or(0));
+    let index = build_index(project_path).await?;
+    let results = run_query(&index, &terms, &options);
+
+    with_state(|state| {
+        // Only cache the index if no write landed while it was being built
+        if state.generations.get(&key).copied().unwrap_or(0) == generation {
+            state.indexes.insert(key, index);
+        }
+    });
+
+    Ok(results)
+}
+
+/// Build a project index from disk
+async fn build_index(project_path: &Path) -> Result<ProjectIndex, SearchError> {
+    let mut index = ProjectIndex::default();
+
+    for issue in list_issues(project_path, None, None).await? {
+        index.insert_issue(&issue);
+    }
+    for doc in list_docs(project_path).await? {
+        index.insert_doc(&doc);
+    }
+    for pr in list_prs(project_path, None, None, None, None).await? {
+        index.insert_pr(&pr);
+    }
+
+    Ok(index)
+}
+
+/// Split a query into unique lowercase terms
+fn tokenize(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for term in query
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+    {
+        if !terms.iter().any(|t| t == term) {
+            terms.push(term.to_string());
+        }
+    }
+    terms
+}
+
+/// Score an entry against the query terms. Returns None unless every term matches.
+fn score_entry(entry: &IndexedEntry, terms: &[String], phrase: &str) -> Option<f64> {
+    let mut score = 0.0;
+
+    for term in terms {
+        let title_count = entry.title_lower.matches(term.as_str()).count();
+        let body_count = entry.body_lower.matches(term.as_str()).count();
+        if title_count == 0 && body_count == 0 {
+            return None;
+        }
+        // Dampen body occurrences so long documents don't dominate
+        score += title_count as f64 * TITLE_WEIGHT + (1.0 + body_count as f64).ln();
+    }
+
+    if entry.title_lower.contains(phrase) {
+        score += TITLE_PHRASE_BONUS;
+    }
+
+    Some(score)
+}
+
+/// Run a query against an index and return the ranked hits within the limit
+fn run_query(index: &ProjectIndex, terms: &[String], options: &SearchOptions) -> SearchResults {
+    let phrase = terms.join(" ");
+
+    let mut scored: Vec<(&EntryKey, &IndexedEntry, f64)> = index
+        .entries
+        .iter()
+        .filter(|((entity_type, _), _)| {
+            options.entity_types.is_empty() || options.entity_types.contains(entity_type)
+        })
+        .filter_map(|(key, entry)| score_entry(entry, terms, &phrase).map(|s| (key, entry, s)))
+        .collect();
+
+    // Highest score first, then a stable order for ties
+    scored.sort_by(|a, b| {
+        b.2.partial_cmp(&a.2)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.0 .0.cmp(&b.0 .0))
+            .then_with(|| a.1.display_number.cmp(&b.1.display_number))
+            .then_with(|| a.0 .1.cmp(&b.0 .1))
+    });
+    let total_count = scored.len();
+    scored.truncate(options.limit.unwrap_or(DEFAULT_SEARCH_LIMIT));
+
+    let hits = scored
+        .into_iter()
+        .map(|((entity_type, id), entry, score)| SearchHit {
+            entity_type: *entity_type,
+            id: id.clone(),
+            display_number: entry.display_number,
+            title: entry.title.clone(),
+            snippet: build_snippet(&entry.body, terms),
+            score,
+        })
+        .collect();
+
+    SearchResults { hits, total_count }
+}
+
+/// Build a snippet from the first body line containing a query term.
+/// Falls back to the first non-empty line for title-only matches.
+fn build_snippet(body: &str, terms: &[String]) -> String {
+    for line in body.lines() {
+        let lower = line.to_lowercase();
+        if let Some(pos) = terms.iter().filter_map(|t| lower.find(t.as_str())).min() {
+            let leading = line.chars().take_while(|c| c.is_whitespace()).count();
+            let match_pos = lower[..pos].chars().count().saturating_sub(leading);
+            return snippet_window(line.trim(), match_pos);
+        }
+    }
+
+    body.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(|line| snippet_window(line, 0))
+        .unwrap_or_default()
+}
+
+/// Cut a line down to `SNIPPET_MAX_CHARS` characters around a match position
+fn snippet_window(line: &str, match_pos: usize) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    if chars.len() <= SNIPPET_MAX_CHARS {
+        return line.to_string();
+    }
+
+    let start = match_pos
+        .min(chars.len())
+        .saturating_sub(SNIPPET_MAX_CHARS / 4)
+        .min(chars.len() - SNIPPET_MAX_CHARS);
+    let end = start + SNIPPET_MAX_CHARS;
+
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push_str("...");
+    }
+    snippet.extend(&chars[start..end]);
+    if end < chars.len() {
+        snippet.push_str("...");
+    }
+    snippet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(query: &str) -> Vec<String> {
+        tokenize(query)
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(terms("Login Bug"), vec!["login", "bug"]);
+        assert_eq!(terms("  login,  login!! "), vec!["login"]);
+        assert!(terms("  --  ").is_empty());
+    }
+
+    #[test]
+    fn test_entity_type_parse() {
+        assert_eq!(SearchEntityType::parse("issue").unwrap(), SearchEntityType::Issue);
+        assert_eq!(SearchEntityType::parse("Docs").unwrap(), SearchEntityType::Doc);
+        assert_eq!(SearchEntityType::parse("pr").unwrap(), SearchEntityType::Pr);
+        assert!(SearchEntityType::parse("milestone").is_err());
+    }
+
+    #[test]
+    fn test_score_requires_all_terms() {
+        let entry = IndexedEntry::new(1, "Login fails", "The button does nothing");
+        assert!(score_entry(&entry, &terms("login button"), "login button").is_some());
+        assert!(score_entry(&entry, &terms("login logout"), "login logout").is_none());
+    }
+
+    #[test]
+    fn test_score_title_outranks_body() {
+        let in_title = IndexedEntry::new(1, "Crash on startup", "Details");
+        let in_body = IndexedEntry::new(2, "Details", "Crash on startup");
+        let t = terms("crash");
+        assert!(score_entry(&in_title, &t, "crash").unwrap() > score_entry(&in_body, &t, "crash").unwrap());
+    }
+
+    #[test]
+    fn test_build_snippet_uses_matching_line() {
+        let body = "First line\n\n  The login page is broken\nLast line";
+        assert_eq!(build_snippet(body, &terms("login")), "The login page is broken");
+    }
+
+    #[test]
+    fn test_build_snippet_falls_back_to_first_line() {
+        let body = "\nSome description\nMore";
+        assert_eq!(build_snippet(body, &terms("title")), "Some description");
+    }
+
+    #[test]
+    fn test_snippet_window_truncates_long_lines() {
+        let line = format!("{}needle{}", "a".repeat(300), "b".repeat(300));
+        let snippet = snippet_window(&line, 300);
+        assert!(snippet.starts_with("..."));
+        assert!(snippet.ends_with("..."));
+        assert!(snippet.contains("needle"));
+        assert_eq!(snippet.chars().count(), SNIPPET_MAX_CHARS + 6);
+    }
+}
@@ -0,0 +1,13 @@
+//! Full-text search across issues, docs and PRs.
+//!
+//! The daemon keeps one in-memory index per project. The index is built
+//! lazily on the first search and kept current by the issue, doc and PR
+//! modules, which push their writes into it after every create, update
+//! and delete.
+
+mod index;
+
+pub use index::{
+    index_doc, index_issue, index_pr, invalidate_project, remove_doc, remove_issue, remove_pr,
+    search, SearchEntityType, SearchError, SearchHit, SearchOptions, SearchResults,
+};
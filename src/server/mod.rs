@@ -1,33 +1,73 @@
-use crate::config::{read_config, write_config, CentyConfig, CustomFieldDefinition as InternalCustomFieldDef, LlmConfig as InternalLlmConfig};
+use crate::config::{config_etag, default_closed_states, read_config, CentyConfig, ConfigError, CustomFieldDefinition as InternalCustomFieldDef, LabelDefinition as InternalLabelDef, LlmConfig as InternalLlmConfig, MemberDefinition as InternalMemberDef, TransitionRule as InternalTransitionRule, WorkflowConfig as InternalWorkflowConfig};
 use crate::migration::{create_registry, MigrationExecutor};
 use crate::version::{compare_versions, daemon_version, SemVer, VersionComparison};
 use crate::docs::{
     create_doc, delete_doc, get_doc, list_docs, update_doc, CreateDocOptions, UpdateDocOptions,
 };
 use crate::issue::{
-    create_issue, delete_issue, get_issue, get_issue_by_display_number, list_issues, priority_label, update_issue,
-    CreateIssueOptions, UpdateIssueOptions,
+    create_issue, delete_issue, get_issue, get_issue_history, list_issues_filtered, priority_label, resolve_display_number, update_issue,
+    CreateIssueOptions, IssueFilter, UpdateIssueOptions,
+    // Label imports
+    delete_label, rename_label,
+    // Config imports
+    update_config, ConfigUpdateError,
+    // Priority imports
+    preview_priority_rescale, rescale_priorities,
     // Asset imports
     add_asset, delete_asset as delete_asset_fn, get_asset, list_assets, list_shared_assets,
     AssetInfo, AssetScope,
+    // Comment imports
+    add_comment, delete_comment, list_comments, update_comment, AddCommentOptions,
+    // Relation imports
+    get_issue_graph, link_issues, unlink_issues, RelationKind,
+    // Hierarchy imports
+    child_status_counts, get_issue_tree, list_children, set_parent,
+    // Custom field imports
+    parse_field_value, CustomFieldType, FilterOp,
+    // Date imports
+    parse_date,
+    // Bulk imports
+    bulk_delete_issues, bulk_update_issues, BulkIssuePatch, BulkTarget,
+    // Transfer imports
+    transfer_issue, TransferIssueOptions, TransferMode,
+    // Archive imports
+    archive_issues, unarchive_issue,
 };
 use crate::pr::{
-    create_pr, delete_pr, get_pr, get_pr_by_display_number, list_prs, update_pr,
+    create_pr, default_pr_statuses, delete_pr, get_pr, get_pr_by_display_number, list_prs, update_pr,
     CreatePrOptions, UpdatePrOptions,
 };
 use crate::manifest::{read_manifest, ManagedFileType as InternalFileType, CentyManifest as InternalManifest};
 use crate::reconciliation::{
     build_reconciliation_plan, execute_reconciliation, ReconciliationDecisions,
 };
+use crate::query::{apply_list_query, ListQuery, QueryContext};
+use crate::search::{search, SearchEntityType, SearchOptions};
+use crate::calendar::export_calendar;
+use crate::trash::{empty_trash, list_trash, restore_from_trash};
+use crate::integrity::{check_project, repair_project, IntegrityError, Severity};
+use crate::milestone::{
+    create_milestone, delete_milestone, get_milestone, list_milestones, milestone_progress,
+    update_milestone, CreateMilestoneOptions, MilestoneError, MilestoneState,
+    UpdateMilestoneOptions,
+};
 use crate::registry::{
     get_project_info, list_projects, set_project_favorite, track_project_async, untrack_project,
     ProjectInfo,
 };
 use crate::utils::get_centy_path;
+use crate::feed::{watch_project, ChangeEntity, WatchError};
+use crate::view::{
+    delete_view, list_views, run_view, save_view, SaveViewOptions, ViewEntity, ViewError, ViewItems,
+};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::pin::Pin;
 use std::process::Command;
 use std::sync::Arc;
 use tokio::sync::watch;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
 use tonic::{Request, Response, Status};
 use tracing::info;
 
@@ -39,6 +79,9 @@ pub mod proto {
 use proto::centy_daemon_server::CentyDaemon;
 use proto::*;
 
+/// Events buffered per WatchProject stream before the sender waits on the client
+const WATCH_BUFFER: usize = 64;
+
 /// Signal type for daemon shutdown/restart
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ShutdownSignal {
@@ -167,6 +210,13 @@ impl CentyDaemon for CentyDaemonService {
             status: if req.status.is_empty() { None } else { Some(req.status) },
             custom_fields: req.custom_fields,
             template: if req.template.is_empty() { None } else { Some(req.template) },
+            labels: req.labels,
+            assignees: req.assignees,
+            parent_id: if req.parent_id.is_empty() { None } else { Some(req.parent_id) },
+            actor: if req.actor.is_empty() { None } else { Some(req.actor) },
+            start_date: if req.start_date.is_empty() { None } else { Some(req.start_date) },
+            due_date: if req.due_date.is_empty() { None } else { Some(req.due_date) },
+            milestone: if req.milestone.is_empty() { None } else { Some(req.milestone) },
         };
 
         match create_issue(project_path, options).await {
@@ -179,6 +229,7 @@ impl CentyDaemon for CentyDaemonService {
                 issue_number: result.issue_number, // Legacy
                 created_files: result.created_files,
                 manifest: Some(manifest_to_proto(&result.manifest)),
+                field_errors: Vec::new(),
             })),
             Err(e) => Ok(Response::new(CreateIssueResponse {
                 success: false,
@@ -188,6 +239,10 @@ impl CentyDaemon for CentyDaemonService {
                 issue_number: String::new(),
                 created_files: vec![],
                 manifest: None,
+                field_errors: match &e {
+                    crate::issue::IssueError::CustomFieldError(err) => field_errors_to_proto(err),
+                    _ => Vec::new(),
+                },
             })),
         }
     }
@@ -205,7 +260,9 @@ impl CentyDaemon for CentyDaemonService {
         let priority_levels = config.as_ref().map(|c| c.priority_levels).unwrap_or(3);
 
         match get_issue(project_path, &req.issue_id).await {
-            Ok(issue) => Ok(Response::new(issue_to_proto(&issue, priority_levels))),
+            Ok(issue) => Ok(Response::new(
+                issue_with_child_counts(project_path, &issue, priority_levels).await,
+            )),
             Err(e) => Err(Status::not_found(e.to_string())),
         }
     }
@@ -222,8 +279,15 @@ impl CentyDaemon for CentyDaemonService {
         let config = read_config(project_path).await.ok().flatten();
         let priority_levels = config.as_ref().map(|c| c.priority_levels).unwrap_or(3);
 
-        match get_issue_by_display_number(project_path, req.display_number).await {
-            Ok(issue) => Ok(Response::new(issue_to_proto(&issue, priority_levels))),
+        match resolve_display_number(project_path, req.display_number).await {
+            Ok(lookup) => {
+                let mut issue =
+                    issue_with_child_counts(project_path, &lookup.issue, priority_levels).await;
+                issue.redirected = lookup.redirected;
+                issue.previous_holder_ids =
+                    lookup.previous_holders.into_iter().map(|i| i.id).collect();
+                Ok(Response::new(issue))
+            }
             Err(e) => Err(Status::not_found(e.to_string())),
         }
     }
@@ -234,26 +298,27 @@ impl CentyDaemon for CentyDaemonService {
     ) -> Result<Response<ListIssuesResponse>, Status> {
         let req = request.into_inner();
         track_project_async(req.project_path.clone());
-        let project_path = Path::new(&req.project_path);
+        let project_path = PathBuf::from(&req.project_path);
 
         // Read config for priority_levels (for label generation)
-        let config = read_config(project_path).await.ok().flatten();
+        let config = read_config(&project_path).await.ok().flatten();
         let priority_levels = config.as_ref().map(|c| c.priority_levels).unwrap_or(3);
 
-        let status_filter = if req.status.is_empty() { None } else { Some(req.status.as_str()) };
-        // Convert int32 priority filter: 0 means no filter
-        let priority_filter = if req.priority == 0 { None } else { Some(req.priority as u32) };
+        let list_query = list_query_from_proto(req.query.clone(), req.sort.clone(), req.page_size, req.page_token.clone());
+        let filter = issue_filter_from_proto(req).map_err(Status::invalid_argument)?;
 
-        match list_issues(project_path, status_filter, priority_filter).await {
-            Ok(issues) => {
-                let total_count = issues.len() as i32;
-                Ok(Response::new(ListIssuesResponse {
-                    issues: issues.into_iter().map(|i| issue_to_proto(&i, priority_levels)).collect(),
-                    total_count,
-                }))
-            }
-            Err(e) => Err(Status::internal(e.to_string())),
-        }
+        let issues = list_issues_filtered(&project_path, &filter)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        let context = QueryContext::for_query(&project_path, &list_query).await;
+        let page = apply_list_query(issues, &list_query, &context)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        Ok(Response::new(ListIssuesResponse {
+            issues: page.items.iter().map(|i| issue_to_proto(i, priority_levels)).collect(),
+            total_count: page.total_count as i32,
+            next_page_token: page.next_page_token.unwrap_or_default(),
+        }))
     }
 
     async fn update_issue(
@@ -275,6 +340,48 @@ impl CentyDaemon for CentyDaemonService {
             status: if req.status.is_empty() { None } else { Some(req.status) },
             priority: if req.priority == 0 { None } else { Some(req.priority as u32) },
             custom_fields: req.custom_fields,
+            labels: if req.clear_labels {
+                Some(Vec::new())
+            } else if req.labels.is_empty() {
+                None
+            } else {
+                Some(req.labels)
+            },
+            assignees: if req.clear_assignees {
+                Some(Vec::new())
+            } else if req.assignees.is_empty() {
+                None
+            } else {
+                Some(req.assignees)
+            },
+            actor: if req.actor.is_empty() { None } else { Some(req.actor) },
+            comment: if req.comment.is_empty() { None } else { Some(req.comment) },
+            start_date: if req.clear_start_date {
+                Some(String::new())
+            } else if req.start_date.is_empty() {
+                None
+            } else {
+                Some(req.start_date)
+            },
+            due_date: if req.clear_due_date {
+                Some(String::new())
+            } else if req.due_date.is_empty() {
+                None
+            } else {
+                Some(req.due_date)
+            },
+            milestone: if req.clear_milestone {
+                Some(String::new())
+            } else if req.milestone.is_empty() {
+                None
+            } else {
+                Some(req.milestone)
+            },
+            expected_updated_at: if req.expected_updated_at.is_empty() {
+                None
+            } else {
+                Some(req.expected_updated_at)
+            },
         };
 
         match update_issue(project_path, &req.issue_id, options).await {
@@ -283,12 +390,26 @@ impl CentyDaemon for CentyDaemonService {
                 error: String::new(),
                 issue: Some(issue_to_proto(&result.issue, priority_levels)),
                 manifest: Some(manifest_to_proto(&result.manifest)),
+                warnings: result.warnings,
+                field_errors: Vec::new(),
+                conflict: false,
             })),
             Err(e) => Ok(Response::new(UpdateIssueResponse {
                 success: false,
                 error: e.to_string(),
-                issue: None,
+                issue: match &e {
+                    crate::issue::IssueCrudError::Conflict(current) => {
+                        Some(issue_to_proto(current, priority_levels))
+                    }
+                    _ => None,
+                },
                 manifest: None,
+                warnings: Vec::new(),
+                field_errors: match &e {
+                    crate::issue::IssueCrudError::CustomFieldError(err) => field_errors_to_proto(err),
+                    _ => Vec::new(),
+                },
+                conflict: matches!(e, crate::issue::IssueCrudError::Conflict(_)),
             })),
         }
     }
@@ -330,6 +451,228 @@ impl CentyDaemon for CentyDaemonService {
         }
     }
 
+    async fn get_issue_history(
+        &self,
+        request: Request<GetIssueHistoryRequest>,
+    ) -> Result<Response<GetIssueHistoryResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        match get_issue_history(project_path, &req.issue_id).await {
+            Ok(events) => {
+                let total_count = events.len() as i32;
+                Ok(Response::new(GetIssueHistoryResponse {
+                    events: events
+                        .into_iter()
+                        .map(|e| HistoryEvent {
+                            timestamp: e.timestamp,
+                            actor: e.actor.unwrap_or_default(),
+                            field: e.field,
+                            old_value: e.old_value.unwrap_or_default(),
+                            new_value: e.new_value.unwrap_or_default(),
+                        })
+                        .collect(),
+                    total_count,
+                }))
+            }
+            Err(e @ crate::issue::HistoryError::IssueNotFound(_)) => Err(Status::not_found(e.to_string())),
+            Err(e) => Err(Status::internal(e.to_string())),
+        }
+    }
+
+    async fn bulk_update_issues(
+        &self,
+        request: Request<BulkUpdateIssuesRequest>,
+    ) -> Result<Response<BulkUpdateIssuesResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        let target = match bulk_target_from_proto(req.issue_ids, req.filter) {
+            Ok(target) => target,
+            Err(e) => {
+                return Ok(Response::new(BulkUpdateIssuesResponse {
+                    success: false,
+                    error: e,
+                    ..Default::default()
+                }))
+            }
+        };
+        // Convert int32 priority: 0 means don't update
+        let patch = BulkIssuePatch {
+            status: if req.status.is_empty() { None } else { Some(req.status) },
+            priority: if req.priority == 0 { None } else { Some(req.priority as u32) },
+            custom_fields: req.custom_fields,
+            add_labels: req.add_labels,
+            remove_labels: req.remove_labels,
+            actor: if req.actor.is_empty() { None } else { Some(req.actor) },
+            comment: if req.comment.is_empty() { None } else { Some(req.comment) },
+        };
+
+        match bulk_update_issues(project_path, target, patch).await {
+            Ok(result) => Ok(Response::new(BulkUpdateIssuesResponse {
+                success: true,
+                error: String::new(),
+                succeeded_count: result.succeeded_count() as i32,
+                failed_count: result.failed_count() as i32,
+                results: bulk_items_to_proto(result.items),
+                manifest: result.manifest.as_ref().map(manifest_to_proto),
+            })),
+            Err(e) => Ok(Response::new(BulkUpdateIssuesResponse {
+                success: false,
+                error: e.to_string(),
+                ..Default::default()
+            })),
+        }
+    }
+
+    async fn bulk_delete_issues(
+        &self,
+        request: Request<BulkDeleteIssuesRequest>,
+    ) -> Result<Response<BulkDeleteIssuesResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        let target = match bulk_target_from_proto(req.issue_ids, req.filter) {
+            Ok(target) => target,
+            Err(e) => {
+                return Ok(Response::new(BulkDeleteIssuesResponse {
+                    success: false,
+                    error: e,
+                    ..Default::default()
+                }))
+            }
+        };
+
+        match bulk_delete_issues(project_path, target).await {
+            Ok(result) => Ok(Response::new(BulkDeleteIssuesResponse {
+                success: true,
+                error: String::new(),
+                succeeded_count: result.succeeded_count() as i32,
+                failed_count: result.failed_count() as i32,
+                results: bulk_items_to_proto(result.items),
+                manifest: result.manifest.as_ref().map(manifest_to_proto),
+            })),
+            Err(e) => Ok(Response::new(BulkDeleteIssuesResponse {
+                success: false,
+                error: e.to_string(),
+                ..Default::default()
+            })),
+        }
+    }
+
+    async fn transfer_issue(
+        &self,
+        request: Request<TransferIssueRequest>,
+    ) -> Result<Response<TransferIssueResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+        let destination_path = Path::new(&req.destination_project_path);
+
+        // Only move issues into projects the daemon knows about
+        match get_project_info(&req.destination_project_path).await {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                return Ok(Response::new(TransferIssueResponse {
+                    success: false,
+                    error: format!(
+                        "Destination project is not tracked: {}",
+                        req.destination_project_path
+                    ),
+                    ..Default::default()
+                }))
+            }
+            Err(e) => {
+                return Ok(Response::new(TransferIssueResponse {
+                    success: false,
+                    error: e.to_string(),
+                    ..Default::default()
+                }))
+            }
+        }
+        track_project_async(req.destination_project_path.clone());
+
+        let config = read_config(destination_path).await.ok().flatten();
+        let priority_levels = config.as_ref().map(|c| c.priority_levels).unwrap_or(3);
+
+        let options = TransferIssueOptions {
+            mode: if req.copy { TransferMode::Copy } else { TransferMode::Move },
+            actor: if req.actor.is_empty() { None } else { Some(req.actor) },
+        };
+
+        match transfer_issue(project_path, &req.issue_id, destination_path, options).await {
+            Ok(result) => Ok(Response::new(TransferIssueResponse {
+                success: true,
+                error: String::new(),
+                issue: Some(issue_to_proto(&result.issue, priority_levels)),
+                destination_manifest: Some(manifest_to_proto(&result.destination_manifest)),
+                source_manifest: result.source_manifest.as_ref().map(manifest_to_proto),
+            })),
+            Err(e) => Ok(Response::new(TransferIssueResponse {
+                success: false,
+                error: e.to_string(),
+                ..Default::default()
+            })),
+        }
+    }
+
+    async fn archive_issues(
+        &self,
+        request: Request<ArchiveIssuesRequest>,
+    ) -> Result<Response<ArchiveIssuesResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        // 0 means use the configured archive_after_days
+        let older_than_days = if req.older_than_days <= 0 { None } else { Some(req.older_than_days as u32) };
+
+        match archive_issues(project_path, older_than_days).await {
+            Ok(result) => Ok(Response::new(ArchiveIssuesResponse {
+                success: true,
+                error: String::new(),
+                archived_issue_ids: result.archived,
+                manifest: result.manifest.as_ref().map(manifest_to_proto),
+            })),
+            Err(e) => Ok(Response::new(ArchiveIssuesResponse {
+                success: false,
+                error: e.to_string(),
+                archived_issue_ids: Vec::new(),
+                manifest: None,
+            })),
+        }
+    }
+
+    async fn unarchive_issue(
+        &self,
+        request: Request<UnarchiveIssueRequest>,
+    ) -> Result<Response<UnarchiveIssueResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        let config = read_config(project_path).await.ok().flatten();
+        let priority_levels = config.as_ref().map(|c| c.priority_levels).unwrap_or(3);
+
+        match unarchive_issue(project_path, &req.issue_id).await {
+            Ok(result) => Ok(Response::new(UnarchiveIssueResponse {
+                success: true,
+                error: String::new(),
+                issue: Some(issue_to_proto(&result.issue, priority_levels)),
+                manifest: Some(manifest_to_proto(&result.manifest)),
+            })),
+            Err(e) => Ok(Response::new(UnarchiveIssueResponse {
+                success: false,
+                error: e.to_string(),
+                issue: None,
+                manifest: None,
+            })),
+        }
+    }
+
     async fn get_manifest(
         &self,
         request: Request<GetManifestRequest>,
@@ -353,29 +696,17 @@ impl CentyDaemon for CentyDaemonService {
         track_project_async(req.project_path.clone());
         let project_path = Path::new(&req.project_path);
 
-        match read_config(project_path).await {
-            Ok(Some(config)) => Ok(Response::new(config_to_proto(&config))),
-            Ok(None) => Ok(Response::new(Config {
-                custom_fields: vec![],
-                defaults: std::collections::HashMap::new(),
-                priority_levels: 3, // Default
-                allowed_states: vec![
-                    "open".to_string(),
-                    "in-progress".to_string(),
-                    "closed".to_string(),
-                ],
-                default_state: "open".to_string(),
-                version: crate::utils::CENTY_VERSION.to_string(),
-                state_colors: std::collections::HashMap::new(),
-                priority_colors: std::collections::HashMap::new(),
-                llm: Some(LlmConfig {
-                    auto_close_on_complete: false,
-                    update_status_on_start: false,
-                    allow_direct_edits: false,
-                }),
-            })),
-            Err(e) => Err(Status::internal(e.to_string())),
-        }
+        let config = match read_config(project_path).await {
+            Ok(config) => config.unwrap_or_default(),
+            Err(e) => return Err(Status::internal(e.to_string())),
+        };
+        let etag = config_etag(project_path)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Response::new(Config {
+            etag,
+            ..config_to_proto(&config)
+        }))
     }
 
     async fn update_config(
@@ -394,6 +725,9 @@ impl CentyDaemon for CentyDaemonService {
                 success: false,
                 error: "Project not initialized".to_string(),
                 config: None,
+                conflict: false,
+                priority_changes: Vec::new(),
+                stripped_issue_ids: Vec::new(),
             }));
         }
 
@@ -405,6 +739,9 @@ impl CentyDaemon for CentyDaemonService {
                     success: false,
                     error: "No config provided".to_string(),
                     config: None,
+                    conflict: false,
+                    priority_changes: Vec::new(),
+                    stripped_issue_ids: Vec::new(),
                 }));
             }
         };
@@ -416,20 +753,87 @@ impl CentyDaemon for CentyDaemonService {
                 success: false,
                 error: e,
                 config: None,
+                conflict: false,
+                priority_changes: Vec::new(),
+                stripped_issue_ids: Vec::new(),
             }));
         }
 
-        // Write config
-        match write_config(project_path, &config).await {
-            Ok(()) => Ok(Response::new(UpdateConfigResponse {
+        // Write config, unless someone else changed it since the client read it.
+        // Labels removed from config are stripped from the issues that used them,
+        // and priorities can be mapped onto a changed number of levels.
+        let expected_etag = Some(req.expected_etag.as_str()).filter(|e| !e.is_empty());
+        match update_config(project_path, &config, expected_etag, req.rescale_priorities).await {
+            Ok(result) => Ok(Response::new(UpdateConfigResponse {
+                success: true,
+                error: String::new(),
+                config: Some(Config {
+                    etag: result.etag,
+                    ..config_to_proto(&config)
+                }),
+                conflict: false,
+                priority_changes: result.priority_changes.iter().map(priority_change_to_proto).collect(),
+                stripped_issue_ids: result.stripped_issue_ids,
+            })),
+            Err(e) => {
+                let current = match &e {
+                    ConfigUpdateError::ConfigError(ConfigError::Conflict { current, etag }) => Some(Config {
+                        etag: etag.clone(),
+                        ..config_to_proto(current)
+                    }),
+                    _ => None,
+                };
+                Ok(Response::new(UpdateConfigResponse {
+                    success: false,
+                    error: e.to_string(),
+                    conflict: current.is_some(),
+                    config: current,
+                    priority_changes: Vec::new(),
+                    stripped_issue_ids: Vec::new(),
+                }))
+            }
+        }
+    }
+
+    async fn rescale_priorities(
+        &self,
+        request: Request<RescalePrioritiesRequest>,
+    ) -> Result<Response<RescalePrioritiesResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        let from_levels = req.from_levels.max(0) as u32;
+        let to_levels = if req.to_levels > 0 {
+            req.to_levels as u32
+        } else {
+            read_config(project_path)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or_default()
+                .priority_levels
+        };
+
+        let result = if req.dry_run {
+            preview_priority_rescale(project_path, from_levels, to_levels).await
+        } else {
+            rescale_priorities(project_path, from_levels, to_levels).await
+        };
+        match result {
+            Ok(result) => Ok(Response::new(RescalePrioritiesResponse {
                 success: true,
                 error: String::new(),
-                config: Some(config_to_proto(&config)),
+                changes: result.changes.iter().map(priority_change_to_proto).collect(),
+                applied: result.applied,
+                manifest: Some(manifest_to_proto(&result.manifest)),
+                old_levels: result.old_levels as i32,
+                new_levels: result.new_levels as i32,
             })),
-            Err(e) => Ok(Response::new(UpdateConfigResponse {
+            Err(e) => Ok(Response::new(RescalePrioritiesResponse {
                 success: false,
                 error: e.to_string(),
-                config: None,
+                ..Default::default()
             })),
         }
     }
@@ -457,6 +861,60 @@ impl CentyDaemon for CentyDaemonService {
         }))
     }
 
+    // ============ Label RPCs ============
+
+    async fn rename_label(
+        &self,
+        request: Request<RenameLabelRequest>,
+    ) -> Result<Response<RenameLabelResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        match rename_label(project_path, &req.old_name, &req.new_name).await {
+            Ok(result) => Ok(Response::new(RenameLabelResponse {
+                success: true,
+                error: String::new(),
+                config: Some(config_to_proto(&result.config)),
+                updated_issue_ids: result.updated_issue_ids,
+                manifest: Some(manifest_to_proto(&result.manifest)),
+            })),
+            Err(e) => Ok(Response::new(RenameLabelResponse {
+                success: false,
+                error: e.to_string(),
+                config: None,
+                updated_issue_ids: vec![],
+                manifest: None,
+            })),
+        }
+    }
+
+    async fn delete_label(
+        &self,
+        request: Request<DeleteLabelRequest>,
+    ) -> Result<Response<DeleteLabelResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        match delete_label(project_path, &req.name).await {
+            Ok(result) => Ok(Response::new(DeleteLabelResponse {
+                success: true,
+                error: String::new(),
+                config: Some(config_to_proto(&result.config)),
+                updated_issue_ids: result.updated_issue_ids,
+                manifest: Some(manifest_to_proto(&result.manifest)),
+            })),
+            Err(e) => Ok(Response::new(DeleteLabelResponse {
+                success: false,
+                error: e.to_string(),
+                config: None,
+                updated_issue_ids: vec![],
+                manifest: None,
+            })),
+        }
+    }
+
     // ============ Doc RPCs ============
 
     async fn create_doc(
@@ -514,16 +972,19 @@ impl CentyDaemon for CentyDaemonService {
         track_project_async(req.project_path.clone());
         let project_path = Path::new(&req.project_path);
 
-        match list_docs(project_path).await {
-            Ok(docs) => {
-                let total_count = docs.len() as i32;
-                Ok(Response::new(ListDocsResponse {
-                    docs: docs.into_iter().map(|d| doc_to_proto(&d)).collect(),
-                    total_count,
-                }))
-            }
-            Err(e) => Err(Status::internal(e.to_string())),
-        }
+        let docs = list_docs(project_path)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        let list_query = list_query_from_proto(req.query, req.sort, req.page_size, req.page_token);
+        let context = QueryContext::for_query(project_path, &list_query).await;
+        let page = apply_list_query(docs, &list_query, &context)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        Ok(Response::new(ListDocsResponse {
+            docs: page.items.iter().map(doc_to_proto).collect(),
+            total_count: page.total_count as i32,
+            next_page_token: page.next_page_token.unwrap_or_default(),
+        }))
     }
 
     async fn update_doc(
@@ -538,6 +999,11 @@ impl CentyDaemon for CentyDaemonService {
             title: if req.title.is_empty() { None } else { Some(req.title) },
             content: if req.content.is_empty() { None } else { Some(req.content) },
             new_slug: if req.new_slug.is_empty() { None } else { Some(req.new_slug) },
+            expected_updated_at: if req.expected_updated_at.is_empty() {
+                None
+            } else {
+                Some(req.expected_updated_at)
+            },
         };
 
         match update_doc(project_path, &req.slug, options).await {
@@ -546,12 +1012,17 @@ impl CentyDaemon for CentyDaemonService {
                 error: String::new(),
                 doc: Some(doc_to_proto(&result.doc)),
                 manifest: Some(manifest_to_proto(&result.manifest)),
+                conflict: false,
             })),
             Err(e) => Ok(Response::new(UpdateDocResponse {
                 success: false,
                 error: e.to_string(),
-                doc: None,
+                doc: match &e {
+                    crate::docs::DocError::Conflict(current) => Some(doc_to_proto(current)),
+                    _ => None,
+                },
                 manifest: None,
+                conflict: matches!(e, crate::docs::DocError::Conflict(_)),
             })),
         }
     }
@@ -578,31 +1049,189 @@ impl CentyDaemon for CentyDaemonService {
         }
     }
 
-    // ============ Asset RPCs ============
+    // ============ Milestone RPCs ============
 
-    async fn add_asset(
+    async fn create_milestone(
         &self,
-        request: Request<AddAssetRequest>,
-    ) -> Result<Response<AddAssetResponse>, Status> {
+        request: Request<CreateMilestoneRequest>,
+    ) -> Result<Response<CreateMilestoneResponse>, Status> {
         let req = request.into_inner();
         track_project_async(req.project_path.clone());
         let project_path = Path::new(&req.project_path);
 
-        let scope = if req.is_shared {
-            AssetScope::Shared
-        } else {
-            AssetScope::IssueSpecific
+        let options = CreateMilestoneOptions {
+            title: req.title,
+            description: req.description,
+            slug: if req.slug.is_empty() { None } else { Some(req.slug) },
+            target_date: if req.target_date.is_empty() { None } else { Some(req.target_date) },
         };
 
-        let issue_id = if req.issue_id.is_empty() {
-            None
-        } else {
-            Some(req.issue_id.as_str())
-        };
+        match create_milestone(project_path, options).await {
+            Ok(result) => Ok(Response::new(CreateMilestoneResponse {
+                success: true,
+                error: String::new(),
+                milestone: Some(milestone_to_proto(&result.milestone, None)),
+                created_file: result.created_file,
+                manifest: Some(manifest_to_proto(&result.manifest)),
+            })),
+            Err(e) => Ok(Response::new(CreateMilestoneResponse {
+                success: false,
+                error: e.to_string(),
+                milestone: None,
+                created_file: String::new(),
+                manifest: None,
+            })),
+        }
+    }
 
-        match add_asset(project_path, issue_id, req.data, &req.filename, scope).await {
-            Ok(result) => {
-                // Re-read manifest for response
+    async fn get_milestone(
+        &self,
+        request: Request<GetMilestoneRequest>,
+    ) -> Result<Response<Milestone>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        let milestone = match get_milestone(project_path, &req.slug).await {
+            Ok(milestone) => milestone,
+            Err(e @ MilestoneError::MilestoneNotFound(_)) => {
+                return Err(Status::not_found(e.to_string()))
+            }
+            Err(e) => return Err(Status::internal(e.to_string())),
+        };
+
+        match milestone_progress(project_path, &req.slug).await {
+            Ok(progress) => Ok(Response::new(milestone_to_proto(&milestone, Some(&progress)))),
+            Err(e) => Err(Status::internal(e.to_string())),
+        }
+    }
+
+    async fn list_milestones(
+        &self,
+        request: Request<ListMilestonesRequest>,
+    ) -> Result<Response<ListMilestonesResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        let state = if req.state.is_empty() {
+            None
+        } else {
+            Some(MilestoneState::parse(&req.state).map_err(|e| Status::invalid_argument(e.to_string()))?)
+        };
+
+        match list_milestones(project_path, state).await {
+            Ok(milestones) => {
+                let total_count = milestones.len() as i32;
+                Ok(Response::new(ListMilestonesResponse {
+                    milestones: milestones.iter().map(|m| milestone_to_proto(m, None)).collect(),
+                    total_count,
+                }))
+            }
+            Err(e) => Err(Status::internal(e.to_string())),
+        }
+    }
+
+    async fn update_milestone(
+        &self,
+        request: Request<UpdateMilestoneRequest>,
+    ) -> Result<Response<UpdateMilestoneResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        let state = if req.state.is_empty() {
+            Ok(None)
+        } else {
+            MilestoneState::parse(&req.state).map(Some)
+        };
+
+        let result = match state {
+            Ok(state) => {
+                let options = UpdateMilestoneOptions {
+                    title: if req.title.is_empty() { None } else { Some(req.title) },
+                    description: if req.description.is_empty() { None } else { Some(req.description) },
+                    target_date: if req.clear_target_date {
+                        Some(String::new())
+                    } else if req.target_date.is_empty() {
+                        None
+                    } else {
+                        Some(req.target_date)
+                    },
+                    state,
+                };
+                update_milestone(project_path, &req.slug, options).await
+            }
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(result) => Ok(Response::new(UpdateMilestoneResponse {
+                success: true,
+                error: String::new(),
+                milestone: Some(milestone_to_proto(&result.milestone, None)),
+                manifest: Some(manifest_to_proto(&result.manifest)),
+            })),
+            Err(e) => Ok(Response::new(UpdateMilestoneResponse {
+                success: false,
+                error: e.to_string(),
+                milestone: None,
+                manifest: None,
+            })),
+        }
+    }
+
+    async fn delete_milestone(
+        &self,
+        request: Request<DeleteMilestoneRequest>,
+    ) -> Result<Response<DeleteMilestoneResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        match delete_milestone(project_path, &req.slug).await {
+            Ok(result) => Ok(Response::new(DeleteMilestoneResponse {
+                success: true,
+                error: String::new(),
+                manifest: Some(manifest_to_proto(&result.manifest)),
+                unassigned_issue_ids: result.unassigned_issues,
+                unassigned_pr_ids: result.unassigned_prs,
+            })),
+            Err(e) => Ok(Response::new(DeleteMilestoneResponse {
+                success: false,
+                error: e.to_string(),
+                manifest: None,
+                unassigned_issue_ids: Vec::new(),
+                unassigned_pr_ids: Vec::new(),
+            })),
+        }
+    }
+
+    // ============ Asset RPCs ============
+
+    async fn add_asset(
+        &self,
+        request: Request<AddAssetRequest>,
+    ) -> Result<Response<AddAssetResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        let scope = if req.is_shared {
+            AssetScope::Shared
+        } else {
+            AssetScope::IssueSpecific
+        };
+
+        let issue_id = if req.issue_id.is_empty() {
+            None
+        } else {
+            Some(req.issue_id.as_str())
+        };
+
+        match add_asset(project_path, issue_id, req.data, &req.filename, scope).await {
+            Ok(result) => {
+                // Re-read manifest for response
                 let manifest = read_manifest(project_path).await.ok().flatten();
                 Ok(Response::new(AddAssetResponse {
                     success: true,
@@ -728,6 +1357,299 @@ impl CentyDaemon for CentyDaemonService {
         }
     }
 
+    // ============ Comment RPCs ============
+
+    async fn add_comment(
+        &self,
+        request: Request<AddCommentRequest>,
+    ) -> Result<Response<AddCommentResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        let options = AddCommentOptions {
+            author: req.author,
+            body: req.body,
+            reply_to: if req.reply_to.is_empty() { None } else { Some(req.reply_to) },
+        };
+
+        match add_comment(project_path, &req.issue_id, options).await {
+            Ok(result) => Ok(Response::new(AddCommentResponse {
+                success: true,
+                error: String::new(),
+                comment: Some(comment_to_proto(&result.comment, &req.issue_id)),
+                manifest: Some(manifest_to_proto(&result.manifest)),
+            })),
+            Err(e) => Ok(Response::new(AddCommentResponse {
+                success: false,
+                error: e.to_string(),
+                comment: None,
+                manifest: None,
+            })),
+        }
+    }
+
+    async fn list_comments(
+        &self,
+        request: Request<ListCommentsRequest>,
+    ) -> Result<Response<ListCommentsResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        match list_comments(project_path, &req.issue_id).await {
+            Ok(comments) => {
+                let total_count = comments.len() as i32;
+                Ok(Response::new(ListCommentsResponse {
+                    comments: comments
+                        .iter()
+                        .map(|c| comment_to_proto(c, &req.issue_id))
+                        .collect(),
+                    total_count,
+                }))
+            }
+            Err(e) => Err(Status::internal(e.to_string())),
+        }
+    }
+
+    async fn update_comment(
+        &self,
+        request: Request<UpdateCommentRequest>,
+    ) -> Result<Response<UpdateCommentResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        match update_comment(project_path, &req.issue_id, &req.comment_id, req.body).await {
+            Ok(result) => Ok(Response::new(UpdateCommentResponse {
+                success: true,
+                error: String::new(),
+                comment: Some(comment_to_proto(&result.comment, &req.issue_id)),
+                manifest: Some(manifest_to_proto(&result.manifest)),
+            })),
+            Err(e) => Ok(Response::new(UpdateCommentResponse {
+                success: false,
+                error: e.to_string(),
+                comment: None,
+                manifest: None,
+            })),
+        }
+    }
+
+    async fn delete_comment(
+        &self,
+        request: Request<DeleteCommentRequest>,
+    ) -> Result<Response<DeleteCommentResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        match delete_comment(project_path, &req.issue_id, &req.comment_id).await {
+            Ok(result) => Ok(Response::new(DeleteCommentResponse {
+                success: true,
+                error: String::new(),
+                deleted_ids: result.deleted_ids,
+                manifest: Some(manifest_to_proto(&result.manifest)),
+            })),
+            Err(e) => Ok(Response::new(DeleteCommentResponse {
+                success: false,
+                error: e.to_string(),
+                deleted_ids: Vec::new(),
+                manifest: None,
+            })),
+        }
+    }
+
+    // ============ Relation RPCs ============
+
+    async fn link_issues(
+        &self,
+        request: Request<LinkIssuesRequest>,
+    ) -> Result<Response<LinkIssuesResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        // Read config for priority_levels (for label generation)
+        let config = read_config(project_path).await.ok().flatten();
+        let priority_levels = config.as_ref().map(|c| c.priority_levels).unwrap_or(3);
+
+        let result = match RelationKind::parse(&req.r#type) {
+            Ok(kind) => link_issues(project_path, &req.source_issue_id, &req.target_issue_id, kind).await,
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(result) => Ok(Response::new(LinkIssuesResponse {
+                success: true,
+                error: String::new(),
+                source_issue: Some(issue_to_proto(&result.source, priority_levels)),
+                target_issue: Some(issue_to_proto(&result.target, priority_levels)),
+                manifest: Some(manifest_to_proto(&result.manifest)),
+            })),
+            Err(e) => Ok(Response::new(LinkIssuesResponse {
+                success: false,
+                error: e.to_string(),
+                source_issue: None,
+                target_issue: None,
+                manifest: None,
+            })),
+        }
+    }
+
+    async fn unlink_issues(
+        &self,
+        request: Request<UnlinkIssuesRequest>,
+    ) -> Result<Response<UnlinkIssuesResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        // Read config for priority_levels (for label generation)
+        let config = read_config(project_path).await.ok().flatten();
+        let priority_levels = config.as_ref().map(|c| c.priority_levels).unwrap_or(3);
+
+        let result = match RelationKind::parse(&req.r#type) {
+            Ok(kind) => unlink_issues(project_path, &req.source_issue_id, &req.target_issue_id, kind).await,
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(result) => Ok(Response::new(UnlinkIssuesResponse {
+                success: true,
+                error: String::new(),
+                source_issue: Some(issue_to_proto(&result.source, priority_levels)),
+                target_issue: Some(issue_to_proto(&result.target, priority_levels)),
+                manifest: Some(manifest_to_proto(&result.manifest)),
+            })),
+            Err(e) => Ok(Response::new(UnlinkIssuesResponse {
+                success: false,
+                error: e.to_string(),
+                source_issue: None,
+                target_issue: None,
+                manifest: None,
+            })),
+        }
+    }
+
+    async fn get_issue_graph(
+        &self,
+        request: Request<GetIssueGraphRequest>,
+    ) -> Result<Response<IssueGraph>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        let root = if req.issue_id.is_empty() { None } else { Some(req.issue_id.as_str()) };
+
+        match get_issue_graph(project_path, root).await {
+            Ok(graph) => Ok(Response::new(IssueGraph {
+                nodes: graph
+                    .nodes
+                    .into_iter()
+                    .map(|n| IssueGraphNode {
+                        id: n.id,
+                        display_number: n.display_number,
+                        title: n.title,
+                        status: n.status,
+                    })
+                    .collect(),
+                edges: graph
+                    .edges
+                    .into_iter()
+                    .map(|e| IssueGraphEdge {
+                        source_issue_id: e.source,
+                        target_issue_id: e.target,
+                        r#type: e.kind.as_str().to_string(),
+                    })
+                    .collect(),
+            })),
+            Err(e @ crate::issue::RelationError::IssueNotFound(_)) => Err(Status::not_found(e.to_string())),
+            Err(e) => Err(Status::internal(e.to_string())),
+        }
+    }
+
+    // ============ Hierarchy RPCs ============
+
+    async fn set_parent(
+        &self,
+        request: Request<SetParentRequest>,
+    ) -> Result<Response<SetParentResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        // Read config for priority_levels (for label generation)
+        let config = read_config(project_path).await.ok().flatten();
+        let priority_levels = config.as_ref().map(|c| c.priority_levels).unwrap_or(3);
+
+        let parent_id = if req.parent_id.is_empty() { None } else { Some(req.parent_id.as_str()) };
+
+        match set_parent(project_path, &req.issue_id, parent_id).await {
+            Ok(result) => Ok(Response::new(SetParentResponse {
+                success: true,
+                error: String::new(),
+                issue: Some(issue_to_proto(&result.issue, priority_levels)),
+                manifest: Some(manifest_to_proto(&result.manifest)),
+            })),
+            Err(e) => Ok(Response::new(SetParentResponse {
+                success: false,
+                error: e.to_string(),
+                issue: None,
+                manifest: None,
+            })),
+        }
+    }
+
+    async fn list_children(
+        &self,
+        request: Request<ListChildrenRequest>,
+    ) -> Result<Response<ListIssuesResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        // Read config for priority_levels (for label generation)
+        let config = read_config(project_path).await.ok().flatten();
+        let priority_levels = config.as_ref().map(|c| c.priority_levels).unwrap_or(3);
+
+        match list_children(project_path, &req.issue_id, req.recursive).await {
+            Ok(issues) => {
+                let total_count = issues.len() as i32;
+                Ok(Response::new(ListIssuesResponse {
+                    issues: issues.into_iter().map(|i| issue_to_proto(&i, priority_levels)).collect(),
+                    total_count,
+                    next_page_token: String::new(),
+                }))
+            }
+            Err(e @ crate::issue::HierarchyError::IssueNotFound(_)) => Err(Status::not_found(e.to_string())),
+            Err(e) => Err(Status::internal(e.to_string())),
+        }
+    }
+
+    async fn get_issue_tree(
+        &self,
+        request: Request<GetIssueTreeRequest>,
+    ) -> Result<Response<GetIssueTreeResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        // Read config for priority_levels (for label generation)
+        let config = read_config(project_path).await.ok().flatten();
+        let priority_levels = config.as_ref().map(|c| c.priority_levels).unwrap_or(3);
+
+        let root = if req.issue_id.is_empty() { None } else { Some(req.issue_id.as_str()) };
+
+        match get_issue_tree(project_path, root).await {
+            Ok(roots) => Ok(Response::new(GetIssueTreeResponse {
+                roots: roots.iter().map(|n| issue_tree_to_proto(n, priority_levels)).collect(),
+            })),
+            Err(e @ crate::issue::HierarchyError::IssueNotFound(_)) => Err(Status::not_found(e.to_string())),
+            Err(e) => Err(Status::internal(e.to_string())),
+        }
+    }
+
     // ============ Project Registry RPCs ============
 
     async fn list_projects(
@@ -1043,110 +1965,435 @@ impl CentyDaemon for CentyDaemonService {
         track_project_async(req.project_path.clone());
         let project_path = Path::new(&req.project_path);
 
-        let options = CreatePrOptions {
-            title: req.title,
-            description: req.description,
-            source_branch: if req.source_branch.is_empty() { None } else { Some(req.source_branch) },
-            target_branch: if req.target_branch.is_empty() { None } else { Some(req.target_branch) },
-            linked_issues: req.linked_issues,
-            reviewers: req.reviewers,
-            priority: if req.priority == 0 { None } else { Some(req.priority as u32) },
-            status: if req.status.is_empty() { None } else { Some(req.status) },
-            custom_fields: req.custom_fields,
-            template: if req.template.is_empty() { None } else { Some(req.template) },
-        };
-
-        match create_pr(project_path, options).await {
-            Ok(result) => Ok(Response::new(CreatePrResponse {
+        let options = CreatePrOptions {
+            title: req.title,
+            description: req.description,
+            source_branch: if req.source_branch.is_empty() { None } else { Some(req.source_branch) },
+            target_branch: if req.target_branch.is_empty() { None } else { Some(req.target_branch) },
+            linked_issues: req.linked_issues,
+            reviewers: req.reviewers,
+            priority: if req.priority == 0 { None } else { Some(req.priority as u32) },
+            status: if req.status.is_empty() { None } else { Some(req.status) },
+            custom_fields: req.custom_fields,
+            template: if req.template.is_empty() { None } else { Some(req.template) },
+            milestone: if req.milestone.is_empty() { None } else { Some(req.milestone) },
+        };
+
+        match create_pr(project_path, options).await {
+            Ok(result) => Ok(Response::new(CreatePrResponse {
+                success: true,
+                error: String::new(),
+                id: result.id,
+                display_number: result.display_number,
+                created_files: result.created_files,
+                manifest: Some(manifest_to_proto(&result.manifest)),
+                detected_source_branch: result.detected_source_branch,
+            })),
+            Err(e) => Ok(Response::new(CreatePrResponse {
+                success: false,
+                error: e.to_string(),
+                id: String::new(),
+                display_number: 0,
+                created_files: vec![],
+                manifest: None,
+                detected_source_branch: String::new(),
+            })),
+        }
+    }
+
+    async fn get_pr(
+        &self,
+        request: Request<GetPrRequest>,
+    ) -> Result<Response<PullRequest>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        // Read config for priority_levels (for label generation)
+        let config = read_config(project_path).await.ok().flatten();
+        let priority_levels = config.as_ref().map(|c| c.priority_levels).unwrap_or(3);
+
+        match get_pr(project_path, &req.pr_id).await {
+            Ok(pr) => Ok(Response::new(pr_to_proto(&pr, priority_levels))),
+            Err(e) => Err(Status::not_found(e.to_string())),
+        }
+    }
+
+    async fn get_pr_by_display_number(
+        &self,
+        request: Request<GetPrByDisplayNumberRequest>,
+    ) -> Result<Response<PullRequest>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        // Read config for priority_levels (for label generation)
+        let config = read_config(project_path).await.ok().flatten();
+        let priority_levels = config.as_ref().map(|c| c.priority_levels).unwrap_or(3);
+
+        match get_pr_by_display_number(project_path, req.display_number).await {
+            Ok(pr) => Ok(Response::new(pr_to_proto(&pr, priority_levels))),
+            Err(e) => Err(Status::not_found(e.to_string())),
+        }
+    }
+
+    async fn list_prs(
+        &self,
+        request: Request<ListPrsRequest>,
+    ) -> Result<Response<ListPrsResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        // Read config for priority_levels (for label generation)
+        let config = read_config(project_path).await.ok().flatten();
+        let priority_levels = config.as_ref().map(|c| c.priority_levels).unwrap_or(3);
+
+        let status_filter = if req.status.is_empty() { None } else { Some(req.status.as_str()) };
+        let source_filter = if req.source_branch.is_empty() { None } else { Some(req.source_branch.as_str()) };
+        let target_filter = if req.target_branch.is_empty() { None } else { Some(req.target_branch.as_str()) };
+        let priority_filter = if req.priority == 0 { None } else { Some(req.priority as u32) };
+
+        let prs = list_prs(project_path, status_filter, source_filter, target_filter, priority_filter)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        let list_query = list_query_from_proto(req.query, req.sort, req.page_size, req.page_token);
+        let context = QueryContext::for_query(project_path, &list_query).await;
+        let page = apply_list_query(prs, &list_query, &context)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        Ok(Response::new(ListPrsResponse {
+            prs: page.items.iter().map(|p| pr_to_proto(p, priority_levels)).collect(),
+            total_count: page.total_count as i32,
+            next_page_token: page.next_page_token.unwrap_or_default(),
+        }))
+    }
+
+    async fn update_pr(
+        &self,
+        request: Request<UpdatePrRequest>,
+    ) -> Result<Response<UpdatePrResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        // Read config for priority_levels (for label generation)
+        let config = read_config(project_path).await.ok().flatten();
+        let priority_levels = config.as_ref().map(|c| c.priority_levels).unwrap_or(3);
+
+        let options = UpdatePrOptions {
+            title: if req.title.is_empty() { None } else { Some(req.title) },
+            description: if req.description.is_empty() { None } else { Some(req.description) },
+            status: if req.status.is_empty() { None } else { Some(req.status) },
+            source_branch: if req.source_branch.is_empty() { None } else { Some(req.source_branch) },
+            target_branch: if req.target_branch.is_empty() { None } else { Some(req.target_branch) },
+            linked_issues: if req.linked_issues.is_empty() { None } else { Some(req.linked_issues) },
+            reviewers: if req.reviewers.is_empty() { None } else { Some(req.reviewers) },
+            priority: if req.priority == 0 { None } else { Some(req.priority as u32) },
+            custom_fields: req.custom_fields,
+            milestone: if req.clear_milestone {
+                Some(String::new())
+            } else if req.milestone.is_empty() {
+                None
+            } else {
+                Some(req.milestone)
+            },
+            expected_updated_at: if req.expected_updated_at.is_empty() {
+                None
+            } else {
+                Some(req.expected_updated_at)
+            },
+        };
+
+        match update_pr(project_path, &req.pr_id, options).await {
+            Ok(result) => Ok(Response::new(UpdatePrResponse {
+                success: true,
+                error: String::new(),
+                pr: Some(pr_to_proto(&result.pr, priority_levels)),
+                manifest: Some(manifest_to_proto(&result.manifest)),
+                warnings: result.warnings,
+                conflict: false,
+            })),
+            Err(e) => Ok(Response::new(UpdatePrResponse {
+                success: false,
+                error: e.to_string(),
+                pr: match &e {
+                    crate::pr::PrCrudError::Conflict(current) => {
+                        Some(pr_to_proto(current, priority_levels))
+                    }
+                    _ => None,
+                },
+                manifest: None,
+                warnings: Vec::new(),
+                conflict: matches!(e, crate::pr::PrCrudError::Conflict(_)),
+            })),
+        }
+    }
+
+    async fn delete_pr(
+        &self,
+        request: Request<DeletePrRequest>,
+    ) -> Result<Response<DeletePrResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        match delete_pr(project_path, &req.pr_id).await {
+            Ok(result) => Ok(Response::new(DeletePrResponse {
+                success: true,
+                error: String::new(),
+                manifest: Some(manifest_to_proto(&result.manifest)),
+            })),
+            Err(e) => Ok(Response::new(DeletePrResponse {
+                success: false,
+                error: e.to_string(),
+                manifest: None,
+            })),
+        }
+    }
+
+    async fn get_next_pr_number(
+        &self,
+        request: Request<GetNextPrNumberRequest>,
+    ) -> Result<Response<GetNextPrNumberResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+        let prs_path = get_centy_path(project_path).join("prs");
+
+        match crate::pr::reconcile::get_next_pr_display_number(&prs_path).await {
+            Ok(next_number) => Ok(Response::new(GetNextPrNumberResponse { next_number })),
+            Err(e) => Err(Status::internal(e.to_string())),
+        }
+    }
+
+    // ============ Search RPCs ============
+
+    async fn search(
+        &self,
+        request: Request<SearchRequest>,
+    ) -> Result<Response<SearchResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        let entity_types = req
+            .entity_types
+            .iter()
+            .map(|t| SearchEntityType::parse(t))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let options = SearchOptions {
+            query: req.query,
+            entity_types,
+            limit: if req.limit <= 0 { None } else { Some(req.limit as usize) },
+        };
+
+        match search(project_path, options).await {
+            Ok(results) => Ok(Response::new(SearchResponse {
+                hits: results.hits.iter().map(search_hit_to_proto).collect(),
+                total_count: results.total_count as i32,
+            })),
+            Err(crate::search::SearchError::EmptyQuery) => {
+                Err(Status::invalid_argument("Search query is empty"))
+            }
+            Err(e) => Err(Status::internal(e.to_string())),
+        }
+    }
+
+    // ============ Calendar RPCs ============
+
+    async fn export_calendar(
+        &self,
+        request: Request<ExportCalendarRequest>,
+    ) -> Result<Response<ExportCalendarResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        match export_calendar(project_path).await {
+            Ok(export) => Ok(Response::new(ExportCalendarResponse {
+                success: true,
+                error: String::new(),
+                ics: export.ics,
+                event_count: export.event_count as i32,
+            })),
+            Err(e) => Ok(Response::new(ExportCalendarResponse {
+                success: false,
+                error: e.to_string(),
+                ics: String::new(),
+                event_count: 0,
+            })),
+        }
+    }
+
+    async fn list_trash(
+        &self,
+        request: Request<ListTrashRequest>,
+    ) -> Result<Response<ListTrashResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        match list_trash(project_path).await {
+            Ok(entries) => {
+                let total_count = entries.len() as i32;
+                Ok(Response::new(ListTrashResponse {
+                    entries: entries.iter().map(trash_entry_to_proto).collect(),
+                    total_count,
+                }))
+            }
+            Err(e) => Err(Status::internal(e.to_string())),
+        }
+    }
+
+    async fn restore_from_trash(
+        &self,
+        request: Request<RestoreFromTrashRequest>,
+    ) -> Result<Response<RestoreFromTrashResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        match restore_from_trash(project_path, &req.entry_id).await {
+            Ok(result) => Ok(Response::new(RestoreFromTrashResponse {
+                success: true,
+                error: String::new(),
+                entry: Some(trash_entry_to_proto(&result.entry)),
+                display_number: result.display_number.unwrap_or(0) as i32,
+                manifest: Some(manifest_to_proto(&result.manifest)),
+            })),
+            Err(e) => Ok(Response::new(RestoreFromTrashResponse {
+                success: false,
+                error: e.to_string(),
+                ..Default::default()
+            })),
+        }
+    }
+
+    async fn empty_trash(
+        &self,
+        request: Request<EmptyTrashRequest>,
+    ) -> Result<Response<EmptyTrashResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        match empty_trash(project_path).await {
+            Ok(result) => Ok(Response::new(EmptyTrashResponse {
                 success: true,
                 error: String::new(),
-                id: result.id,
-                display_number: result.display_number,
-                created_files: result.created_files,
+                purged_count: result.purged_count as i32,
                 manifest: Some(manifest_to_proto(&result.manifest)),
-                detected_source_branch: result.detected_source_branch,
             })),
-            Err(e) => Ok(Response::new(CreatePrResponse {
+            Err(e) => Ok(Response::new(EmptyTrashResponse {
                 success: false,
                 error: e.to_string(),
-                id: String::new(),
-                display_number: 0,
-                created_files: vec![],
+                purged_count: 0,
                 manifest: None,
-                detected_source_branch: String::new(),
             })),
         }
     }
 
-    async fn get_pr(
+    // ============ View RPCs ============
+
+    async fn list_views(
         &self,
-        request: Request<GetPrRequest>,
-    ) -> Result<Response<PullRequest>, Status> {
+        request: Request<ListViewsRequest>,
+    ) -> Result<Response<ListViewsResponse>, Status> {
         let req = request.into_inner();
         track_project_async(req.project_path.clone());
         let project_path = Path::new(&req.project_path);
 
-        // Read config for priority_levels (for label generation)
-        let config = read_config(project_path).await.ok().flatten();
-        let priority_levels = config.as_ref().map(|c| c.priority_levels).unwrap_or(3);
+        let entity = if req.entity.is_empty() {
+            None
+        } else {
+            Some(ViewEntity::parse(&req.entity).map_err(|e| Status::invalid_argument(e.to_string()))?)
+        };
 
-        match get_pr(project_path, &req.pr_id).await {
-            Ok(pr) => Ok(Response::new(pr_to_proto(&pr, priority_levels))),
-            Err(e) => Err(Status::not_found(e.to_string())),
+        match list_views(project_path, entity).await {
+            Ok(views) => {
+                let total_count = views.len() as i32;
+                Ok(Response::new(ListViewsResponse {
+                    views: views.iter().map(view_to_proto).collect(),
+                    total_count,
+                }))
+            }
+            Err(e) => Err(Status::internal(e.to_string())),
         }
     }
 
-    async fn get_pr_by_display_number(
+    async fn save_view(
         &self,
-        request: Request<GetPrByDisplayNumberRequest>,
-    ) -> Result<Response<PullRequest>, Status> {
+        request: Request<SaveViewRequest>,
+    ) -> Result<Response<SaveViewResponse>, Status> {
         let req = request.into_inner();
         track_project_async(req.project_path.clone());
         let project_path = Path::new(&req.project_path);
 
-        // Read config for priority_levels (for label generation)
-        let config = read_config(project_path).await.ok().flatten();
-        let priority_levels = config.as_ref().map(|c| c.priority_levels).unwrap_or(3);
+        let entity = if req.entity.is_empty() {
+            Ok(ViewEntity::default())
+        } else {
+            ViewEntity::parse(&req.entity)
+        };
+        let result = match entity {
+            Ok(entity) => {
+                let options = SaveViewOptions {
+                    title: req.title,
+                    slug: if req.slug.is_empty() { None } else { Some(req.slug) },
+                    entity,
+                    query: req.query,
+                    sort: req.sort,
+                    columns: req.columns,
+                };
+                save_view(project_path, options).await
+            }
+            Err(e) => Err(e),
+        };
 
-        match get_pr_by_display_number(project_path, req.display_number).await {
-            Ok(pr) => Ok(Response::new(pr_to_proto(&pr, priority_levels))),
-            Err(e) => Err(Status::not_found(e.to_string())),
+        match result {
+            Ok(result) => Ok(Response::new(SaveViewResponse {
+                success: true,
+                error: String::new(),
+                view: Some(view_to_proto(&result.view)),
+                created: result.created,
+                manifest: Some(manifest_to_proto(&result.manifest)),
+            })),
+            Err(e) => Ok(Response::new(SaveViewResponse {
+                success: false,
+                error: e.to_string(),
+                ..Default::default()
+            })),
         }
     }
 
-    async fn list_prs(
+    async fn delete_view(
         &self,
-        request: Request<ListPrsRequest>,
-    ) -> Result<Response<ListPrsResponse>, Status> {
+        request: Request<DeleteViewRequest>,
+    ) -> Result<Response<DeleteViewResponse>, Status> {
         let req = request.into_inner();
         track_project_async(req.project_path.clone());
         let project_path = Path::new(&req.project_path);
 
-        // Read config for priority_levels (for label generation)
-        let config = read_config(project_path).await.ok().flatten();
-        let priority_levels = config.as_ref().map(|c| c.priority_levels).unwrap_or(3);
-
-        let status_filter = if req.status.is_empty() { None } else { Some(req.status.as_str()) };
-        let source_filter = if req.source_branch.is_empty() { None } else { Some(req.source_branch.as_str()) };
-        let target_filter = if req.target_branch.is_empty() { None } else { Some(req.target_branch.as_str()) };
-        let priority_filter = if req.priority == 0 { None } else { Some(req.priority as u32) };
-
-        match list_prs(project_path, status_filter, source_filter, target_filter, priority_filter).await {
-            Ok(prs) => {
-                let total_count = prs.len() as i32;
-                Ok(Response::new(ListPrsResponse {
-                    prs: prs.into_iter().map(|p| pr_to_proto(&p, priority_levels)).collect(),
-                    total_count,
-                }))
-            }
-            Err(e) => Err(Status::internal(e.to_string())),
+        match delete_view(project_path, &req.slug).await {
+            Ok(manifest) => Ok(Response::new(DeleteViewResponse {
+                success: true,
+                error: String::new(),
+                manifest: Some(manifest_to_proto(&manifest)),
+            })),
+            Err(e) => Ok(Response::new(DeleteViewResponse {
+                success: false,
+                error: e.to_string(),
+                manifest: None,
+            })),
         }
     }
 
-    async fn update_pr(
+    async fn run_view(
         &self,
-        request: Request<UpdatePrRequest>,
-    ) -> Result<Response<UpdatePrResponse>, Status> {
+        request: Request<RunViewRequest>,
+    ) -> Result<Response<RunViewResponse>, Status> {
         let req = request.into_inner();
         track_project_async(req.project_path.clone());
         let project_path = Path::new(&req.project_path);
@@ -1155,69 +2402,137 @@ impl CentyDaemon for CentyDaemonService {
         let config = read_config(project_path).await.ok().flatten();
         let priority_levels = config.as_ref().map(|c| c.priority_levels).unwrap_or(3);
 
-        let options = UpdatePrOptions {
-            title: if req.title.is_empty() { None } else { Some(req.title) },
-            description: if req.description.is_empty() { None } else { Some(req.description) },
-            status: if req.status.is_empty() { None } else { Some(req.status) },
-            source_branch: if req.source_branch.is_empty() { None } else { Some(req.source_branch) },
-            target_branch: if req.target_branch.is_empty() { None } else { Some(req.target_branch) },
-            linked_issues: if req.linked_issues.is_empty() { None } else { Some(req.linked_issues) },
-            reviewers: if req.reviewers.is_empty() { None } else { Some(req.reviewers) },
-            priority: if req.priority == 0 { None } else { Some(req.priority as u32) },
-            custom_fields: req.custom_fields,
+        let result = match run_view(project_path, &req.slug, req.page_size.max(0) as u32, req.page_token).await {
+            Ok(result) => result,
+            Err(e @ ViewError::ViewNotFound(_)) => return Err(Status::not_found(e.to_string())),
+            Err(e @ ViewError::InvalidQuery(_)) => return Err(Status::invalid_argument(e.to_string())),
+            Err(e) => return Err(Status::internal(e.to_string())),
         };
 
-        match update_pr(project_path, &req.pr_id, options).await {
-            Ok(result) => Ok(Response::new(UpdatePrResponse {
-                success: true,
-                error: String::new(),
-                pr: Some(pr_to_proto(&result.pr, priority_levels)),
-                manifest: Some(manifest_to_proto(&result.manifest)),
-            })),
-            Err(e) => Ok(Response::new(UpdatePrResponse {
+        let mut response = RunViewResponse {
+            view: Some(view_to_proto(&result.view)),
+            total_count: result.total_count as i32,
+            next_page_token: result.next_page_token.unwrap_or_default(),
+            ..Default::default()
+        };
+        match &result.items {
+            ViewItems::Issues(issues) => {
+                response.issues = issues.iter().map(|i| issue_to_proto(i, priority_levels)).collect();
+            }
+            ViewItems::Prs(prs) => {
+                response.prs = prs.iter().map(|p| pr_to_proto(p, priority_levels)).collect();
+            }
+            ViewItems::Docs(docs) => {
+                response.docs = docs.iter().map(doc_to_proto).collect();
+            }
+        }
+        Ok(Response::new(response))
+    }
+
+    // ============ Integrity RPCs ============
+
+    async fn check_project(
+        &self,
+        request: Request<CheckProjectRequest>,
+    ) -> Result<Response<CheckProjectResponse>, Status> {
+        let req = request.into_inner();
+        track_project_async(req.project_path.clone());
+        let project_path = Path::new(&req.project_path);
+
+        match check_project(project_path).await {
+            Ok(findings) => {
+                let count = |severity| findings.iter().filter(|f| f.severity == severity).count() as i32;
+                Ok(Response::new(CheckProjectResponse {
+                    success: true,
+                    error: String::new(),
+                    error_count: count(Severity::Error),
+                    warning_count: count(Severity::Warning),
+                    info_count: count(Severity::Info),
+                    findings: findings.iter().map(finding_to_proto).collect(),
+                }))
+            }
+            Err(e) => Ok(Response::new(CheckProjectResponse {
                 success: false,
                 error: e.to_string(),
-                pr: None,
-                manifest: None,
+                ..Default::default()
             })),
         }
     }
 
-    async fn delete_pr(
+    async fn repair_project(
         &self,
-        request: Request<DeletePrRequest>,
-    ) -> Result<Response<DeletePrResponse>, Status> {
+        request: Request<RepairProjectRequest>,
+    ) -> Result<Response<RepairProjectResponse>, Status> {
         let req = request.into_inner();
         track_project_async(req.project_path.clone());
         let project_path = Path::new(&req.project_path);
 
-        match delete_pr(project_path, &req.pr_id).await {
-            Ok(result) => Ok(Response::new(DeletePrResponse {
-                success: true,
+        match repair_project(project_path, &req.finding_ids).await {
+            Ok(result) => Ok(Response::new(RepairProjectResponse {
+                success: result.failed.is_empty(),
                 error: String::new(),
-                manifest: Some(manifest_to_proto(&result.manifest)),
+                repaired: result.repaired.iter().map(finding_to_proto).collect(),
+                failed: result
+                    .failed
+                    .iter()
+                    .map(|failure| RepairFailure {
+                        finding: Some(finding_to_proto(&failure.finding)),
+                        error: failure.error.clone(),
+                    })
+                    .collect(),
+                remaining: result.remaining.iter().map(finding_to_proto).collect(),
             })),
-            Err(e) => Ok(Response::new(DeletePrResponse {
+            Err(e @ IntegrityError::FindingNotFound(_)) => Err(Status::not_found(e.to_string())),
+            Err(e) => Ok(Response::new(RepairProjectResponse {
                 success: false,
                 error: e.to_string(),
-                manifest: None,
+                ..Default::default()
             })),
         }
     }
 
-    async fn get_next_pr_number(
+    // ============ Watch RPCs ============
+
+    type WatchProjectStream = Pin<Box<dyn Stream<Item = Result<ChangeEvent, Status>> + Send>>;
+
+    async fn watch_project(
         &self,
-        request: Request<GetNextPrNumberRequest>,
-    ) -> Result<Response<GetNextPrNumberResponse>, Status> {
+        request: Request<WatchProjectRequest>,
+    ) -> Result<Response<Self::WatchProjectStream>, Status> {
         let req = request.into_inner();
         track_project_async(req.project_path.clone());
         let project_path = Path::new(&req.project_path);
-        let prs_path = get_centy_path(project_path).join("prs");
 
-        match crate::pr::reconcile::get_next_pr_display_number(&prs_path).await {
-            Ok(next_number) => Ok(Response::new(GetNextPrNumberResponse { next_number })),
-            Err(e) => Err(Status::internal(e.to_string())),
-        }
+        let entities = req
+            .entities
+            .iter()
+            .map(|name| ChangeEntity::parse(name).ok_or_else(|| WatchError::UnknownEntity(name.clone())))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let mut subscription = match watch_project(project_path, entities) {
+            Ok(subscription) => subscription,
+            Err(e @ WatchError::NotInitialized) => return Err(Status::failed_precondition(e.to_string())),
+            Err(e) => return Err(Status::internal(e.to_string())),
+        };
+
+        // Forward events until the client goes away
+        let (tx, rx) = tokio::sync::mpsc::channel(WATCH_BUFFER);
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = tx.closed() => break,
+                    event = subscription.recv() => {
+                        let Some(event) = event else { break };
+                        if tx.send(Ok(change_event_to_proto(&event))).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
     }
 }
 
@@ -1269,6 +2584,35 @@ fn config_to_proto(config: &CentyConfig) -> Config {
             update_status_on_start: config.llm.update_status_on_start,
             allow_direct_edits: config.llm.allow_direct_edits,
         }),
+        labels: config
+            .labels
+            .iter()
+            .map(|l| LabelDefinition {
+                name: l.name.clone(),
+                color: l.color.clone().unwrap_or_default(),
+                description: l.description.clone().unwrap_or_default(),
+            })
+            .collect(),
+        members: config
+            .members
+            .iter()
+            .map(|m| Member {
+                name: m.name.clone(),
+                email: m.email.clone().unwrap_or_default(),
+                handle: m.handle.clone(),
+            })
+            .collect(),
+        assign_git_user_by_default: config.assign_git_user_by_default,
+        closed_states: config.closed_states.clone(),
+        block_close_with_open_children: config.block_close_with_open_children,
+        workflow: Some(workflow_to_proto(&config.workflow)),
+        pr_workflow: Some(workflow_to_proto(&config.pr_workflow)),
+        trash_retention_days: match config.trash_retention_days {
+            0 => -1,
+            days => days as i32,
+        },
+        archive_after_days: config.archive_after_days as i32,
+        etag: String::new(),
     }
 }
 
@@ -1299,6 +2643,88 @@ fn proto_to_config(proto: &Config) -> CentyConfig {
         state_colors: proto.state_colors.clone(),
         priority_colors: proto.priority_colors.clone(),
         llm: llm_config,
+        labels: proto
+            .labels
+            .iter()
+            .map(|l| InternalLabelDef {
+                name: l.name.trim().to_string(),
+                color: if l.color.is_empty() { None } else { Some(l.color.clone()) },
+                description: if l.description.is_empty() { None } else { Some(l.description.clone()) },
+            })
+            .collect(),
+        members: proto
+            .members
+            .iter()
+            .map(|m| InternalMemberDef {
+                name: m.name.clone(),
+                email: if m.email.is_empty() { None } else { Some(m.email.clone()) },
+                handle: m.handle.trim().to_string(),
+            })
+            .collect(),
+        assign_git_user_by_default: proto.assign_git_user_by_default,
+        closed_states: if proto.closed_states.is_empty() {
+            default_closed_states()
+                .into_iter()
+                .filter(|s| proto.allowed_states.contains(s))
+                .collect()
+        } else {
+            proto.closed_states.clone()
+        },
+        block_close_with_open_children: proto.block_close_with_open_children,
+        workflow: proto.workflow.as_ref().map(proto_to_workflow).unwrap_or_default(),
+        pr_workflow: proto.pr_workflow.as_ref().map(proto_to_workflow).unwrap_or_default(),
+        trash_retention_days: match proto.trash_retention_days {
+            0 => CentyConfig::default().trash_retention_days,
+            days if days < 0 => 0,
+            days => days as u32,
+        },
+        archive_after_days: proto.archive_after_days.max(0) as u32,
+    }
+}
+
+fn workflow_to_proto(workflow: &InternalWorkflowConfig) -> WorkflowConfig {
+    let mut transitions: Vec<WorkflowTransition> = workflow
+        .transitions
+        .iter()
+        .map(|(from, to)| WorkflowTransition {
+            from: from.clone(),
+            to: to.clone(),
+        })
+        .collect();
+    transitions.sort_by(|a, b| a.from.cmp(&b.from));
+
+    WorkflowConfig {
+        strict: workflow.strict,
+        transitions,
+        comment_required: workflow
+            .comment_required
+            .iter()
+            .map(|r| TransitionRule {
+                from: r.from.clone(),
+                to: r.to.clone(),
+            })
+            .collect(),
+        terminal_states: workflow.terminal_states.clone(),
+    }
+}
+
+fn proto_to_workflow(proto: &WorkflowConfig) -> InternalWorkflowConfig {
+    InternalWorkflowConfig {
+        strict: proto.strict,
+        transitions: proto
+            .transitions
+            .iter()
+            .map(|t| (t.from.clone(), t.to.clone()))
+            .collect(),
+        comment_required: proto
+            .comment_required
+            .iter()
+            .map(|r| InternalTransitionRule {
+                from: r.from.clone(),
+                to: r.to.clone(),
+            })
+            .collect(),
+        terminal_states: proto.terminal_states.clone(),
     }
 }
 
@@ -1329,13 +2755,30 @@ fn validate_config(config: &CentyConfig) -> Result<(), String> {
             return Err(format!("duplicate custom field name: '{}'", field.name));
         }
 
+        let Some(field_type) = CustomFieldType::parse(&field.field_type) else {
+            return Err(format!(
+                "custom field '{}' has unknown type '{}'",
+                field.name, field.field_type
+            ));
+        };
+
         // Check enum fields have values
-        if field.field_type == "enum" && field.enum_values.is_empty() {
+        if matches!(field_type, CustomFieldType::Enum | CustomFieldType::MultiEnum)
+            && field.enum_values.is_empty()
+        {
             return Err(format!(
-                "custom field '{}' is of type 'enum' but has no enum_values",
-                field.name
+                "custom field '{}' is of type '{}' but has no enum_values",
+                field.name,
+                field_type.as_str()
             ));
         }
+
+        // Check defaults are valid values for the field
+        if let Some(default_value) = field.default_value.as_ref().filter(|v| !v.is_empty()) {
+            parse_field_value(field, default_value, &config.members).map_err(|e| {
+                format!("invalid default for custom field '{}': {}", field.name, e)
+            })?;
+        }
     }
 
     // Validate color formats (hex colors)
@@ -1357,6 +2800,47 @@ fn validate_config(config: &CentyConfig) -> Result<(), String> {
         }
     }
 
+    // Check label names are non-empty and unique, and label colors are valid
+    let mut label_names = std::collections::HashSet::new();
+    for label in &config.labels {
+        if label.name.is_empty() {
+            return Err("label name must not be empty".to_string());
+        }
+        if !label_names.insert(&label.name) {
+            return Err(format!("duplicate label name: '{}'", label.name));
+        }
+        if let Some(color) = &label.color {
+            if !hex_color_regex.is_match(color) {
+                return Err(format!(
+                    "invalid color '{}' for label '{}': must be hex format (#RGB or #RRGGBB)",
+                    color, label.name
+                ));
+            }
+        }
+    }
+
+    // Check member handles are non-empty and unique
+    let mut member_handles = std::collections::HashSet::new();
+    for member in &config.members {
+        if member.handle.is_empty() {
+            return Err(format!("member '{}' must have a handle", member.name));
+        }
+        if !member_handles.insert(&member.handle) {
+            return Err(format!("duplicate member handle: '{}'", member.handle));
+        }
+    }
+
+    // Check workflows only name known states
+    config.workflow.validate(&config.allowed_states)?;
+    config.pr_workflow.validate(&default_pr_statuses())?;
+
+    // Check closed_states are all allowed states
+    for state in &config.closed_states {
+        if !config.allowed_states.contains(state) {
+            return Err(format!("closed state '{}' must be in allowed_states", state));
+        }
+    }
+
     Ok(())
 }
 
@@ -1376,7 +2860,165 @@ fn issue_to_proto(issue: &crate::issue::Issue, priority_levels: u32) -> Issue {
             updated_at: issue.metadata.updated_at.clone(),
             custom_fields: issue.metadata.custom_fields.clone(),
             priority_label: priority_label(issue.metadata.priority, priority_levels),
+            labels: issue.metadata.labels.clone(),
+            assignees: issue.metadata.assignees.clone(),
+            parent_id: issue.metadata.parent_id.clone().unwrap_or_default(),
+            start_date: issue.metadata.start_date.clone().unwrap_or_default(),
+            due_date: issue.metadata.due_date.clone().unwrap_or_default(),
+            milestone: issue.metadata.milestone.clone().unwrap_or_default(),
+            archived: issue.metadata.archived,
+            previous_display_numbers: issue.metadata.previous_display_numbers.clone(),
+            typed_custom_fields: issue
+                .metadata
+                .custom_field_values
+                .iter()
+                .map(|(k, v)| (k.clone(), custom_field_value_to_proto(v)))
+                .collect(),
+            relations: issue
+                .metadata
+                .relations
+                .iter()
+                .map(|r| IssueRelation {
+                    r#type: r.kind.as_str().to_string(),
+                    issue_id: r.issue_id.clone(),
+                })
+                .collect(),
+        }),
+        child_counts: HashMap::new(),
+        redirected: false,
+        previous_holder_ids: Vec::new(),
+    }
+}
+
+fn custom_field_value_to_proto(value: &serde_json::Value) -> CustomFieldValue {
+    use custom_field_value::Kind;
+
+    let kind = match value {
+        serde_json::Value::Number(n) => Kind::NumberValue(n.as_f64().unwrap_or_default()),
+        serde_json::Value::Bool(b) => Kind::BoolValue(*b),
+        serde_json::Value::Array(items) => Kind::ListValue(StringList {
+            values: items.iter().map(crate::issue::value_to_string).collect(),
         }),
+        other => Kind::StringValue(crate::issue::value_to_string(other)),
+    };
+    CustomFieldValue { kind: Some(kind) }
+}
+
+fn field_errors_to_proto(err: &crate::issue::CustomFieldError) -> Vec<FieldError> {
+    err.field_errors()
+        .iter()
+        .map(|e| FieldError {
+            field: e.field.clone(),
+            message: e.message.clone(),
+        })
+        .collect()
+}
+
+/// Build a list query from the query, sort and paging fields shared by list requests
+fn list_query_from_proto(query: String, sort: String, page_size: i32, page_token: String) -> ListQuery {
+    ListQuery {
+        query,
+        sort,
+        page_size: page_size.max(0) as u32,
+        page_token,
+    }
+}
+
+/// Build an issue filter from list request fields
+fn issue_filter_from_proto(req: ListIssuesRequest) -> Result<IssueFilter, String> {
+    let custom_fields = req
+        .custom_field_filters
+        .into_iter()
+        .map(|f| {
+            let op = FilterOp::parse(&f.op)
+                .ok_or_else(|| format!("Invalid filter operator: '{}'", f.op))?;
+            Ok(crate::issue::CustomFieldFilter {
+                field: f.field,
+                op,
+                value: f.value,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let parse_day = |value: &str| {
+        if value.is_empty() {
+            Ok(None)
+        } else {
+            parse_date(value).map(Some)
+        }
+    };
+
+    // Convert int32 priority filter: 0 means no filter
+    Ok(IssueFilter {
+        status: if req.status.is_empty() { None } else { Some(req.status) },
+        priority: if req.priority == 0 { None } else { Some(req.priority as u32) },
+        labels: req.labels,
+        assignee: if req.assignee.is_empty() { None } else { Some(req.assignee) },
+        milestone: if req.milestone.is_empty() { None } else { Some(req.milestone) },
+        custom_fields,
+        due_before: parse_day(&req.due_before)?,
+        due_after: parse_day(&req.due_after)?,
+        overdue: req.overdue,
+        closed_states: Vec::new(),
+        include_archived: req.include_archived,
+    })
+}
+
+/// Select bulk targets by ID, falling back to the filter and its query
+fn bulk_target_from_proto(
+    issue_ids: Vec<String>,
+    filter: Option<ListIssuesRequest>,
+) -> Result<BulkTarget, String> {
+    if !issue_ids.is_empty() {
+        return Ok(BulkTarget::Ids(issue_ids));
+    }
+    let Some(filter) = filter else {
+        return Err("Either issue_ids or filter is required".to_string());
+    };
+    // A page of a bulk selection would silently leave the rest untouched
+    if filter.page_size != 0 || !filter.page_token.is_empty() {
+        return Err("page_size and page_token are not supported in a bulk filter".to_string());
+    }
+
+    let list_query = list_query_from_proto(filter.query.clone(), filter.sort.clone(), 0, String::new());
+    Ok(BulkTarget::Query(issue_filter_from_proto(filter)?, list_query))
+}
+
+fn bulk_items_to_proto(items: Vec<crate::issue::BulkItemResult>) -> Vec<BulkItemResult> {
+    items
+        .into_iter()
+        .map(|item| BulkItemResult {
+            issue_id: item.issue_id,
+            success: item.error.is_none(),
+            error: item.error.unwrap_or_default(),
+            warnings: item.warnings,
+        })
+        .collect()
+}
+
+/// Convert an issue to proto, including its child counts by status
+async fn issue_with_child_counts(
+    project_path: &Path,
+    issue: &crate::issue::Issue,
+    priority_levels: u32,
+) -> Issue {
+    let mut proto_issue = issue_to_proto(issue, priority_levels);
+    if let Ok(counts) = child_status_counts(project_path, &issue.id).await {
+        proto_issue.child_counts = counts
+            .into_iter()
+            .map(|(status, count)| (status, count as i32))
+            .collect();
+    }
+    proto_issue
+}
+
+fn issue_tree_to_proto(node: &crate::issue::IssueTreeNode, priority_levels: u32) -> IssueTreeNode {
+    IssueTreeNode {
+        issue: Some(issue_to_proto(&node.issue, priority_levels)),
+        children: node
+            .children
+            .iter()
+            .map(|child| issue_tree_to_proto(child, priority_levels))
+            .collect(),
     }
 }
 
@@ -1392,6 +3034,37 @@ fn doc_to_proto(doc: &crate::docs::Doc) -> Doc {
     }
 }
 
+fn milestone_to_proto(
+    milestone: &crate::milestone::Milestone,
+    progress: Option<&crate::milestone::MilestoneProgress>,
+) -> Milestone {
+    let counts = |counts: &std::collections::BTreeMap<String, u32>| {
+        counts
+            .iter()
+            .map(|(status, count)| (status.clone(), *count as i32))
+            .collect()
+    };
+
+    Milestone {
+        slug: milestone.slug.clone(),
+        title: milestone.title.clone(),
+        description: milestone.description.clone(),
+        target_date: milestone.metadata.target_date.clone().unwrap_or_default(),
+        state: milestone.metadata.state.as_str().to_string(),
+        created_at: milestone.metadata.created_at.clone(),
+        updated_at: milestone.metadata.updated_at.clone(),
+        progress: progress.map(|p| MilestoneProgress {
+            total_issues: p.total_issues as i32,
+            completed_issues: p.completed_issues as i32,
+            total_prs: p.total_prs as i32,
+            completed_prs: p.completed_prs as i32,
+            completion_percentage: p.completion_percentage,
+            issue_status_counts: counts(&p.issue_status_counts),
+            pr_status_counts: counts(&p.pr_status_counts),
+        }),
+    }
+}
+
 fn project_info_to_proto(info: &ProjectInfo) -> proto::ProjectInfo {
     proto::ProjectInfo {
         path: info.path.clone(),
@@ -1416,6 +3089,18 @@ fn asset_info_to_proto(asset: &AssetInfo) -> Asset {
     }
 }
 
+fn comment_to_proto(comment: &crate::issue::Comment, issue_id: &str) -> Comment {
+    Comment {
+        id: comment.id.clone(),
+        issue_id: issue_id.to_string(),
+        author: comment.author.clone(),
+        body: comment.body.clone(),
+        reply_to: comment.reply_to.clone().unwrap_or_default(),
+        created_at: comment.created_at.clone(),
+        updated_at: comment.updated_at.clone(),
+    }
+}
+
 fn pr_to_proto(pr: &crate::pr::PullRequest, priority_levels: u32) -> PullRequest {
     PullRequest {
         id: pr.id.clone(),
@@ -1436,6 +3121,75 @@ fn pr_to_proto(pr: &crate::pr::PullRequest, priority_levels: u32) -> PullRequest
             merged_at: pr.metadata.merged_at.clone(),
             closed_at: pr.metadata.closed_at.clone(),
             custom_fields: pr.metadata.custom_fields.clone(),
+            milestone: pr.metadata.milestone.clone().unwrap_or_default(),
+            previous_display_numbers: pr.metadata.previous_display_numbers.clone(),
         }),
     }
 }
+
+fn trash_entry_to_proto(entry: &crate::trash::TrashEntry) -> TrashEntry {
+    TrashEntry {
+        entry_id: entry.entry_id.clone(),
+        kind: entry.kind.as_str().to_string(),
+        item_id: entry.item_id.clone(),
+        title: entry.title.clone(),
+        display_number: entry.display_number.unwrap_or(0) as i32,
+        deleted_at: entry.deleted_at.clone(),
+    }
+}
+
+fn priority_change_to_proto(change: &crate::issue::PriorityChange) -> PriorityChange {
+    PriorityChange {
+        kind: change.kind.as_str().to_string(),
+        id: change.id.clone(),
+        display_number: change.display_number as i32,
+        title: change.title.clone(),
+        old_priority: change.old_priority as i32,
+        new_priority: change.new_priority as i32,
+    }
+}
+
+fn finding_to_proto(finding: &crate::integrity::Finding) -> IntegrityFinding {
+    IntegrityFinding {
+        id: finding.id.clone(),
+        kind: finding.kind.as_str().to_string(),
+        severity: finding.severity.as_str().to_string(),
+        path: finding.path.clone(),
+        message: finding.message.clone(),
+        suggested_fix: finding.suggested_fix.clone(),
+    }
+}
+
+fn view_to_proto(view: &crate::view::View) -> View {
+    View {
+        slug: view.slug.clone(),
+        title: view.title.clone(),
+        entity: view.entity.as_str().to_string(),
+        query: view.query.clone(),
+        sort: view.sort.clone(),
+        columns: view.columns.clone(),
+        created_at: view.created_at.clone(),
+        updated_at: view.updated_at.clone(),
+    }
+}
+
+fn change_event_to_proto(event: &crate::feed::ChangeEvent) -> ChangeEvent {
+    ChangeEvent {
+        entity: event.entity.as_str().to_string(),
+        kind: event.kind.as_str().to_string(),
+        id: event.id.clone(),
+        source: event.source.as_str().to_string(),
+        timestamp: event.timestamp.clone(),
+    }
+}
+
+fn search_hit_to_proto(hit: &crate::search::SearchHit) -> SearchHit {
+    SearchHit {
+        entity_type: hit.entity_type.as_str().to_string(),
+        id: hit.id.clone(),
+        display_number: hit.display_number,
+        title: hit.title.clone(),
+        snippet: hit.snippet.clone(),
+        score: hit.score,
+    }
+}
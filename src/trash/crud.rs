@@ -0,0 +1,480 @@
+use crate::feed::{notify_change, ChangeEntity, ChangeKind};
+use crate::config::read_config;
+use crate::docs::read_doc_from_disk;
+use crate::issue::archive::archived_issues_path;
+use crate::issue::crud::read_issue_from_disk;
+use crate::issue::hierarchy::reattach_children;
+use crate::issue::relations::restore_issue_relations;
+use crate::issue::{get_next_display_number, is_valid_issue_folder, IssueMetadata, IssueRelation};
+use crate::manifest::{
+    read_manifest, write_manifest, update_manifest_timestamp, CentyManifest,
+};
+use crate::milestone::milestone_exists;
+use crate::pr::crud::read_pr_from_disk;
+use crate::pr::{get_next_pr_display_number, is_valid_pr_folder};
+use crate::search::{index_doc, index_issue, index_pr};
+use crate::utils::{get_centy_path, lock_project, now_iso, write_atomic};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use tokio::fs;
+use tracing::warn;
+
+/// Per-entry description written next to the trashed data
+const ENTRY_FILE: &str = "entry.json";
+/// The trashed folder (issues, PRs) or file (docs)
+const DATA_NAME: &str = "data";
+
+#[derive(Error, Debug)]
+pub enum TrashError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Manifest error: {0}")]
+    ManifestError(#[from] crate::manifest::ManifestError),
+
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("Centy not initialized. Run 'centy init' first.")]
+    NotInitialized,
+
+    #[error("Trash entry '{0}' not found")]
+    EntryNotFound(String),
+
+    #[error("Cannot restore: {0} already exists")]
+    ItemExists(String),
+
+    #[error("Issue error: {0}")]
+    IssueError(#[from] crate::issue::IssueCrudError),
+
+    #[error("Doc error: {0}")]
+    DocError(#[from] crate::docs::DocError),
+
+    #[error("PR error: {0}")]
+    PrError(#[from] crate::pr::PrCrudError),
+
+    #[error("Reconcile error: {0}")]
+    ReconcileError(#[from] crate::issue::ReconcileError),
+
+    #[error("PR reconcile error: {0}")]
+    PrReconcileError(#[from] crate::pr::ReconcileError),
+
+    #[error("Relation error: {0}")]
+    RelationError(#[from] crate::issue::RelationError),
+}
+
+/// Kind of entity in the trash
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrashKind {
+    Issue,
+    Doc,
+    Pr,
+}
+
+impl TrashKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TrashKind::Issue => "issue",
+            TrashKind::Doc => "doc",
+            TrashKind::Pr => "pr",
+        }
+    }
+}
+
+/// A deleted issue, doc or PR waiting in `.centy/trash/`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashEntry {
+    /// Folder name inside `.centy/trash/`: the kind, item ID and deletion time
+    /// in milliseconds, e.g. "doc-{slug}-{millis}". The time keeps repeated
+    /// deletions of the same doc slug apart.
+    #[serde(skip)]
+    pub entry_id: String,
+    pub kind: TrashKind,
+    /// Issue/PR ID or doc slug
+    pub item_id: String,
+    pub title: String,
+    /// Display number at deletion time (issues and PRs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_number: Option<u32>,
+    pub deleted_at: String,
+    #[serde(flatten)]
+    pub links: RemovedLinks,
+}
+
+/// Links to other issues that were cut when an issue was trashed, so they
+/// can be put back on restore
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemovedLinks {
+    /// Relations whose inverse was removed from the other issue
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relations: Vec<IssueRelation>,
+    /// Children whose parent was cleared
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub child_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RestoreResult {
+    pub entry: TrashEntry,
+    /// Display number after restoring; differs from the entry's when the old
+    /// one was taken in the meantime
+    pub display_number: Option<u32>,
+    pub manifest: CentyManifest,
+}
+
+#[derive(Debug, Clone)]
+pub struct EmptyTrashResult {
+    pub purged_count: u32,
+    pub manifest: CentyManifest,
+}
+
+fn trash_path(project_path: &Path) -> PathBuf {
+    get_centy_path(project_path).join("trash")
+}
+
+/// Move a deleted item's folder or file into the trash, then purge entries
+/// past the configured retention.
+pub(crate) async fn move_to_trash(
+    project_path: &Path,
+    kind: TrashKind,
+    item_id: &str,
+    title: &str,
+    display_number: Option<u32>,
+    source: &Path,
+    links: RemovedLinks,
+) -> Result<(), std::io::Error> {
+    let mut millis = Utc::now().timestamp_millis();
+    let (entry_id, entry_path) = loop {
+        let entry_id = format!("{}-{}-{}", kind.as_str(), item_id, millis);
+        let entry_path = trash_path(project_path).join(&entry_id);
+        if !entry_path.exists() {
+            break (entry_id, entry_path);
+        }
+        millis += 1;
+    };
+    fs::create_dir_all(&entry_path).await?;
+    fs::rename(source, entry_path.join(DATA_NAME)).await?;
+
+    let entry = TrashEntry {
+        entry_id,
+        kind,
+        item_id: item_id.to_string(),
+        title: title.to_string(),
+        display_number,
+        deleted_at: now_iso(),
+        links,
+    };
+    write_atomic(entry_path.join(ENTRY_FILE), serde_json::to_string_pretty(&entry)?).await?;
+
+    if let Err(e) = purge_expired(project_path).await {
+        warn!(error = %e, "Failed to purge expired trash entries");
+    }
+    Ok(())
+}
+
+/// List trash entries, newest first. Entries past the retention are purged first.
+pub async fn list_trash(project_path: &Path) -> Result<Vec<TrashEntry>, TrashError> {
+    read_manifest(project_path)
+        .await?
+        .ok_or(TrashError::NotInitialized)?;
+
+    {
+        let _lock = lock_project(project_path).await?;
+        purge_expired(project_path).await?;
+    }
+    let mut entries = read_entries(project_path).await?;
+    entries.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    Ok(entries)
+}
+
+/// Put a trashed item back where it came from.
+///
+/// A restored issue or PR keeps its display number if no other item has
+/// taken it, and gets the next free one otherwise. An issue's relations and
+/// children are linked again where the other issue still exists; links to
+/// items that no longer exist are dropped.
+pub async fn restore_from_trash(
+    project_path: &Path,
+    entry_id: &str,
+) -> Result<RestoreResult, TrashError> {
+    let mut manifest = read_manifest(project_path)
+        .await?
+        .ok_or(TrashError::NotInitialized)?;
+
+    // The entry ID and the item ID it names are both joined into paths
+    let (kind, item_id) =
+        parse_entry_id(entry_id).ok_or_else(|| TrashError::EntryNotFound(entry_id.to_string()))?;
+    let entry_path = trash_path(project_path).join(entry_id);
+    let entry = read_entry(&entry_path, entry_id)
+        .await
+        .filter(|entry| entry.kind == kind && entry.item_id == item_id)
+        .ok_or_else(|| TrashError::EntryNotFound(entry_id.to_string()))?;
+    let data_path = entry_path.join(DATA_NAME);
+    let centy_path = get_centy_path(project_path);
+
+    // Held while the display number is checked or allocated and written
+    let _lock = lock_project(project_path).await?;
+    let display_number = match entry.kind {
+        TrashKind::Issue => {
+            let issues_path = centy_path.join("issues");
+            let target = issues_path.join(&entry.item_id);
+            if target.exists() {
+                return Err(TrashError::ItemExists(format!("issue {}", entry.item_id)));
+            }
+            fs::create_dir_all(&issues_path).await?;
+            let number = match entry.display_number {
+                Some(n)
+                    if n > 0
+                        && !display_number_taken(&issues_path, n).await?
+                        && !display_number_taken(&archived_issues_path(project_path), n).await? =>
+                {
+                    n
+                }
+                _ => get_next_display_number(&issues_path).await?,
+            };
+            fs::rename(&data_path, &target).await?;
+
+            let metadata_path = target.join("metadata.json");
+            let mut metadata: IssueMetadata =
+                serde_json::from_str(&fs::read_to_string(&metadata_path).await?)?;
+            if metadata.display_number != number && metadata.display_number != 0 {
+                metadata.previous_display_numbers.push(metadata.display_number);
+            }
+            metadata.display_number = number;
+            // The other side of each relation was removed on delete
+            metadata.relations =
+                restore_issue_relations(project_path, &entry.item_id, &entry.links.relations).await?;
+            metadata.parent_id = metadata
+                .parent_id
+                .filter(|parent| issues_path.join(parent).join("metadata.json").exists());
+            metadata.milestone = metadata
+                .milestone
+                .filter(|slug| milestone_exists(project_path, slug));
+            write_atomic(&metadata_path, serde_json::to_string_pretty(&metadata)?).await?;
+            reattach_children(project_path, &entry.item_id, &entry.links.child_ids).await?;
+
+            let issue = read_issue_from_disk(&target, &entry.item_id).await?;
+            index_issue(project_path, &issue);
+            notify_change(project_path, ChangeEntity::Issue, ChangeKind::Created, &entry.item_id);
+            Some(number)
+        }
+        TrashKind::Pr => {
+            let prs_path = centy_path.join("prs");
+            let target = prs_path.join(&entry.item_id);
+            if target.exists() {
+                return Err(TrashError::ItemExists(format!("PR {}", entry.item_id)));
+            }
+            fs::create_dir_all(&prs_path).await?;
+            let number = match entry.display_number {
+                Some(n) if n > 0 && !display_number_taken(&prs_path, n).await? => n,
+                _ => get_next_pr_display_number(&prs_path).await?,
+            };
+            fs::rename(&data_path, &target).await?;
+
+            // Rewrite only the display numbers so unknown fields survive
+            let metadata_path = target.join("metadata.json");
+            let mut metadata: serde_json::Value =
+                serde_json::from_str(&fs::read_to_string(&metadata_path).await?)?;
+            if let Some(old) = entry.display_number.filter(|&n| n != number && n != 0) {
+                match metadata["previousDisplayNumbers"].as_array_mut() {
+                    Some(previous) => previous.push(old.into()),
+                    None => metadata["previousDisplayNumbers"] = vec![old].into(),
+                }
+            }
+            metadata["displayNumber"] = number.into();
+            write_atomic(&metadata_path, serde_json::to_string_pretty(&metadata)?).await?;
+
+            let pr = read_pr_from_disk(&target, &entry.item_id).await?;
+            index_pr(project_path, &pr);
+            notify_change(project_path, ChangeEntity::Pr, ChangeKind::Created, &entry.item_id);
+            Some(number)
+        }
+        TrashKind::Doc => {
+            let docs_path = centy_path.join("docs");
+            let target = docs_path.join(format!("{}.md", entry.item_id));
+            if target.exists() {
+                return Err(TrashError::ItemExists(format!("doc '{}'", entry.item_id)));
+            }
+            fs::create_dir_all(&docs_path).await?;
+            fs::rename(&data_path, &target).await?;
+
+            let doc = read_doc_from_disk(&target, &entry.item_id).await?;
+            index_doc(project_path, &doc);
+            notify_change(project_path, ChangeEntity::Doc, ChangeKind::Created, &entry.item_id);
+            None
+        }
+    };
+
+    fs::remove_dir_all(&entry_path).await?;
+
+    update_manifest_timestamp(&mut manifest);
+    write_manifest(project_path, &manifest).await?;
+
+    Ok(RestoreResult {
+        entry,
+        display_number,
+        manifest,
+    })
+}
+
+/// Permanently delete everything in the trash
+pub async fn empty_trash(project_path: &Path) -> Result<EmptyTrashResult, TrashError> {
+    let mut manifest = read_manifest(project_path)
+        .await?
+        .ok_or(TrashError::NotInitialized)?;
+    let _lock = lock_project(project_path).await?;
+
+    let purged_count = purge(project_path, |_| true).await?;
+
+    update_manifest_timestamp(&mut manifest);
+    write_manifest(project_path, &manifest).await?;
+
+    Ok(EmptyTrashResult {
+        purged_count,
+        manifest,
+    })
+}
+
+/// Delete entries older than `trash_retention_days` (0 = keep forever)
+async fn purge_expired(project_path: &Path) -> Result<u32, TrashError> {
+    let retention_days = read_config(project_path)
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+        .trash_retention_days;
+    if retention_days == 0 {
+        return Ok(0);
+    }
+
+    let cutoff = Utc::now() - Duration::days(i64::from(retention_days));
+    purge(project_path, |entry| {
+        DateTime::parse_from_rfc3339(&entry.deleted_at).is_ok_and(|t| t < cutoff)
+    })
+    .await
+}
+
+async fn purge(
+    project_path: &Path,
+    should_purge: impl Fn(&TrashEntry) -> bool,
+) -> Result<u32, TrashError> {
+    let mut purged = 0;
+    for entry in read_entries(project_path).await? {
+        if should_purge(&entry) {
+            fs::remove_dir_all(trash_path(project_path).join(&entry.entry_id)).await?;
+            purged += 1;
+        }
+    }
+    Ok(purged)
+}
+
+async fn read_entries(project_path: &Path) -> Result<Vec<TrashEntry>, TrashError> {
+    let trash_path = trash_path(project_path);
+    if !trash_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = Vec::new();
+    let mut dir = fs::read_dir(&trash_path).await?;
+    while let Some(item) = dir.next_entry().await? {
+        if !item.file_type().await?.is_dir() {
+            continue;
+        }
+        let Some(entry_id) = item.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        match read_entry(&item.path(), &entry_id).await {
+            Some(entry) => entries.push(entry),
+            None => warn!(entry_id = %entry_id, "Skipping unreadable trash entry"),
+        }
+    }
+    Ok(entries)
+}
+
+/// Split an entry ID into its kind and item ID. Returns `None` unless it is
+/// `<kind>-<id>-<millis>` with an issue/PR folder name or a doc slug as the ID.
+fn parse_entry_id(entry_id: &str) -> Option<(TrashKind, &str)> {
+    let (kind, rest) = entry_id.split_once('-')?;
+    let (item_id, millis) = rest.rsplit_once('-')?;
+    if millis.is_empty() || !millis.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let kind = match kind {
+        "issue" => TrashKind::Issue,
+        "pr" => TrashKind::Pr,
+        "doc" => TrashKind::Doc,
+        _ => return None,
+    };
+    let valid = match kind {
+        TrashKind::Issue => is_valid_issue_folder(item_id),
+        TrashKind::Pr => is_valid_pr_folder(item_id),
+        TrashKind::Doc => {
+            !item_id.is_empty() && item_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        }
+    };
+    valid.then_some((kind, item_id))
+}
+
+async fn read_entry(entry_path: &Path, entry_id: &str) -> Option<TrashEntry> {
+    let content = fs::read_to_string(entry_path.join(ENTRY_FILE)).await.ok()?;
+    let mut entry: TrashEntry = serde_json::from_str(&content).ok()?;
+    entry.entry_id = entry_id.to_string();
+    Some(entry)
+}
+
+/// Whether any issue or PR folder in `dir` already uses this display number
+async fn display_number_taken(dir: &Path, display_number: u32) -> Result<bool, std::io::Error> {
+    if !dir.exists() {
+        return Ok(false);
+    }
+
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let Ok(content) = fs::read_to_string(entry.path().join("metadata.json")).await else {
+            continue;
+        };
+        let number = serde_json::from_str::<serde_json::Value>(&content)
+            .ok()
+            .and_then(|m| m.get("displayNumber")?.as_u64());
+        if number == Some(u64::from(display_number)) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_entry_id() {
+        let uuid = "550e8400-e29b-41d4-a716-446655440000";
+        assert_eq!(
+            parse_entry_id(&format!("issue-{}-1718000000000", uuid)),
+            Some((TrashKind::Issue, uuid))
+        );
+        assert_eq!(
+            parse_entry_id(&format!("pr-{}-1718000000000", uuid)),
+            Some((TrashKind::Pr, uuid))
+        );
+        assert_eq!(
+            parse_entry_id("doc-getting-started-2-1718000000000"),
+            Some((TrashKind::Doc, "getting-started-2"))
+        );
+
+        assert_eq!(parse_entry_id(&format!("issue-{}", uuid)), None);
+        assert_eq!(parse_entry_id(&format!("../issues/{}-1", uuid)), None);
+        assert_eq!(parse_entry_id(&format!("issue-../{}-1", uuid)), None);
+        assert_eq!(parse_entry_id("doc-../config.json-1"), None);
+        assert_eq!(parse_entry_id("doc--1"), None);
+        assert_eq!(parse_entry_id("doc-notes-"), None);
+        assert_eq!(parse_entry_id("pr-0001-1"), None);
+        assert_eq!(parse_entry_id("view-board-1"), None);
+    }
+}
@@ -0,0 +1,12 @@
+//! Trash bin for deleted issues, docs and PRs
+//!
+//! Deleting an entity moves it to `.centy/trash/{kind}-{id}/`, where it can
+//! be restored until it is purged by `trashRetentionDays` or `empty_trash`.
+
+mod crud;
+
+pub(crate) use crud::move_to_trash;
+pub use crud::{
+    empty_trash, list_trash, restore_from_trash, EmptyTrashResult, RemovedLinks, RestoreResult,
+    TrashEntry, TrashError, TrashKind,
+};
@@ -0,0 +1,165 @@
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Suffix of the temporary file a write goes to before it is renamed into place
+pub const TEMP_SUFFIX: &str = ".tmp";
+
+static NEXT_TEMP: AtomicU64 = AtomicU64::new(0);
+
+/// Whether a file name is one of our temporary files, e.g. left behind by a
+/// crash. Watchers and folder scans skip these.
+pub fn is_temp_file(name: &str) -> bool {
+    name.starts_with('.') && name.ends_with(TEMP_SUFFIX)
+}
+
+/// Replace a file's contents so that readers, and the file after a crash or
+/// a full disk, see either the old or the new contents but never a mix.
+///
+/// Drop-in for `tokio::fs::write`: the data goes to a hidden temporary file
+/// next to `path`, is flushed to disk and then renamed over `path`.
+pub async fn write_atomic(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    write_files_atomic(&[(path.as_ref(), contents.as_ref())]).await
+}
+
+/// Write several files that only make sense together, such as an issue's
+/// `issue.md` and `metadata.json`.
+///
+/// Every file is staged before any is replaced, so a failed write changes
+/// nothing. The files are then renamed into place in the given order: list
+/// the file that marks the item as present (its metadata) last.
+pub async fn write_files_atomic(files: &[(&Path, &[u8])]) -> io::Result<()> {
+    let files: Vec<(PathBuf, Vec<u8>)> = files
+        .iter()
+        .map(|(path, contents)| (path.to_path_buf(), contents.to_vec()))
+        .collect();
+    tokio::task::spawn_blocking(move || write_files_blocking(&files))
+        .await
+        .map_err(io::Error::other)?
+}
+
+fn write_files_blocking(files: &[(PathBuf, Vec<u8>)]) -> io::Result<()> {
+    let mut staged = Vec::with_capacity(files.len());
+    for (path, contents) in files {
+        match stage(path, contents) {
+            Ok(temp) => staged.push((temp, path)),
+            Err(e) => {
+                discard(&staged);
+                return Err(e);
+            }
+        }
+    }
+
+    let mut dirs: Vec<&Path> = Vec::new();
+    for (i, (temp, path)) in staged.iter().enumerate() {
+        if let Err(e) = fs::rename(temp, path) {
+            discard(&staged[i..]);
+            return Err(e);
+        }
+        if let Some(dir) = path.parent().filter(|dir| !dirs.contains(dir)) {
+            dirs.push(dir);
+        }
+    }
+    // Make the renames themselves survive a crash
+    for dir in dirs {
+        sync_dir(dir)?;
+    }
+    Ok(())
+}
+
+/// Write `contents` to a new temporary file next to `path` and flush it
+fn stage(path: &Path, contents: &[u8]) -> io::Result<PathBuf> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?
+        .to_string_lossy();
+    let temp = path.with_file_name(format!(
+        ".{}.{}-{}{}",
+        name,
+        std::process::id(),
+        NEXT_TEMP.fetch_add(1, Ordering::Relaxed),
+        TEMP_SUFFIX
+    ));
+
+    let result = File::create(&temp).and_then(|mut file| {
+        file.write_all(contents)?;
+        file.sync_all()
+    });
+    match result {
+        Ok(()) => Ok(temp),
+        Err(e) => {
+            let _ = fs::remove_file(&temp);
+            Err(e)
+        }
+    }
+}
+
+fn discard(staged: &[(PathBuf, &PathBuf)]) {
+    for (temp, _) in staged {
+        let _ = fs::remove_file(temp);
+    }
+}
+
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    File::open(dir)?.sync_all()
+}
+
+/// Directories cannot be opened for syncing here; the rename is still atomic
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[tokio::test]
+    async fn test_write_atomic_replaces_contents() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("metadata.json");
+
+        write_atomic(&path, "{}").await.unwrap();
+        write_atomic(&path, "{\"status\": \"open\"}").await.unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"status\": \"open\"}");
+        assert_eq!(file_names(temp_dir.path()), vec!["metadata.json"]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_staged_write_changes_nothing() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let issue_md = temp_dir.path().join("issue.md");
+        fs::write(&issue_md, "# Old\n").unwrap();
+
+        // The second file cannot be staged, so the first is not replaced either
+        let missing = temp_dir.path().join("missing").join("metadata.json");
+        let result = write_files_atomic(&[
+            (issue_md.as_path(), b"# New\n".as_slice()),
+            (missing.as_path(), b"{}".as_slice()),
+        ])
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&issue_md).unwrap(), "# Old\n");
+        assert_eq!(file_names(temp_dir.path()), vec!["issue.md"]);
+    }
+
+    #[test]
+    fn test_is_temp_file() {
+        assert!(is_temp_file(".metadata.json.123-0.tmp"));
+        assert!(!is_temp_file("metadata.json"));
+        assert!(!is_temp_file("notes.tmp"));
+    }
+}
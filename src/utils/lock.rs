@@ -0,0 +1,124 @@
+use super::get_centy_path;
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions, TryLockError};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+/// Lock file in `.centy/` that serializes writers across processes
+pub const LOCK_FILE: &str = ".lock";
+
+/// Exclusive right to write to a project, released on drop
+pub struct ProjectLock {
+    _file: File,
+    _guard: OwnedMutexGuard<()>,
+}
+
+static PROJECT_LOCKS: OnceLock<Mutex<HashMap<PathBuf, Arc<AsyncMutex<()>>>>> = OnceLock::new();
+
+/// The in-process lock for a project, shared by every spelling of its path
+fn process_lock(project_path: &Path) -> Arc<AsyncMutex<()>> {
+    let key = project_path
+        .canonicalize()
+        .unwrap_or_else(|_| project_path.to_path_buf());
+    let mut locks = PROJECT_LOCKS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    Arc::clone(locks.entry(key).or_default())
+}
+
+/// Wait until no other task in this daemon, and no other process, is writing
+/// to the project. Used where a value read from disk decides what is written,
+/// e.g. allocating the next display number.
+///
+/// Not reentrant: a task holding the lock must not ask for it again.
+pub async fn lock_project(project_path: &Path) -> Result<ProjectLock, std::io::Error> {
+    // Queue up inside the daemon first, so only one task blocks on the file
+    let guard = process_lock(project_path).lock_owned().await;
+
+    let lock_path = get_centy_path(project_path).join(LOCK_FILE);
+    let file = tokio::task::spawn_blocking(move || {
+        let file = open_lock_file(&lock_path)?;
+        file.lock()?;
+        Ok::<_, std::io::Error>(file)
+    })
+    .await
+    .map_err(std::io::Error::other)??;
+
+    Ok(ProjectLock {
+        _file: file,
+        _guard: guard,
+    })
+}
+
+/// Take the project lock only if nobody holds it right now, including the
+/// calling task. For work that can be put off until the project is idle,
+/// e.g. reconciling display numbers from a read path.
+pub async fn try_lock_project(project_path: &Path) -> Result<Option<ProjectLock>, std::io::Error> {
+    let Ok(guard) = process_lock(project_path).try_lock_owned() else {
+        return Ok(None);
+    };
+
+    let lock_path = get_centy_path(project_path).join(LOCK_FILE);
+    let file = tokio::task::spawn_blocking(move || {
+        let file = open_lock_file(&lock_path)?;
+        match file.try_lock() {
+            Ok(()) => Ok(Some(file)),
+            Err(TryLockError::WouldBlock) => Ok(None),
+            Err(TryLockError::Error(e)) => Err(e),
+        }
+    })
+    .await
+    .map_err(std::io::Error::other)??;
+
+    Ok(file.map(|file| ProjectLock {
+        _file: file,
+        _guard: guard,
+    }))
+}
+
+fn open_lock_file(lock_path: &Path) -> Result<File, std::io::Error> {
+    OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(lock_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_lock_is_exclusive() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(get_centy_path(temp_dir.path())).unwrap();
+
+        let held = lock_project(temp_dir.path()).await.unwrap();
+        let waiting = lock_project(temp_dir.path());
+        tokio::pin!(waiting);
+        assert!(tokio::time::timeout(Duration::from_millis(100), &mut waiting)
+            .await
+            .is_err());
+
+        drop(held);
+        tokio::time::timeout(Duration::from_secs(5), waiting)
+            .await
+            .expect("Lock should be released")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_try_lock_does_not_wait() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(get_centy_path(temp_dir.path())).unwrap();
+
+        let held = lock_project(temp_dir.path()).await.unwrap();
+        assert!(try_lock_project(temp_dir.path()).await.unwrap().is_none());
+
+        drop(held);
+        assert!(try_lock_project(temp_dir.path()).await.unwrap().is_some());
+    }
+}
@@ -1,6 +1,10 @@
+mod atomic;
 mod hash;
+mod lock;
 
+pub use atomic::{is_temp_file, write_atomic, write_files_atomic, TEMP_SUFFIX};
 pub use hash::{compute_hash, compute_file_hash};
+pub use lock::{lock_project, try_lock_project, ProjectLock, LOCK_FILE};
 
 use std::path::Path;
 
@@ -0,0 +1,354 @@
+use crate::docs::{list_docs, slugify, Doc, DocError};
+use crate::issue::{list_issues, Issue, IssueCrudError};
+use crate::manifest::{read_manifest, update_manifest_timestamp, write_manifest, CentyManifest};
+use crate::pr::{list_prs, PrCrudError, PullRequest};
+use crate::query::{
+    apply_list_query, validate_list_query, ListQuery, QueryContext, QueryError, Queryable,
+};
+use crate::utils::{get_centy_path, lock_project, now_iso, write_atomic};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use tokio::fs;
+
+#[derive(Error, Debug)]
+pub enum ViewError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Manifest error: {0}")]
+    ManifestError(#[from] crate::manifest::ManifestError),
+
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("Centy not initialized. Run 'centy init' first.")]
+    NotInitialized,
+
+    #[error("View '{0}' not found")]
+    ViewNotFound(String),
+
+    #[error("Title is required")]
+    TitleRequired,
+
+    #[error("Invalid slug: {0}")]
+    InvalidSlug(String),
+
+    #[error("Invalid view entity '{0}' (expected 'issues', 'prs' or 'docs')")]
+    InvalidEntity(String),
+
+    #[error("Invalid query: {0}")]
+    InvalidQuery(#[from] QueryError),
+
+    #[error("Unknown column '{0}'")]
+    UnknownColumn(String),
+
+    #[error("Issue error: {0}")]
+    IssueError(#[from] IssueCrudError),
+
+    #[error("PR error: {0}")]
+    PrError(#[from] PrCrudError),
+
+    #[error("Doc error: {0}")]
+    DocError(#[from] DocError),
+}
+
+/// What a view lists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ViewEntity {
+    #[default]
+    Issues,
+    Prs,
+    Docs,
+}
+
+impl ViewEntity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ViewEntity::Issues => "issues",
+            ViewEntity::Prs => "prs",
+            ViewEntity::Docs => "docs",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, ViewError> {
+        match value.trim().to_lowercase().as_str() {
+            "issues" => Ok(ViewEntity::Issues),
+            "prs" => Ok(ViewEntity::Prs),
+            "docs" => Ok(ViewEntity::Docs),
+            _ => Err(ViewError::InvalidEntity(value.to_string())),
+        }
+    }
+}
+
+/// A named query shared with everyone who clones the project.
+///
+/// Stored as `.centy/views/{slug}.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct View {
+    #[serde(skip)]
+    pub slug: String,
+    pub title: String,
+    #[serde(default)]
+    pub entity: ViewEntity,
+    /// Query string in the list query language
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub query: String,
+    /// Sort spec, e.g. `priority,-updated`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub sort: String,
+    /// Fields a client should show as columns, in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Options for saving a view
+#[derive(Debug, Clone, Default)]
+pub struct SaveViewOptions {
+    pub title: String,
+    /// Slug to save under. None = derived from the title.
+    pub slug: Option<String>,
+    pub entity: ViewEntity,
+    pub query: String,
+    pub sort: String,
+    pub columns: Vec<String>,
+}
+
+/// Result of saving a view
+#[derive(Debug, Clone)]
+pub struct SaveViewResult {
+    pub view: View,
+    /// Whether the view was new rather than replacing an existing one
+    pub created: bool,
+    pub manifest: CentyManifest,
+}
+
+/// The items a view lists
+#[derive(Debug, Clone)]
+pub enum ViewItems {
+    Issues(Vec<Issue>),
+    Prs(Vec<PullRequest>),
+    Docs(Vec<Doc>),
+}
+
+/// One page of a view's results
+#[derive(Debug, Clone)]
+pub struct RunViewResult {
+    pub view: View,
+    pub items: ViewItems,
+    /// Number of items matching across all pages
+    pub total_count: usize,
+    /// Token for the next page, or None on the last page
+    pub next_page_token: Option<String>,
+}
+
+fn views_path(project_path: &Path) -> PathBuf {
+    get_centy_path(project_path).join("views")
+}
+
+fn view_path(project_path: &Path, slug: &str) -> PathBuf {
+    views_path(project_path).join(format!("{}.json", slug))
+}
+
+fn view_exists(project_path: &Path, slug: &str) -> bool {
+    !slug.is_empty() && slugify(slug) == slug && view_path(project_path, slug).exists()
+}
+
+/// Create a view, or replace the view with the same slug
+pub async fn save_view(
+    project_path: &Path,
+    options: SaveViewOptions,
+) -> Result<SaveViewResult, ViewError> {
+    if options.title.trim().is_empty() {
+        return Err(ViewError::TitleRequired);
+    }
+
+    let mut manifest = read_manifest(project_path)
+        .await?
+        .ok_or(ViewError::NotInitialized)?;
+    let _lock = lock_project(project_path).await?;
+
+    let slug = match options.slug {
+        Some(s) if !s.trim().is_empty() => slugify(&s),
+        _ => slugify(&options.title),
+    };
+    if slug.is_empty() {
+        return Err(ViewError::InvalidSlug(
+            "Slug must contain at least one alphanumeric character".to_string(),
+        ));
+    }
+
+    let columns: Vec<String> = options
+        .columns
+        .iter()
+        .map(|c| c.trim().to_lowercase())
+        .filter(|c| !c.is_empty())
+        .collect();
+    match options.entity {
+        ViewEntity::Issues => validate::<Issue>(&options.query, &options.sort, &columns)?,
+        ViewEntity::Prs => validate::<PullRequest>(&options.query, &options.sort, &columns)?,
+        ViewEntity::Docs => validate::<Doc>(&options.query, &options.sort, &columns)?,
+    }
+
+    let existing = if view_exists(project_path, &slug) {
+        Some(read_view_from_disk(project_path, &slug).await?)
+    } else {
+        None
+    };
+    let now = now_iso();
+    let view = View {
+        slug: slug.clone(),
+        title: options.title.trim().to_string(),
+        entity: options.entity,
+        query: options.query.trim().to_string(),
+        sort: options.sort.trim().to_string(),
+        columns,
+        created_at: existing
+            .as_ref()
+            .map_or_else(|| now.clone(), |v| v.created_at.clone()),
+        updated_at: now,
+    };
+
+    fs::create_dir_all(views_path(project_path)).await?;
+    write_atomic(view_path(project_path, &slug), serde_json::to_string_pretty(&view)?).await?;
+
+    update_manifest_timestamp(&mut manifest);
+    write_manifest(project_path, &manifest).await?;
+
+    Ok(SaveViewResult {
+        view,
+        created: existing.is_none(),
+        manifest,
+    })
+}
+
+/// Get a single view by its slug
+pub async fn get_view(project_path: &Path, slug: &str) -> Result<View, ViewError> {
+    read_manifest(project_path)
+        .await?
+        .ok_or(ViewError::NotInitialized)?;
+
+    if !view_exists(project_path, slug) {
+        return Err(ViewError::ViewNotFound(slug.to_string()));
+    }
+
+    read_view_from_disk(project_path, slug).await
+}
+
+/// List all views, sorted by title
+pub async fn list_views(
+    project_path: &Path,
+    entity_filter: Option<ViewEntity>,
+) -> Result<Vec<View>, ViewError> {
+    read_manifest(project_path)
+        .await?
+        .ok_or(ViewError::NotInitialized)?;
+
+    let path = views_path(project_path);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut views = Vec::new();
+    let mut entries = fs::read_dir(&path).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let file_path = entry.path();
+        if file_path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let Some(slug) = file_path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        match read_view_from_disk(project_path, slug).await {
+            Ok(view) => views.push(view),
+            Err(_) => continue, // Skip views that can't be read
+        }
+    }
+
+    if let Some(entity) = entity_filter {
+        views.retain(|v| v.entity == entity);
+    }
+    views.sort_by(|a, b| (a.title.to_lowercase(), &a.slug).cmp(&(b.title.to_lowercase(), &b.slug)));
+
+    Ok(views)
+}
+
+/// Delete a view
+pub async fn delete_view(project_path: &Path, slug: &str) -> Result<CentyManifest, ViewError> {
+    let mut manifest = read_manifest(project_path)
+        .await?
+        .ok_or(ViewError::NotInitialized)?;
+    let _lock = lock_project(project_path).await?;
+
+    if !view_exists(project_path, slug) {
+        return Err(ViewError::ViewNotFound(slug.to_string()));
+    }
+
+    fs::remove_file(view_path(project_path, slug)).await?;
+
+    update_manifest_timestamp(&mut manifest);
+    write_manifest(project_path, &manifest).await?;
+
+    Ok(manifest)
+}
+
+/// Run a view's query. `me` resolves to whoever runs the view.
+pub async fn run_view(
+    project_path: &Path,
+    slug: &str,
+    page_size: u32,
+    page_token: String,
+) -> Result<RunViewResult, ViewError> {
+    let view = get_view(project_path, slug).await?;
+    let list_query = ListQuery {
+        query: view.query.clone(),
+        sort: view.sort.clone(),
+        page_size,
+        page_token,
+    };
+    let context = QueryContext::for_query(project_path, &list_query).await;
+
+    let (items, total_count, next_page_token) = match view.entity {
+        ViewEntity::Issues => {
+            let issues = list_issues(project_path, None, None).await?;
+            let page = apply_list_query(issues, &list_query, &context)?;
+            (ViewItems::Issues(page.items), page.total_count, page.next_page_token)
+        }
+        ViewEntity::Prs => {
+            let prs = list_prs(project_path, None, None, None, None).await?;
+            let page = apply_list_query(prs, &list_query, &context)?;
+            (ViewItems::Prs(page.items), page.total_count, page.next_page_token)
+        }
+        ViewEntity::Docs => {
+            let docs = list_docs(project_path).await?;
+            let page = apply_list_query(docs, &list_query, &context)?;
+            (ViewItems::Docs(page.items), page.total_count, page.next_page_token)
+        }
+    };
+
+    Ok(RunViewResult {
+        view,
+        items,
+        total_count,
+        next_page_token,
+    })
+}
+
+fn validate<T: Queryable>(query: &str, sort: &str, columns: &[String]) -> Result<(), ViewError> {
+    validate_list_query::<T>(query, sort)?;
+    match columns.iter().find(|c| !T::has_field(c)) {
+        Some(column) => Err(ViewError::UnknownColumn(column.clone())),
+        None => Ok(()),
+    }
+}
+
+async fn read_view_from_disk(project_path: &Path, slug: &str) -> Result<View, ViewError> {
+    let content = fs::read_to_string(view_path(project_path, slug)).await?;
+    let mut view: View = serde_json::from_str(&content)?;
+    view.slug = slug.to_string();
+    Ok(view)
+}
@@ -0,0 +1,6 @@
+mod crud;
+
+pub use crud::{
+    delete_view, get_view, list_views, run_view, save_view, RunViewResult, SaveViewOptions,
+    SaveViewResult, View, ViewEntity, ViewError, ViewItems,
+};
@@ -0,0 +1,158 @@
+mod common;
+
+use centy_daemon::issue::{
+    create_issue, get_issue, get_issue_by_display_number, list_issues, resolve_display_number,
+    CreateIssueOptions,
+};
+use centy_daemon::pr::{create_pr, get_pr, get_pr_by_display_number, list_prs, CreatePrOptions};
+use centy_daemon::utils::lock_project;
+use common::{create_test_dir, init_centy_project};
+use std::path::Path;
+use std::time::Duration;
+
+/// Overwrite the display number in a metadata file, as a merge from another
+/// branch would
+fn set_display_number(metadata_path: &Path, number: u32) {
+    let mut metadata: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(metadata_path).unwrap()).unwrap();
+    metadata["displayNumber"] = number.into();
+    std::fs::write(metadata_path, serde_json::to_string_pretty(&metadata).unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn test_old_issue_number_redirects_after_reconciliation() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let first = create_issue(
+        project_path,
+        CreateIssueOptions {
+            title: "Ours".to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should create issue")
+    .id;
+    let second = create_issue(
+        project_path,
+        CreateIssueOptions {
+            title: "Theirs".to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should create issue")
+    .id;
+
+    // Both branches created #1; merging them puts two #1s on disk
+    set_display_number(
+        &project_path.join(".centy/issues").join(&second).join("metadata.json"),
+        1,
+    );
+    list_issues(project_path, None, None).await.unwrap();
+
+    let moved = get_issue(project_path, &second).await.unwrap();
+    assert_eq!(moved.metadata.display_number, 2);
+    assert_eq!(moved.metadata.previous_display_numbers, vec![1]);
+
+    // #1 still belongs to the older issue, but the caller learns it is ambiguous
+    let lookup = resolve_display_number(project_path, 1).await.unwrap();
+    assert_eq!(lookup.issue.id, first);
+    assert!(!lookup.redirected);
+    let holders: Vec<_> = lookup.previous_holders.iter().map(|i| &i.id).collect();
+    assert_eq!(holders, vec![&second]);
+
+    // Once nobody holds #1 any more, it redirects to the renumbered issue
+    set_display_number(
+        &project_path.join(".centy/issues").join(&first).join("metadata.json"),
+        7,
+    );
+    // The edit reaches the cached issues through the watcher
+    let mut lookup = resolve_display_number(project_path, 1).await.unwrap();
+    for _ in 0..50 {
+        if lookup.issue.id != first {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        lookup = resolve_display_number(project_path, 1).await.unwrap();
+    }
+    assert_eq!(lookup.issue.id, second);
+    assert!(lookup.redirected);
+    assert!(lookup.previous_holders.is_empty());
+    assert_eq!(
+        get_issue_by_display_number(project_path, 1).await.unwrap().id,
+        second
+    );
+}
+
+#[tokio::test]
+async fn test_old_pr_number_finds_renumbered_pr() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let mut ids = Vec::new();
+    for title in ["Ours", "Theirs"] {
+        let pr = create_pr(
+            project_path,
+            CreatePrOptions {
+                title: title.to_string(),
+                source_branch: Some("feature".to_string()),
+                target_branch: Some("main".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Should create PR");
+        ids.push(pr.id);
+    }
+
+    let prs_path = project_path.join(".centy/prs");
+    set_display_number(&prs_path.join(&ids[1]).join("metadata.json"), 1);
+    // Looking up #1 reconciles first, so the older PR keeps it
+    assert_eq!(get_pr_by_display_number(project_path, 1).await.unwrap().id, ids[0]);
+
+    let moved = get_pr(project_path, &ids[1]).await.unwrap();
+    assert_eq!(moved.metadata.display_number, 2);
+    assert_eq!(moved.metadata.previous_display_numbers, vec![1]);
+
+    set_display_number(&prs_path.join(&ids[0]).join("metadata.json"), 7);
+    assert_eq!(get_pr_by_display_number(project_path, 1).await.unwrap().id, ids[1]);
+}
+
+#[tokio::test]
+async fn test_pr_reads_leave_duplicates_while_locked() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let mut ids = Vec::new();
+    for title in ["Ours", "Theirs"] {
+        let pr = create_pr(
+            project_path,
+            CreatePrOptions {
+                title: title.to_string(),
+                source_branch: Some("feature".to_string()),
+                target_branch: Some("main".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Should create PR");
+        ids.push(pr.id);
+    }
+    set_display_number(&project_path.join(".centy/prs").join(&ids[1]).join("metadata.json"), 1);
+
+    // A writer holds the lock: reads neither wait nor renumber
+    let lock = lock_project(project_path).await.unwrap();
+    let prs = list_prs(project_path, None, None, None, None).await.unwrap();
+    assert!(prs.iter().all(|pr| pr.metadata.display_number == 1));
+    assert_eq!(get_pr_by_display_number(project_path, 1).await.unwrap().id, ids[0]);
+
+    drop(lock);
+    let prs = list_prs(project_path, None, None, None, None).await.unwrap();
+    let numbers: Vec<_> = prs.iter().map(|pr| (pr.id.clone(), pr.metadata.display_number)).collect();
+    assert_eq!(numbers, vec![(ids[0].clone(), 1), (ids[1].clone(), 2)]);
+}
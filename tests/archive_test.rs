@@ -0,0 +1,205 @@
+mod common;
+
+use centy_daemon::issue::{
+    archive_issues, create_issue, get_issue, get_issue_by_display_number, get_issue_history,
+    list_issues_filtered, unarchive_issue, update_issue, CreateIssueOptions, IssueCrudError,
+    IssueFilter, UpdateIssueOptions,
+};
+use centy_daemon::milestone::{
+    create_milestone, delete_milestone, milestone_progress, CreateMilestoneOptions,
+};
+use common::{create_issue_with, create_test_dir, init_centy_project};
+use std::path::Path;
+
+async fn create(project_path: &Path, title: &str, status: &str) -> String {
+    create_issue_with(
+        project_path,
+        CreateIssueOptions {
+            title: title.to_string(),
+            status: Some(status.to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+/// Pretend the issue was last touched long ago
+fn backdate(project_path: &Path, issue_id: &str) {
+    let path = project_path
+        .join(".centy/issues")
+        .join(issue_id)
+        .join("metadata.json");
+    let mut metadata: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    metadata["updatedAt"] = "2020-01-01T00:00:00+00:00".into();
+    std::fs::write(&path, metadata.to_string()).unwrap();
+}
+
+async fn titles(project_path: &Path, include_archived: bool) -> Vec<String> {
+    let filter = IssueFilter {
+        include_archived,
+        ..Default::default()
+    };
+    list_issues_filtered(project_path, &filter)
+        .await
+        .expect("Should list issues")
+        .into_iter()
+        .map(|i| i.title)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_archive_old_closed_issues() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let old_closed = create(project_path, "Old closed", "closed").await;
+    let old_open = create(project_path, "Old open", "open").await;
+    create(project_path, "Recent closed", "closed").await;
+    backdate(project_path, &old_closed);
+    backdate(project_path, &old_open);
+
+    let result = archive_issues(project_path, Some(30))
+        .await
+        .expect("Should archive");
+    assert_eq!(result.archived, vec![old_closed.clone()]);
+    assert!(result.manifest.is_some());
+    assert!(project_path
+        .join(".centy/archive/issues")
+        .join(&old_closed)
+        .exists());
+
+    assert_eq!(titles(project_path, false).await, vec!["Old open", "Recent closed"]);
+    assert_eq!(
+        titles(project_path, true).await,
+        vec!["Old closed", "Old open", "Recent closed"]
+    );
+
+    // Archived issues stay reachable by ID and display number
+    let issue = get_issue_by_display_number(project_path, 1)
+        .await
+        .expect("Should resolve archived issue");
+    assert_eq!(issue.id, old_closed);
+    assert!(issue.metadata.archived);
+    assert!(get_issue(project_path, &old_closed).await.unwrap().metadata.archived);
+
+    let result = update_issue(
+        project_path,
+        &old_closed,
+        UpdateIssueOptions {
+            status: Some("open".to_string()),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(result, Err(IssueCrudError::IssueArchived(_))));
+}
+
+#[tokio::test]
+async fn test_archived_display_numbers_are_not_reused() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    create(project_path, "First", "open").await;
+    let last = create(project_path, "Last", "closed").await;
+    backdate(project_path, &last);
+    archive_issues(project_path, Some(1)).await.expect("Should archive");
+
+    let result = create_issue(
+        project_path,
+        CreateIssueOptions {
+            title: "New".to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should create issue");
+    assert_eq!(result.display_number, 3);
+}
+
+#[tokio::test]
+async fn test_unarchive_issue() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let id = create(project_path, "Done", "closed").await;
+    backdate(project_path, &id);
+
+    // Archiving is off unless configured or requested
+    let result = archive_issues(project_path, None).await.expect("Should run");
+    assert!(result.archived.is_empty());
+    assert!(result.manifest.is_none());
+
+    archive_issues(project_path, Some(7)).await.expect("Should archive");
+    assert!(titles(project_path, false).await.is_empty());
+
+    let result = unarchive_issue(project_path, &id)
+        .await
+        .expect("Should unarchive");
+    assert!(!result.issue.metadata.archived);
+    assert_eq!(titles(project_path, false).await, vec!["Done"]);
+
+    let result = unarchive_issue(project_path, &id).await;
+    assert!(matches!(result, Err(IssueCrudError::IssueNotFound(_))));
+
+    // Only archived issue folders can be moved back, not arbitrary paths
+    let result = unarchive_issue(project_path, &format!("../../issues/{}", id)).await;
+    assert!(matches!(result, Err(IssueCrudError::IssueNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_archived_issues_keep_milestone_and_history() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let slug = create_milestone(
+        project_path,
+        CreateMilestoneOptions {
+            title: "Release".to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should create milestone")
+    .milestone
+    .slug;
+    let done = create_issue_with(
+        project_path,
+        CreateIssueOptions {
+            title: "Done".to_string(),
+            status: Some("closed".to_string()),
+            milestone: Some(slug.clone()),
+            ..Default::default()
+        },
+    )
+    .await;
+    create_issue_with(
+        project_path,
+        CreateIssueOptions {
+            title: "Todo".to_string(),
+            milestone: Some(slug.clone()),
+            ..Default::default()
+        },
+    )
+    .await;
+    backdate(project_path, &done);
+    archive_issues(project_path, Some(30)).await.expect("Should archive");
+
+    // Archiving a closed issue doesn't change how far along the milestone is
+    let progress = milestone_progress(project_path, &slug).await.unwrap();
+    assert_eq!(progress.total_issues, 2);
+    assert_eq!(progress.completed_issues, 1);
+
+    let history = get_issue_history(project_path, &done)
+        .await
+        .expect("Should get history of archived issue");
+    assert!(!history.is_empty());
+
+    let result = delete_milestone(project_path, &slug).await.unwrap();
+    assert_eq!(result.unassigned_issues.len(), 2);
+    assert!(get_issue(project_path, &done).await.unwrap().metadata.milestone.is_none());
+}
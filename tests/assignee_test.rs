@@ -0,0 +1,200 @@
+mod common;
+
+use centy_daemon::config::{read_config, write_config, MemberDefinition};
+use centy_daemon::issue::{
+    create_issue, get_issue, list_issues_filtered, update_issue, AssigneeError, CreateIssueOptions,
+    IssueCrudError, IssueError, IssueFilter, UpdateIssueOptions,
+};
+use common::{create_issue_with, create_test_dir, init_centy_project};
+use std::path::Path;
+use std::process::Command;
+
+async fn set_members(project_path: &Path, assign_git_user_by_default: bool) {
+    let mut config = read_config(project_path)
+        .await
+        .expect("Should read config")
+        .unwrap_or_default();
+    config.members = vec![
+        MemberDefinition {
+            name: "Alice".to_string(),
+            email: Some("alice@example.com".to_string()),
+            handle: "alice".to_string(),
+        },
+        MemberDefinition {
+            name: "Bob".to_string(),
+            email: Some("bob@example.com".to_string()),
+            handle: "bob".to_string(),
+        },
+    ];
+    config.assign_git_user_by_default = assign_git_user_by_default;
+    write_config(project_path, &config)
+        .await
+        .expect("Should write config");
+}
+
+async fn create_assigned_issue(project_path: &Path, title: &str, assignees: &[&str]) -> String {
+    create_issue_with(
+        project_path,
+        CreateIssueOptions {
+            title: title.to_string(),
+            assignees: assignees.iter().map(|a| a.to_string()).collect(),
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+fn git(project_path: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(args)
+        .current_dir(project_path)
+        .status()
+        .expect("Should run git");
+    assert!(status.success(), "git {:?} should succeed", args);
+}
+
+#[tokio::test]
+async fn test_create_issue_resolves_assignees_to_handles() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    set_members(project_path, false).await;
+
+    let id = create_assigned_issue(project_path, "Issue", &["alice", "BOB@example.com"]).await;
+
+    let issue = get_issue(project_path, &id).await.expect("Should get issue");
+    assert_eq!(issue.metadata.assignees, vec!["alice", "bob"]);
+}
+
+#[tokio::test]
+async fn test_create_issue_rejects_non_member() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    set_members(project_path, false).await;
+
+    let result = create_issue(
+        project_path,
+        CreateIssueOptions {
+            title: "Issue".to_string(),
+            assignees: vec!["carol".to_string()],
+            ..Default::default()
+        },
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(IssueError::AssigneeError(AssigneeError::UnknownMember(_)))
+    ));
+}
+
+#[tokio::test]
+async fn test_update_issue_assignees() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    set_members(project_path, false).await;
+
+    let id = create_assigned_issue(project_path, "Issue", &["alice"]).await;
+
+    let result = update_issue(
+        project_path,
+        &id,
+        UpdateIssueOptions {
+            assignees: Some(vec!["bob".to_string()]),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should update");
+    assert_eq!(result.issue.metadata.assignees, vec!["bob"]);
+
+    let result = update_issue(
+        project_path,
+        &id,
+        UpdateIssueOptions {
+            assignees: Some(vec!["carol".to_string()]),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(result, Err(IssueCrudError::AssigneeError(_))));
+
+    let result = update_issue(
+        project_path,
+        &id,
+        UpdateIssueOptions {
+            assignees: Some(Vec::new()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should update");
+    assert!(result.issue.metadata.assignees.is_empty());
+}
+
+#[tokio::test]
+async fn test_list_issues_filtered_by_assignee() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    set_members(project_path, false).await;
+
+    create_assigned_issue(project_path, "Alice's", &["alice"]).await;
+    create_assigned_issue(project_path, "Shared", &["alice", "bob"]).await;
+    create_assigned_issue(project_path, "Unassigned", &[]).await;
+
+    let filter = IssueFilter {
+        assignee: Some("alice".to_string()),
+        ..Default::default()
+    };
+    let issues = list_issues_filtered(project_path, &filter)
+        .await
+        .expect("Should list");
+    assert_eq!(issues.len(), 2);
+
+    // Filtering by email resolves to the member handle
+    let filter = IssueFilter {
+        assignee: Some("bob@example.com".to_string()),
+        ..Default::default()
+    };
+    let issues = list_issues_filtered(project_path, &filter)
+        .await
+        .expect("Should list");
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].title, "Shared");
+}
+
+#[tokio::test]
+async fn test_create_issue_defaults_to_git_user() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    git(project_path, &["init", "-q"]);
+    git(project_path, &["config", "user.email", "alice@example.com"]);
+    init_centy_project(project_path).await;
+    set_members(project_path, true).await;
+
+    let id = create_assigned_issue(project_path, "Mine", &[]).await;
+    let issue = get_issue(project_path, &id).await.expect("Should get issue");
+    assert_eq!(issue.metadata.assignees, vec!["alice"]);
+
+    // Explicit assignees win over the git default
+    let id = create_assigned_issue(project_path, "Bob's", &["bob"]).await;
+    let issue = get_issue(project_path, &id).await.expect("Should get issue");
+    assert_eq!(issue.metadata.assignees, vec!["bob"]);
+}
+
+#[tokio::test]
+async fn test_git_default_disabled_leaves_issue_unassigned() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    git(project_path, &["init", "-q"]);
+    git(project_path, &["config", "user.email", "alice@example.com"]);
+    init_centy_project(project_path).await;
+    set_members(project_path, false).await;
+
+    let id = create_assigned_issue(project_path, "Nobody's", &[]).await;
+    let issue = get_issue(project_path, &id).await.expect("Should get issue");
+    assert!(issue.metadata.assignees.is_empty());
+}
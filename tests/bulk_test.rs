@@ -0,0 +1,172 @@
+mod common;
+
+use centy_daemon::issue::{
+    bulk_delete_issues, bulk_update_issues, get_issue, list_issues_filtered, BulkIssuePatch,
+    BulkTarget, CreateIssueOptions, IssueFilter,
+};
+use centy_daemon::manifest::read_manifest;
+use centy_daemon::query::ListQuery;
+use common::{create_issue_with, create_test_dir, init_centy_project};
+use std::path::Path;
+
+async fn create(project_path: &Path, title: &str, labels: &[&str]) -> String {
+    create_issue_with(
+        project_path,
+        CreateIssueOptions {
+            title: title.to_string(),
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_bulk_update_by_filter() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let stale_a = create(project_path, "Stale A", &["stale", "bug"]).await;
+    let stale_b = create(project_path, "Stale B", &["stale"]).await;
+    let fresh = create(project_path, "Fresh", &["bug"]).await;
+
+    let target = BulkTarget::Filter(IssueFilter {
+        labels: vec!["stale".to_string()],
+        ..Default::default()
+    });
+    let patch = BulkIssuePatch {
+        status: Some("closed".to_string()),
+        priority: Some(3),
+        add_labels: vec!["wontfix".to_string()],
+        remove_labels: vec!["stale".to_string()],
+        ..Default::default()
+    };
+    let result = bulk_update_issues(project_path, target, patch)
+        .await
+        .expect("Should bulk update");
+    assert_eq!(result.succeeded_count(), 2);
+    assert_eq!(result.failed_count(), 0);
+    assert!(result.manifest.is_some());
+
+    for id in [&stale_a, &stale_b] {
+        let issue = get_issue(project_path, id).await.expect("Should get issue");
+        assert_eq!(issue.metadata.status, "closed");
+        assert_eq!(issue.metadata.priority, 3);
+        assert!(issue.metadata.labels.contains(&"wontfix".to_string()));
+        assert!(!issue.metadata.labels.contains(&"stale".to_string()));
+    }
+    let issue = get_issue(project_path, &stale_a).await.expect("Should get issue");
+    assert!(issue.metadata.labels.contains(&"bug".to_string()));
+
+    let untouched = get_issue(project_path, &fresh).await.expect("Should get issue");
+    assert_eq!(untouched.metadata.status, "open");
+}
+
+#[tokio::test]
+async fn test_bulk_update_reports_per_item_failures() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let id = create(project_path, "Issue", &[]).await;
+    let before = read_manifest(project_path).await.unwrap().unwrap();
+
+    // Every item fails: the manifest is left alone
+    let result = bulk_update_issues(
+        project_path,
+        BulkTarget::Ids(vec![id.clone()]),
+        BulkIssuePatch {
+            priority: Some(99),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should run bulk update");
+    assert_eq!(result.failed_count(), 1);
+    assert!(result.manifest.is_none());
+    let after = read_manifest(project_path).await.unwrap().unwrap();
+    assert_eq!(before.updated_at, after.updated_at);
+
+    let result = bulk_update_issues(
+        project_path,
+        BulkTarget::Ids(vec![id.clone(), "missing".to_string()]),
+        BulkIssuePatch {
+            status: Some("in-progress".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should run bulk update");
+    assert_eq!(result.succeeded_count(), 1);
+    assert_eq!(result.items[0].issue_id, id);
+    assert!(result.items[0].error.is_none());
+    assert_eq!(result.items[1].issue_id, "missing");
+    assert!(result.items[1].error.is_some());
+}
+
+#[tokio::test]
+async fn test_bulk_delete() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let a = create(project_path, "A", &[]).await;
+    let b = create(project_path, "B", &[]).await;
+    create(project_path, "C", &[]).await;
+
+    let result = bulk_delete_issues(project_path, BulkTarget::Ids(vec![a, b, "missing".to_string()]))
+        .await
+        .expect("Should bulk delete");
+    assert_eq!(result.succeeded_count(), 2);
+    assert_eq!(result.failed_count(), 1);
+
+    let remaining = list_issues_filtered(project_path, &IssueFilter::default())
+        .await
+        .expect("Should list issues");
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].title, "C");
+}
+
+#[tokio::test]
+async fn test_bulk_delete_by_query_only() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let stale = create(project_path, "Stale", &["stale"]).await;
+    create(project_path, "Fresh", &["bug"]).await;
+
+    let target = BulkTarget::Query(
+        IssueFilter::default(),
+        ListQuery {
+            query: "label:stale".to_string(),
+            ..Default::default()
+        },
+    );
+    let result = bulk_delete_issues(project_path, target)
+        .await
+        .expect("Should bulk delete");
+    assert_eq!(result.succeeded_count(), 1);
+    assert_eq!(result.items[0].issue_id, stale);
+
+    let remaining = list_issues_filtered(project_path, &IssueFilter::default())
+        .await
+        .expect("Should list issues");
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].title, "Fresh");
+
+    // An invalid query fails the whole operation rather than matching everything
+    let target = BulkTarget::Query(
+        IssueFilter::default(),
+        ListQuery {
+            query: "nosuchfield:x".to_string(),
+            ..Default::default()
+        },
+    );
+    assert!(bulk_delete_issues(project_path, target).await.is_err());
+    let remaining = list_issues_filtered(project_path, &IssueFilter::default())
+        .await
+        .expect("Should list issues");
+    assert_eq!(remaining.len(), 1);
+}
@@ -0,0 +1,189 @@
+mod common;
+
+use centy_daemon::issue::{
+    add_comment, delete_comment, list_comments, update_comment, update_issue, AddCommentOptions,
+    CommentError, CreateIssueOptions, UpdateIssueOptions,
+};
+use common::{create_issue_with, create_test_dir, init_centy_project};
+use std::path::Path;
+
+async fn create_test_issue(project_path: &Path) -> String {
+    create_issue_with(
+        project_path,
+        CreateIssueOptions {
+            title: "Issue with discussion".to_string(),
+            description: "Original description".to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+fn comment(author: &str, body: &str, reply_to: Option<&str>) -> AddCommentOptions {
+    AddCommentOptions {
+        author: author.to_string(),
+        body: body.to_string(),
+        reply_to: reply_to.map(String::from),
+    }
+}
+
+#[tokio::test]
+async fn test_add_and_list_comments() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    let issue_id = create_test_issue(project_path).await;
+
+    let first = add_comment(project_path, &issue_id, comment("alice", "First **thought**", None))
+        .await
+        .expect("Should add comment");
+    let reply = add_comment(
+        project_path,
+        &issue_id,
+        comment("bob", "Agreed", Some(&first.comment.id)),
+    )
+    .await
+    .expect("Should add reply");
+
+    let comment_file = project_path
+        .join(".centy/issues")
+        .join(&issue_id)
+        .join("comments")
+        .join(format!("{}.json", first.comment.id));
+    assert!(comment_file.exists(), "Comment file should exist");
+
+    let comments = list_comments(project_path, &issue_id)
+        .await
+        .expect("Should list comments");
+    assert_eq!(comments.len(), 2);
+    assert_eq!(comments[0].id, first.comment.id);
+    assert_eq!(comments[0].author, "alice");
+    assert_eq!(comments[0].body, "First **thought**");
+    assert!(comments[0].reply_to.is_none());
+    assert_eq!(comments[1].id, reply.comment.id);
+    assert_eq!(comments[1].reply_to.as_deref(), Some(first.comment.id.as_str()));
+}
+
+#[tokio::test]
+async fn test_comments_do_not_touch_issue_description() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    let issue_id = create_test_issue(project_path).await;
+
+    add_comment(project_path, &issue_id, comment("alice", "A comment", None))
+        .await
+        .expect("Should add comment");
+
+    let result = update_issue(
+        project_path,
+        &issue_id,
+        UpdateIssueOptions {
+            description: Some("New description".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should update issue");
+    assert_eq!(result.issue.description, "New description");
+
+    let comments = list_comments(project_path, &issue_id)
+        .await
+        .expect("Should list comments");
+    assert_eq!(comments.len(), 1);
+}
+
+#[tokio::test]
+async fn test_add_comment_validation() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    let issue_id = create_test_issue(project_path).await;
+
+    let result = add_comment(project_path, &issue_id, comment("", "Body", None)).await;
+    assert!(matches!(result, Err(CommentError::AuthorRequired)));
+
+    let result = add_comment(project_path, &issue_id, comment("alice", "  ", None)).await;
+    assert!(matches!(result, Err(CommentError::BodyRequired)));
+
+    let result = add_comment(
+        project_path,
+        &issue_id,
+        comment("alice", "Body", Some("550e8400-e29b-41d4-a716-446655440000")),
+    )
+    .await;
+    assert!(matches!(result, Err(CommentError::ReplyTargetNotFound(_))));
+
+    let result = add_comment(
+        project_path,
+        "550e8400-e29b-41d4-a716-446655440000",
+        comment("alice", "Body", None),
+    )
+    .await;
+    assert!(matches!(result, Err(CommentError::IssueNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_update_comment() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    let issue_id = create_test_issue(project_path).await;
+
+    let added = add_comment(project_path, &issue_id, comment("alice", "Typo", None))
+        .await
+        .expect("Should add comment");
+
+    let updated = update_comment(project_path, &issue_id, &added.comment.id, "Fixed".to_string())
+        .await
+        .expect("Should update comment");
+
+    assert_eq!(updated.comment.body, "Fixed");
+    assert_eq!(updated.comment.author, "alice");
+    assert_eq!(updated.comment.created_at, added.comment.created_at);
+
+    let result = update_comment(
+        project_path,
+        &issue_id,
+        "550e8400-e29b-41d4-a716-446655440000",
+        "Body".to_string(),
+    )
+    .await;
+    assert!(matches!(result, Err(CommentError::CommentNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_delete_comment_removes_replies() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    let issue_id = create_test_issue(project_path).await;
+
+    let root = add_comment(project_path, &issue_id, comment("alice", "Root", None))
+        .await
+        .expect("Should add comment");
+    let reply = add_comment(
+        project_path,
+        &issue_id,
+        comment("bob", "Reply", Some(&root.comment.id)),
+    )
+    .await
+    .expect("Should add reply");
+    let other = add_comment(project_path, &issue_id, comment("carol", "Other", None))
+        .await
+        .expect("Should add comment");
+
+    let result = delete_comment(project_path, &issue_id, &root.comment.id)
+        .await
+        .expect("Should delete comment");
+
+    assert_eq!(result.deleted_ids.len(), 2);
+    assert!(result.deleted_ids.contains(&root.comment.id));
+    assert!(result.deleted_ids.contains(&reply.comment.id));
+
+    let comments = list_comments(project_path, &issue_id)
+        .await
+        .expect("Should list comments");
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0].id, other.comment.id);
+}
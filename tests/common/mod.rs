@@ -1,3 +1,7 @@
+// Each test binary includes this module and uses only some of the helpers
+#![allow(dead_code)]
+
+use centy_daemon::issue::{create_issue, CreateIssueOptions};
 use std::path::Path;
 use tempfile::TempDir;
 
@@ -29,3 +33,23 @@ pub fn verify_centy_structure(project_path: &Path) {
     assert!(centy_path.join("assets").exists(), "assets/ should exist");
     assert!(centy_path.join("README.md").exists(), "README.md should exist");
 }
+
+/// Create an issue and return its ID
+pub async fn create_issue_with(project_path: &Path, options: CreateIssueOptions) -> String {
+    create_issue(project_path, options)
+        .await
+        .expect("Should create issue")
+        .id
+}
+
+/// Create an issue with only a title and return its ID
+pub async fn create_titled_issue(project_path: &Path, title: &str) -> String {
+    create_issue_with(
+        project_path,
+        CreateIssueOptions {
+            title: title.to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+}
@@ -0,0 +1,268 @@
+mod common;
+
+use centy_daemon::config::{config_etag, read_config, ConfigError};
+use centy_daemon::docs::{create_doc, update_doc, CreateDocOptions, DocError, UpdateDocOptions};
+use centy_daemon::issue::{
+    create_issue, get_issue, link_issues, update_config, update_issue, ConfigUpdateError,
+    CreateIssueOptions, IssueCrudError, RelationKind, UpdateIssueOptions,
+};
+use centy_daemon::pr::{create_pr, get_pr, update_pr, CreatePrOptions, PrCrudError, UpdatePrOptions};
+use common::{create_test_dir, init_centy_project};
+
+#[tokio::test]
+async fn test_stale_issue_update_is_rejected() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let id = create_issue(
+        project_path,
+        CreateIssueOptions {
+            title: "Shared".to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should create issue")
+    .id;
+    let seen = get_issue(project_path, &id).await.unwrap().metadata.updated_at;
+
+    // The CLI saves first...
+    let saved = update_issue(
+        project_path,
+        &id,
+        UpdateIssueOptions {
+            title: Some("From the CLI".to_string()),
+            expected_updated_at: Some(seen.clone()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("First update should apply");
+
+    // ...so the desktop app's edit, based on the same version, conflicts
+    let result = update_issue(
+        project_path,
+        &id,
+        UpdateIssueOptions {
+            status: Some("closed".to_string()),
+            expected_updated_at: Some(seen),
+            ..Default::default()
+        },
+    )
+    .await;
+    match result {
+        Err(IssueCrudError::Conflict(current)) => {
+            assert_eq!(current.title, "From the CLI");
+            assert_eq!(current.metadata.updated_at, saved.issue.metadata.updated_at);
+        }
+        other => panic!("Expected a conflict, got {:?}", other),
+    }
+    assert_eq!(get_issue(project_path, &id).await.unwrap().metadata.status, "open");
+
+    // Without a precondition the update still goes through
+    update_issue(
+        project_path,
+        &id,
+        UpdateIssueOptions {
+            status: Some("closed".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Unchecked update should apply");
+}
+
+#[tokio::test]
+async fn test_stale_doc_and_pr_updates_are_rejected() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let doc = create_doc(
+        project_path,
+        CreateDocOptions {
+            title: "Guide".to_string(),
+            content: "v1".to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should create doc");
+    let result = update_doc(
+        project_path,
+        &doc.slug,
+        UpdateDocOptions {
+            content: Some("v2".to_string()),
+            expected_updated_at: Some("2000-01-01T00:00:00+00:00".to_string()),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(result, Err(DocError::Conflict(current)) if current.content == "v1"));
+
+    let pr_id = create_pr(
+        project_path,
+        CreatePrOptions {
+            title: "Add login".to_string(),
+            source_branch: Some("feature/login".to_string()),
+            target_branch: Some("main".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should create PR")
+    .id;
+    let seen = get_pr(project_path, &pr_id).await.unwrap().metadata.updated_at;
+    let update = |title: &str, expected: &str| {
+        update_pr(
+            project_path,
+            &pr_id,
+            UpdatePrOptions {
+                title: Some(title.to_string()),
+                expected_updated_at: Some(expected.to_string()),
+                ..Default::default()
+            },
+        )
+    };
+    update("Add login page", &seen).await.expect("Should apply");
+    let result = update("Add login form", &seen).await;
+    assert!(matches!(result, Err(PrCrudError::Conflict(current)) if current.title == "Add login page"));
+}
+
+#[tokio::test]
+async fn test_stale_config_update_is_rejected() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let etag = config_etag(project_path).await.unwrap();
+    let mut config = read_config(project_path).await.unwrap().unwrap_or_default();
+
+    config.priority_levels = 5;
+    let new_etag = update_config(project_path, &config, Some(&etag), false)
+        .await
+        .expect("Should write config")
+        .etag;
+    assert_ne!(new_etag, etag);
+    assert_eq!(new_etag, config_etag(project_path).await.unwrap());
+
+    config.priority_levels = 4;
+    let result = update_config(project_path, &config, Some(&etag), false).await;
+    match result {
+        Err(ConfigUpdateError::ConfigError(ConfigError::Conflict { current, etag })) => {
+            assert_eq!(current.priority_levels, 5);
+            assert_eq!(etag, new_etag);
+        }
+        other => panic!("Expected a conflict, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_concurrent_creates_get_unique_numbers() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path().to_path_buf();
+    init_centy_project(&project_path).await;
+
+    let mut tasks = Vec::new();
+    for i in 0..8 {
+        let project_path = project_path.clone();
+        tasks.push(tokio::spawn(async move {
+            let issue = create_issue(
+                &project_path,
+                CreateIssueOptions {
+                    title: format!("Issue {}", i),
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("Should create issue");
+            let pr = create_pr(
+                &project_path,
+                CreatePrOptions {
+                    title: format!("PR {}", i),
+                    source_branch: Some("feature".to_string()),
+                    target_branch: Some("main".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("Should create PR");
+            (issue.display_number, pr.display_number)
+        }));
+    }
+
+    // Numbers are unique as handed out, not only after reconciliation
+    let mut issue_numbers = Vec::new();
+    let mut pr_numbers = Vec::new();
+    for task in tasks {
+        let (issue, pr) = task.await.unwrap();
+        issue_numbers.push(issue);
+        pr_numbers.push(pr);
+    }
+    issue_numbers.sort_unstable();
+    pr_numbers.sort_unstable();
+    assert_eq!(issue_numbers, (1..=8).collect::<Vec<u32>>());
+    assert_eq!(pr_numbers, (1..=8).collect::<Vec<u32>>());
+    assert!(project_path.join(".centy/.lock").exists());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_link_and_update_keep_both() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path().to_path_buf();
+    init_centy_project(&project_path).await;
+
+    let create = |title: &str| {
+        let project_path = project_path.clone();
+        let title = title.to_string();
+        async move {
+            create_issue(
+                &project_path,
+                CreateIssueOptions {
+                    title,
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("Should create issue")
+            .id
+        }
+    };
+    let hub = create("Hub").await;
+    let mut others = Vec::new();
+    for i in 0..8 {
+        others.push(create(&format!("Other {}", i)).await);
+    }
+
+    // Each update rewrites the hub's metadata.json while links are added to it
+    let mut tasks = Vec::new();
+    for (i, other) in others.iter().enumerate() {
+        let (path, hub_id, other) = (project_path.clone(), hub.clone(), other.clone());
+        tasks.push(tokio::spawn(async move {
+            link_issues(&path, &hub_id, &other, RelationKind::RelatesTo)
+                .await
+                .expect("Should link");
+        }));
+        let (path, hub_id) = (project_path.clone(), hub.clone());
+        tasks.push(tokio::spawn(async move {
+            update_issue(
+                &path,
+                &hub_id,
+                UpdateIssueOptions {
+                    description: Some(format!("Revision {}", i)),
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("Should update issue");
+        }));
+    }
+    for task in tasks {
+        task.await.unwrap();
+    }
+
+    let issue = get_issue(&project_path, &hub).await.unwrap();
+    assert_eq!(issue.metadata.relations.len(), 8);
+    assert!(issue.description.starts_with("Revision"));
+}
@@ -0,0 +1,182 @@
+mod common;
+
+use centy_daemon::config::{read_config, write_config, CustomFieldDefinition};
+use centy_daemon::issue::{
+    create_issue, get_issue, list_issues_filtered, update_issue, CreateIssueOptions,
+    CustomFieldFilter, FilterOp, IssueCrudError, IssueError, IssueFilter, UpdateIssueOptions,
+};
+use common::{create_test_dir, init_centy_project};
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::Path;
+
+fn field(name: &str, field_type: &str) -> CustomFieldDefinition {
+    CustomFieldDefinition {
+        name: name.to_string(),
+        field_type: field_type.to_string(),
+        required: false,
+        default_value: None,
+        enum_values: Vec::new(),
+    }
+}
+
+async fn set_custom_fields(project_path: &Path) {
+    let mut config = read_config(project_path)
+        .await
+        .expect("Should read config")
+        .unwrap_or_default();
+
+    let mut component = field("component", "multi-enum");
+    component.enum_values = vec!["api".to_string(), "ui".to_string(), "cli".to_string()];
+    let mut team = field("team", "string");
+    team.required = true;
+    team.default_value = Some("core".to_string());
+
+    config.custom_fields = vec![
+        field("estimate", "number"),
+        field("billable", "boolean"),
+        field("due", "date"),
+        component,
+        team,
+    ];
+    write_config(project_path, &config)
+        .await
+        .expect("Should write config");
+}
+
+async fn create(project_path: &Path, fields: &[(&str, &str)]) -> Result<String, IssueError> {
+    create_issue(
+        project_path,
+        CreateIssueOptions {
+            title: "Issue".to_string(),
+            custom_fields: fields
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            ..Default::default()
+        },
+    )
+    .await
+    .map(|r| r.id)
+}
+
+#[tokio::test]
+async fn test_custom_fields_are_stored_typed() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    set_custom_fields(project_path).await;
+
+    let id = create(
+        project_path,
+        &[("estimate", "5"), ("billable", "yes"), ("due", "2024-06-01"), ("component", "ui, api")],
+    )
+    .await
+    .expect("Should create issue");
+
+    let issue = get_issue(project_path, &id).await.expect("Should get issue");
+    let values = &issue.metadata.custom_field_values;
+    assert_eq!(values["estimate"], json!(5));
+    assert_eq!(values["billable"], json!(true));
+    assert_eq!(values["due"], json!("2024-06-01"));
+    assert_eq!(values["component"], json!(["ui", "api"]));
+    assert_eq!(values["team"], json!("core"));
+    assert_eq!(issue.metadata.custom_fields["component"], "ui, api");
+
+    let metadata = std::fs::read_to_string(
+        project_path.join(".centy/issues").join(&id).join("metadata.json"),
+    )
+    .expect("Should read metadata");
+    assert!(metadata.contains("\"estimate\": 5"));
+}
+
+#[tokio::test]
+async fn test_invalid_values_report_each_field() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    set_custom_fields(project_path).await;
+
+    let result = create(
+        project_path,
+        &[("estimate", "soon"), ("component", "docs"), ("team", "")],
+    )
+    .await;
+    let Err(IssueError::CustomFieldError(err)) = result else {
+        panic!("Expected custom field error, got {result:?}");
+    };
+    let fields: Vec<_> = err.field_errors().iter().map(|e| e.field.as_str()).collect();
+    assert_eq!(fields, vec!["component", "estimate", "team"]);
+}
+
+#[tokio::test]
+async fn test_update_validates_and_preserves_types() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    set_custom_fields(project_path).await;
+
+    let id = create(project_path, &[("estimate", "3")])
+        .await
+        .expect("Should create issue");
+
+    let result = update_issue(
+        project_path,
+        &id,
+        UpdateIssueOptions {
+            custom_fields: HashMap::from([("billable".to_string(), "maybe".to_string())]),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(result, Err(IssueCrudError::CustomFieldError(_))));
+
+    let result = update_issue(
+        project_path,
+        &id,
+        UpdateIssueOptions {
+            title: Some("Renamed".to_string()),
+            custom_fields: HashMap::from([("billable".to_string(), "false".to_string())]),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should update issue");
+    let values = &result.issue.metadata.custom_field_values;
+    assert_eq!(values["estimate"], json!(3));
+    assert_eq!(values["billable"], json!(false));
+}
+
+#[tokio::test]
+async fn test_filter_on_numeric_field() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    set_custom_fields(project_path).await;
+
+    for estimate in ["2", "8", "13"] {
+        create(project_path, &[("estimate", estimate)])
+            .await
+            .expect("Should create issue");
+    }
+    create(project_path, &[]).await.expect("Should create issue");
+
+    let filter = IssueFilter {
+        custom_fields: vec![CustomFieldFilter {
+            field: "estimate".to_string(),
+            op: FilterOp::Ge,
+            value: "8".to_string(),
+        }],
+        ..Default::default()
+    };
+    let issues = list_issues_filtered(project_path, &filter)
+        .await
+        .expect("Should list issues");
+    let mut estimates: Vec<_> = issues
+        .iter()
+        .map(|i| i.metadata.custom_fields["estimate"].clone())
+        .collect();
+    estimates.sort();
+    // Numeric, not lexicographic: "13" >= "8"
+    assert_eq!(estimates, vec!["13", "8"]);
+}
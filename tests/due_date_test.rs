@@ -0,0 +1,156 @@
+mod common;
+
+use centy_daemon::calendar::export_calendar;
+use centy_daemon::issue::{
+    create_issue, format_date, list_issues_filtered, today, update_issue, CreateIssueOptions,
+    IssueCrudError, IssueError, IssueFilter, UpdateIssueOptions,
+};
+use chrono::{Duration, NaiveDate};
+use common::{create_issue_with, create_test_dir, init_centy_project};
+use std::path::Path;
+
+async fn create(project_path: &Path, title: &str, start: Option<&str>, due: Option<&str>) -> String {
+    create_issue_with(
+        project_path,
+        CreateIssueOptions {
+            title: title.to_string(),
+            start_date: start.map(str::to_string),
+            due_date: due.map(str::to_string),
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+async fn titles(project_path: &Path, filter: IssueFilter) -> Vec<String> {
+    let mut titles: Vec<_> = list_issues_filtered(project_path, &filter)
+        .await
+        .expect("Should list issues")
+        .into_iter()
+        .map(|i| i.title)
+        .collect();
+    titles.sort();
+    titles
+}
+
+#[tokio::test]
+async fn test_dates_are_validated_and_updatable() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let result = create_issue(
+        project_path,
+        CreateIssueOptions {
+            title: "Bad".to_string(),
+            due_date: Some("next friday".to_string()),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(result, Err(IssueError::InvalidDate(_))));
+
+    let id = create(project_path, "Issue", Some("2024-05-01"), Some("2024-05-10")).await;
+
+    // Moving the due date before the start date is rejected
+    let result = update_issue(
+        project_path,
+        &id,
+        UpdateIssueOptions {
+            due_date: Some("2024-04-30".to_string()),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(result, Err(IssueCrudError::InvalidDate(_))));
+
+    let result = update_issue(
+        project_path,
+        &id,
+        UpdateIssueOptions {
+            start_date: Some(String::new()),
+            due_date: Some("2024-04-30".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should update dates");
+    assert_eq!(result.issue.metadata.start_date, None);
+    assert_eq!(result.issue.metadata.due_date.as_deref(), Some("2024-04-30"));
+}
+
+#[tokio::test]
+async fn test_due_range_and_overdue_filters() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let yesterday = format_date(today() - Duration::days(1));
+    let tomorrow = format_date(today() + Duration::days(1));
+
+    create(project_path, "late", None, Some(&yesterday)).await;
+    create(project_path, "soon", None, Some(&tomorrow)).await;
+    create(project_path, "undated", None, None).await;
+    let done = create(project_path, "done", None, Some(&yesterday)).await;
+    update_issue(
+        project_path,
+        &done,
+        UpdateIssueOptions {
+            status: Some("closed".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should close issue");
+
+    let overdue = IssueFilter {
+        overdue: true,
+        ..Default::default()
+    };
+    assert_eq!(titles(project_path, overdue).await, vec!["late"]);
+
+    let due_today_or_later = IssueFilter {
+        due_after: Some(today()),
+        ..Default::default()
+    };
+    assert_eq!(titles(project_path, due_today_or_later).await, vec!["soon"]);
+
+    let due_by_today = IssueFilter {
+        due_before: Some(today()),
+        ..Default::default()
+    };
+    assert_eq!(titles(project_path, due_by_today).await, vec!["done", "late"]);
+}
+
+#[tokio::test]
+async fn test_export_calendar() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let id = create(project_path, "Ship, finally", Some("2024-05-01"), Some("2024-05-10")).await;
+    create(project_path, "Someday", None, None).await;
+    let kickoff = create(project_path, "Kickoff", Some("2024-06-03"), None).await;
+
+    let export = export_calendar(project_path).await.expect("Should export");
+    assert_eq!(export.event_count, 2);
+
+    let ics = export.ics;
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    assert!(ics.contains(&format!("UID:issue-{}@centy", id)));
+    assert!(ics.contains("DTSTART;VALUE=DATE:20240501\r\n"));
+    // All-day DTEND is exclusive
+    let end = NaiveDate::from_ymd_opt(2024, 5, 11).unwrap();
+    assert!(ics.contains(&format!("DTEND;VALUE=DATE:{}\r\n", end.format("%Y%m%d"))));
+    assert!(ics.contains("SUMMARY:#1 Ship\\, finally\r\n"));
+    assert!(!ics.contains("Someday"));
+
+    // An issue with only a start date gets a single-day event
+    let kickoff_event = ics
+        .split("BEGIN:VEVENT")
+        .find(|e| e.contains(&format!("UID:issue-{}@centy", kickoff)))
+        .expect("Start-only issue should be exported");
+    assert!(kickoff_event.contains("DTSTART;VALUE=DATE:20240603\r\n"));
+    assert!(kickoff_event.contains("DTEND;VALUE=DATE:20240604\r\n"));
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use centy_daemon::feed::{
+    watch_project, ChangeEntity, ChangeEvent, ChangeKind, ChangeSource, Subscription, WatchError,
+};
+use centy_daemon::issue::{delete_issue};
+use common::{create_test_dir, create_titled_issue, init_centy_project};
+use std::time::Duration;
+
+async fn next_event(subscription: &mut Subscription) -> ChangeEvent {
+    tokio::time::timeout(Duration::from_secs(5), subscription.recv())
+        .await
+        .expect("Should receive an event in time")
+        .expect("Feed should be open")
+}
+
+#[tokio::test]
+async fn test_daemon_changes_are_reported_once() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let mut subscription = watch_project(project_path, Vec::new()).expect("Should watch");
+
+    let id = create_titled_issue(project_path, "Live").await;
+    let event = next_event(&mut subscription).await;
+    assert_eq!(event.entity, ChangeEntity::Issue);
+    assert_eq!(event.kind, ChangeKind::Created);
+    assert_eq!(event.id, id);
+    assert_eq!(event.source, ChangeSource::Daemon);
+
+    delete_issue(project_path, &id).await.expect("Should delete");
+    let event = next_event(&mut subscription).await;
+    assert_eq!((event.kind, event.id.as_str()), (ChangeKind::Deleted, id.as_str()));
+
+    // The watcher sees the same writes on disk, but they are not repeated
+    let extra = tokio::time::timeout(Duration::from_millis(800), subscription.recv()).await;
+    assert!(extra.is_err(), "Unexpected event: {:?}", extra);
+}
+
+#[tokio::test]
+async fn test_direct_disk_edits_are_reported() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    let centy_path = project_path.join(".centy");
+
+    let mut subscription = watch_project(project_path, Vec::new()).expect("Should watch");
+
+    // As if a teammate's doc and issue arrived through git pull
+    let issue_id = "550e8400-e29b-41d4-a716-446655440000";
+    std::fs::create_dir_all(centy_path.join("issues").join(issue_id)).unwrap();
+    std::fs::write(centy_path.join("issues").join(issue_id).join("issue.md"), "# Pulled\n").unwrap();
+    std::fs::create_dir_all(centy_path.join("docs")).unwrap();
+    std::fs::write(centy_path.join("docs/pulled.md"), "# Pulled doc\n").unwrap();
+
+    let mut seen = Vec::new();
+    while seen.len() < 2 {
+        let event = next_event(&mut subscription).await;
+        assert_eq!(event.source, ChangeSource::Disk);
+        seen.push((event.entity, event.kind, event.id));
+    }
+    seen.sort_by_key(|(entity, _, _)| entity.as_str());
+    assert_eq!(
+        seen,
+        vec![
+            (ChangeEntity::Doc, ChangeKind::Created, "pulled".to_string()),
+            (ChangeEntity::Issue, ChangeKind::Created, issue_id.to_string()),
+        ]
+    );
+
+    std::fs::write(centy_path.join("docs/pulled.md"), "# Pulled doc\n\nMore").unwrap();
+    let event = next_event(&mut subscription).await;
+    assert_eq!((event.entity, event.kind), (ChangeEntity::Doc, ChangeKind::Updated));
+
+    std::fs::remove_file(centy_path.join("docs/pulled.md")).unwrap();
+    let event = next_event(&mut subscription).await;
+    assert_eq!((event.entity, event.kind), (ChangeEntity::Doc, ChangeKind::Deleted));
+}
+
+#[tokio::test]
+async fn test_watch_filters_entities() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+
+    let result = watch_project(project_path, Vec::new());
+    assert!(matches!(result, Err(WatchError::NotInitialized)));
+
+    init_centy_project(project_path).await;
+    let mut subscription =
+        watch_project(project_path, vec![ChangeEntity::Config]).expect("Should watch");
+
+    create_titled_issue(project_path, "Ignored").await;
+    let config_path = project_path.join(".centy/config.json");
+    let config = std::fs::read_to_string(&config_path).unwrap_or_else(|_| "{}".to_string());
+    std::fs::write(&config_path, config).unwrap();
+
+    let event = next_event(&mut subscription).await;
+    assert_eq!(event.entity, ChangeEntity::Config);
+    assert_eq!(event.id, "config");
+}
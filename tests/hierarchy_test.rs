@@ -0,0 +1,283 @@
+mod common;
+
+use centy_daemon::config::{read_config, write_config};
+use centy_daemon::issue::{
+    archive_issues, child_status_counts, create_issue, delete_issue, get_issue, get_issue_tree,
+    list_children, list_issues, set_parent, update_issue, CreateIssueOptions, HierarchyError,
+    IssueCrudError, IssueError, UpdateIssueOptions,
+};
+use common::{create_issue_with, create_test_dir, init_centy_project};
+use std::path::Path;
+use std::time::Duration;
+
+async fn create(project_path: &Path, title: &str, parent_id: Option<&str>) -> String {
+    create_issue_with(
+        project_path,
+        CreateIssueOptions {
+            title: title.to_string(),
+            parent_id: parent_id.map(str::to_string),
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+async fn set_status(project_path: &Path, issue_id: &str, status: &str) -> Result<Vec<String>, IssueCrudError> {
+    update_issue(
+        project_path,
+        issue_id,
+        UpdateIssueOptions {
+            status: Some(status.to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .map(|r| r.warnings)
+}
+
+#[tokio::test]
+async fn test_create_issue_with_parent() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let epic = create(project_path, "Epic", None).await;
+    let task = create(project_path, "Task", Some(&epic)).await;
+
+    let issue = get_issue(project_path, &task).await.expect("Should get issue");
+    assert_eq!(issue.metadata.parent_id.as_deref(), Some(epic.as_str()));
+
+    let result = create_issue(
+        project_path,
+        CreateIssueOptions {
+            title: "Orphan".to_string(),
+            parent_id: Some("missing".to_string()),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(result, Err(IssueError::ParentNotFound(_))));
+
+    // A path that resolves to the parent is still not an issue ID
+    let result = create_issue(
+        project_path,
+        CreateIssueOptions {
+            title: "Sneaky".to_string(),
+            parent_id: Some(format!("../issues/{}", epic)),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(result, Err(IssueError::ParentNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_set_parent_and_list_children() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let epic = create(project_path, "Epic", None).await;
+    let task = create(project_path, "Task", None).await;
+    let subtask = create(project_path, "Subtask", Some(&task)).await;
+
+    let result = set_parent(project_path, &task, Some(&epic))
+        .await
+        .expect("Should set parent");
+    assert_eq!(result.issue.metadata.parent_id.as_deref(), Some(epic.as_str()));
+
+    let children = list_children(project_path, &epic, false)
+        .await
+        .expect("Should list children");
+    assert_eq!(children.len(), 1);
+    assert_eq!(children[0].id, task);
+
+    let descendants = list_children(project_path, &epic, true)
+        .await
+        .expect("Should list descendants");
+    let ids: Vec<_> = descendants.iter().map(|i| i.id.as_str()).collect();
+    assert_eq!(ids, vec![task.as_str(), subtask.as_str()]);
+
+    let result = set_parent(project_path, &task, None)
+        .await
+        .expect("Should clear parent");
+    assert!(result.issue.metadata.parent_id.is_none());
+}
+
+#[tokio::test]
+async fn test_set_parent_rejects_cycles() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let epic = create(project_path, "Epic", None).await;
+    let task = create(project_path, "Task", Some(&epic)).await;
+    let subtask = create(project_path, "Subtask", Some(&task)).await;
+
+    let result = set_parent(project_path, &epic, Some(&subtask)).await;
+    assert!(matches!(result, Err(HierarchyError::CycleDetected(_))));
+
+    let result = set_parent(project_path, &epic, Some(&epic)).await;
+    assert!(matches!(result, Err(HierarchyError::SelfParent)));
+
+    let result = set_parent(project_path, &epic, Some("missing")).await;
+    assert!(matches!(result, Err(HierarchyError::ParentNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_issue_tree() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let epic = create(project_path, "Epic", None).await;
+    let task = create(project_path, "Task", Some(&epic)).await;
+    create(project_path, "Subtask", Some(&task)).await;
+    create(project_path, "Standalone", None).await;
+
+    let forest = get_issue_tree(project_path, None).await.expect("Should build tree");
+    assert_eq!(forest.len(), 2);
+    assert_eq!(forest[0].issue.id, epic);
+    assert_eq!(forest[0].children.len(), 1);
+    assert_eq!(forest[0].children[0].children.len(), 1);
+    assert!(forest[1].children.is_empty());
+
+    let tree = get_issue_tree(project_path, Some(&task)).await.expect("Should build tree");
+    assert_eq!(tree.len(), 1);
+    assert_eq!(tree[0].issue.id, task);
+    assert_eq!(tree[0].children[0].issue.title, "Subtask");
+}
+
+#[tokio::test]
+async fn test_issue_tree_keeps_parent_cycles() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    create(project_path, "Standalone", None).await;
+    let first = create(project_path, "First", None).await;
+    let second = create(project_path, "Second", Some(&first)).await;
+    create(project_path, "Leaf", Some(&second)).await;
+
+    // Two branches each set a parent; merging them leaves First and Second
+    // pointing at each other
+    let metadata_path = project_path.join(".centy/issues").join(&first).join("metadata.json");
+    let mut metadata: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&metadata_path).unwrap()).unwrap();
+    metadata["parentId"] = second.clone().into();
+    std::fs::write(&metadata_path, serde_json::to_string_pretty(&metadata).unwrap()).unwrap();
+
+    // The edit reaches the cached issues through the watcher
+    for _ in 0..50 {
+        let issues = list_issues(project_path, None, None).await.unwrap();
+        if issues.iter().any(|i| i.id == first && i.metadata.parent_id.is_some()) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    let forest = get_issue_tree(project_path, None).await.expect("Should build tree");
+    assert_eq!(forest.len(), 2);
+    assert_eq!(forest[0].issue.title, "Standalone");
+    assert_eq!(forest[1].issue.id, first);
+    assert_eq!(forest[1].children[0].issue.id, second);
+    assert_eq!(forest[1].children[0].children[0].issue.title, "Leaf");
+}
+
+#[tokio::test]
+async fn test_child_status_counts() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let epic = create(project_path, "Epic", None).await;
+    let done = create(project_path, "Done", Some(&epic)).await;
+    create(project_path, "Open 1", Some(&epic)).await;
+    create(project_path, "Open 2", Some(&epic)).await;
+    set_status(project_path, &done, "closed").await.expect("Should close");
+
+    let counts = child_status_counts(project_path, &epic)
+        .await
+        .expect("Should count children");
+    assert_eq!(counts.get("open"), Some(&2));
+    assert_eq!(counts.get("closed"), Some(&1));
+
+    // Archiving the closed child keeps it in the rollup
+    let metadata_path = project_path.join(".centy/issues").join(&done).join("metadata.json");
+    let mut metadata: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&metadata_path).unwrap()).unwrap();
+    metadata["updatedAt"] = "2020-01-01T00:00:00+00:00".into();
+    std::fs::write(&metadata_path, metadata.to_string()).unwrap();
+    // The edit reaches the cached issues through the watcher
+    let mut archived = Vec::new();
+    for _ in 0..50 {
+        archived = archive_issues(project_path, Some(30)).await.expect("Should archive").archived;
+        if !archived.is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(archived, vec![done]);
+
+    let counts = child_status_counts(project_path, &epic)
+        .await
+        .expect("Should count children");
+    assert_eq!(counts.get("closed"), Some(&1));
+}
+
+#[tokio::test]
+async fn test_closing_parent_with_open_children_warns() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let epic = create(project_path, "Epic", None).await;
+    create(project_path, "Task", Some(&epic)).await;
+
+    let warnings = set_status(project_path, &epic, "closed").await.expect("Should close");
+    assert_eq!(warnings.len(), 1);
+
+    let issue = get_issue(project_path, &epic).await.expect("Should get issue");
+    assert_eq!(issue.metadata.status, "closed");
+}
+
+#[tokio::test]
+async fn test_closing_parent_with_open_children_refused_by_config() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let mut config = read_config(project_path)
+        .await
+        .expect("Should read config")
+        .unwrap_or_default();
+    config.block_close_with_open_children = true;
+    write_config(project_path, &config)
+        .await
+        .expect("Should write config");
+
+    let epic = create(project_path, "Epic", None).await;
+    let task = create(project_path, "Task", Some(&epic)).await;
+
+    let result = set_status(project_path, &epic, "closed").await;
+    assert!(matches!(result, Err(IssueCrudError::OpenChildren(1))));
+
+    set_status(project_path, &task, "closed").await.expect("Should close child");
+    let warnings = set_status(project_path, &epic, "closed").await.expect("Should close parent");
+    assert!(warnings.is_empty());
+}
+
+#[tokio::test]
+async fn test_delete_parent_detaches_children() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let epic = create(project_path, "Epic", None).await;
+    let task = create(project_path, "Task", Some(&epic)).await;
+
+    delete_issue(project_path, &epic).await.expect("Should delete");
+
+    let issue = get_issue(project_path, &task).await.expect("Should get issue");
+    assert!(issue.metadata.parent_id.is_none());
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use centy_daemon::issue::{
+    add_asset, delete_asset, get_issue_history, update_issue, AssetScope, CreateIssueOptions,
+    HistoryError, UpdateIssueOptions,
+};
+use common::{create_issue_with, create_test_dir, init_centy_project};
+use std::path::Path;
+
+async fn create(project_path: &Path, title: &str) -> String {
+    create_issue_with(
+        project_path,
+        CreateIssueOptions {
+            title: title.to_string(),
+            actor: Some("alice".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_create_issue_records_initial_values() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let id = create(project_path, "New issue").await;
+
+    let history = get_issue_history(project_path, &id)
+        .await
+        .expect("Should get history");
+    let fields: Vec<_> = history.iter().map(|e| e.field.as_str()).collect();
+    assert_eq!(fields, vec!["title", "status", "priority"]);
+    assert!(history.iter().all(|e| e.old_value.is_none()));
+    assert!(history.iter().all(|e| e.actor.as_deref() == Some("alice")));
+    assert_eq!(history[0].new_value.as_deref(), Some("New issue"));
+}
+
+#[tokio::test]
+async fn test_update_issue_appends_changed_fields() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let id = create(project_path, "Issue").await;
+    let before = get_issue_history(project_path, &id)
+        .await
+        .expect("Should get history")
+        .len();
+
+    update_issue(
+        project_path,
+        &id,
+        UpdateIssueOptions {
+            status: Some("closed".to_string()),
+            actor: Some("bob".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should update");
+
+    update_issue(
+        project_path,
+        &id,
+        UpdateIssueOptions {
+            status: Some("open".to_string()),
+            actor: Some("carol".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should update");
+
+    // An update that changes nothing records nothing
+    update_issue(
+        project_path,
+        &id,
+        UpdateIssueOptions {
+            status: Some("open".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should update");
+
+    let history = get_issue_history(project_path, &id)
+        .await
+        .expect("Should get history");
+    assert_eq!(history.len(), before + 2);
+
+    let reopened = history.last().unwrap();
+    assert_eq!(reopened.field, "status");
+    assert_eq!(reopened.old_value.as_deref(), Some("closed"));
+    assert_eq!(reopened.new_value.as_deref(), Some("open"));
+    assert_eq!(reopened.actor.as_deref(), Some("carol"));
+    assert!(!reopened.timestamp.is_empty());
+}
+
+#[tokio::test]
+async fn test_asset_changes_are_recorded() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let id = create(project_path, "Issue").await;
+
+    add_asset(project_path, Some(&id), vec![1, 2, 3], "screenshot.png", AssetScope::IssueSpecific)
+        .await
+        .expect("Should add asset");
+    delete_asset(project_path, Some(&id), "screenshot.png", false)
+        .await
+        .expect("Should delete asset");
+
+    let history = get_issue_history(project_path, &id)
+        .await
+        .expect("Should get history");
+    let asset_events: Vec<_> = history.iter().filter(|e| e.field == "assets").collect();
+    assert_eq!(asset_events.len(), 2);
+    assert_eq!(asset_events[0].new_value.as_deref(), Some("screenshot.png"));
+    assert!(asset_events[0].old_value.is_none());
+    assert_eq!(asset_events[1].old_value.as_deref(), Some("screenshot.png"));
+    assert!(asset_events[1].new_value.is_none());
+}
+
+#[tokio::test]
+async fn test_history_of_missing_issue() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let result = get_issue_history(project_path, "00000000-0000-0000-0000-000000000000").await;
+    assert!(matches!(result, Err(HistoryError::IssueNotFound(_))));
+}
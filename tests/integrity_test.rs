@@ -0,0 +1,152 @@
+mod common;
+
+use centy_daemon::docs::{create_doc, CreateDocOptions};
+use centy_daemon::integrity::{check_project, repair_project, FindingKind, IntegrityError, Severity};
+use centy_daemon::issue::{get_issue, list_issues};
+use common::{create_test_dir, create_titled_issue, init_centy_project};
+use std::path::Path;
+
+fn set_metadata_field(project_path: &Path, issue_id: &str, field: &str, value: serde_json::Value) {
+    let path = project_path
+        .join(".centy/issues")
+        .join(issue_id)
+        .join("metadata.json");
+    let mut metadata: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    metadata[field] = value;
+    std::fs::write(&path, serde_json::to_string_pretty(&metadata).unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn test_clean_project_has_no_findings() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    create_titled_issue(project_path, "Healthy issue").await;
+
+    let findings = check_project(project_path).await.expect("Should check");
+    assert!(findings.is_empty(), "Unexpected findings: {:?}", findings);
+}
+
+#[tokio::test]
+async fn test_check_reports_broken_issues() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    let centy = project_path.join(".centy");
+
+    let broken = create_titled_issue(project_path, "Broken").await;
+    let no_md = create_titled_issue(project_path, "No markdown").await;
+    let odd = create_titled_issue(project_path, "Odd values").await;
+
+    std::fs::write(centy.join("issues").join(&broken).join("metadata.json"), "{\"status\": ").unwrap();
+    std::fs::remove_file(centy.join("issues").join(&no_md).join("issue.md")).unwrap();
+    set_metadata_field(project_path, &odd, "status", "someday".into());
+    set_metadata_field(project_path, &odd, "priority", 9.into());
+    std::fs::write(centy.join("assets").join("empty.png"), "").unwrap();
+
+    // Broken issues are left out of lists
+    let listed = list_issues(project_path, None, None).await.unwrap();
+    assert_eq!(listed.len(), 1);
+
+    let findings = check_project(project_path).await.expect("Should check");
+    let find = |kind: FindingKind| {
+        findings
+            .iter()
+            .find(|f| f.kind == kind)
+            .unwrap_or_else(|| panic!("No {:?} finding in {:?}", kind, findings))
+    };
+
+    let invalid = find(FindingKind::InvalidMetadata);
+    assert_eq!(invalid.severity, Severity::Error);
+    assert_eq!(invalid.path, format!("issues/{}", broken));
+    assert_eq!(find(FindingKind::MissingContent).path, format!("issues/{}", no_md));
+    assert_eq!(find(FindingKind::UnknownStatus).suggested_fix, "Set the status to 'open'");
+    assert_eq!(find(FindingKind::PriorityOutOfRange).suggested_fix, "Set the priority to 3");
+    assert_eq!(find(FindingKind::EmptyAsset).path, "assets/empty.png");
+    // Errors come first
+    assert_eq!(findings[0].severity, Severity::Error);
+}
+
+#[tokio::test]
+async fn test_repair_selected_finding() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let issue_id = create_titled_issue(project_path, "Odd values").await;
+    set_metadata_field(project_path, &issue_id, "status", "someday".into());
+    set_metadata_field(project_path, &issue_id, "priority", 9.into());
+
+    let status_id = format!("unknown-status:issues/{}", issue_id);
+    let result = repair_project(project_path, &[status_id])
+        .await
+        .expect("Should repair");
+    assert_eq!(result.repaired.len(), 1);
+    assert!(result.failed.is_empty());
+    assert_eq!(result.remaining.len(), 1);
+    assert_eq!(result.remaining[0].kind, FindingKind::PriorityOutOfRange);
+
+    let issue = get_issue(project_path, &issue_id).await.unwrap();
+    assert_eq!(issue.metadata.status, "open");
+    assert_eq!(issue.metadata.priority, 9);
+}
+
+#[tokio::test]
+async fn test_repair_all_makes_issues_visible_again() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    let centy = project_path.join(".centy");
+
+    let broken = create_titled_issue(project_path, "Broken").await;
+    let no_meta = create_titled_issue(project_path, "No metadata").await;
+    create_titled_issue(project_path, "First").await;
+    // Takes the first issue's number below
+    let second = create_titled_issue(project_path, "Second").await;
+    create_doc(
+        project_path,
+        CreateDocOptions {
+            title: "Notes".to_string(),
+            content: "Some notes".to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    std::fs::write(centy.join("issues").join(&broken).join("metadata.json"), "").unwrap();
+    std::fs::remove_file(centy.join("issues").join(&no_meta).join("metadata.json")).unwrap();
+    set_metadata_field(project_path, &second, "displayNumber", 3.into());
+    std::fs::write(
+        centy.join("docs/notes.md"),
+        "---\ntitle: \"Notes\"\ncreatedAt: \"last week\"\n---\n\n# Notes\n\nSome notes\n",
+    )
+    .unwrap();
+    std::fs::write(centy.join(".config.json.1-0.tmp"), "{").unwrap();
+    std::fs::remove_file(centy.join("README.md")).unwrap();
+
+    let result = repair_project(project_path, &[]).await.expect("Should repair");
+    assert!(result.failed.is_empty(), "Failed: {:?}", result.failed);
+    assert!(result.remaining.is_empty(), "Remaining: {:?}", result.remaining);
+
+    let listed = list_issues(project_path, None, None).await.unwrap();
+    assert_eq!(listed.len(), 4);
+    let mut numbers: Vec<u32> = listed.iter().map(|i| i.metadata.display_number).collect();
+    numbers.dedup();
+    assert_eq!(numbers.len(), 4);
+
+    assert!(centy.join("issues").join(&broken).join("metadata.json.bak").exists());
+    assert!(!centy.join(".config.json.1-0.tmp").exists());
+    assert!(centy.join("README.md").exists());
+}
+
+#[tokio::test]
+async fn test_repair_unknown_finding_is_rejected() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let result = repair_project(project_path, &["temp-file:nothing".to_string()]).await;
+    assert!(matches!(result, Err(IntegrityError::FindingNotFound(_))));
+}
@@ -0,0 +1,169 @@
+mod common;
+
+use centy_daemon::issue::{
+    get_issue_by_display_number, list_issues, list_issues_filtered, update_issue, Issue,
+    IssueFilter, UpdateIssueOptions,
+};
+use centy_daemon::utils::lock_project;
+use common::{create_test_dir, create_titled_issue, init_centy_project};
+use std::path::Path;
+use std::time::Duration;
+
+/// List issues until `done` holds, as outside edits arrive through the watcher
+async fn list_until(project_path: &Path, done: impl Fn(&[Issue]) -> bool) -> Vec<Issue> {
+    for _ in 0..50 {
+        let issues = list_issues(project_path, None, None).await.unwrap();
+        if done(&issues) {
+            return issues;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("Cached issues never caught up with the disk");
+}
+
+#[tokio::test]
+async fn test_daemon_writes_are_visible_immediately() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let id = create_titled_issue(project_path, "First").await;
+    assert_eq!(list_issues(project_path, None, None).await.unwrap().len(), 1);
+
+    update_issue(
+        project_path,
+        &id,
+        UpdateIssueOptions {
+            title: Some("Renamed".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should update issue");
+    create_titled_issue(project_path, "Second").await;
+
+    let titles: Vec<_> = list_issues(project_path, None, None)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|i| i.title)
+        .collect();
+    assert_eq!(titles, vec!["Renamed", "Second"]);
+    let issue = get_issue_by_display_number(project_path, 2).await.unwrap();
+    assert_eq!(issue.title, "Second");
+}
+
+#[tokio::test]
+async fn test_outside_edits_reach_the_cache() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let id = create_titled_issue(project_path, "Original").await;
+    list_issues(project_path, None, None).await.unwrap();
+
+    let issue_path = project_path.join(".centy/issues").join(&id);
+    std::fs::write(issue_path.join("issue.md"), "# Edited in an editor\n").unwrap();
+    list_until(project_path, |issues| issues[0].title == "Edited in an editor").await;
+
+    std::fs::remove_dir_all(&issue_path).unwrap();
+    list_until(project_path, |issues| issues.is_empty()).await;
+}
+
+#[tokio::test]
+async fn test_pulled_issue_with_taken_number_is_reconciled() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let id = create_titled_issue(project_path, "Mine").await;
+    list_issues(project_path, None, None).await.unwrap();
+
+    // A teammate created #1 on another branch at the same time
+    let issues_path = project_path.join(".centy/issues");
+    let pulled = issues_path.join("550e8400-e29b-41d4-a716-446655440000");
+    std::fs::create_dir_all(&pulled).unwrap();
+    let metadata = std::fs::read_to_string(issues_path.join(&id).join("metadata.json"))
+        .unwrap()
+        .replace("\"createdAt\": \"", "\"createdAt\": \"9");
+    std::fs::write(pulled.join("issue.md"), "# Theirs\n").unwrap();
+    std::fs::write(pulled.join("metadata.json"), metadata).unwrap();
+
+    let issues = list_until(project_path, |issues| issues.len() == 2).await;
+    let numbers: Vec<_> = issues
+        .iter()
+        .map(|i| (i.title.as_str(), i.metadata.display_number))
+        .collect();
+    assert_eq!(numbers, vec![("Mine", 1), ("Theirs", 2)]);
+}
+
+#[tokio::test]
+async fn test_reads_do_not_reconcile_while_a_writer_holds_the_lock() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let id = create_titled_issue(project_path, "Mine").await;
+    let issues_path = project_path.join(".centy/issues");
+    let pulled = issues_path.join("550e8400-e29b-41d4-a716-446655440000");
+    std::fs::create_dir_all(&pulled).unwrap();
+    let metadata = std::fs::read_to_string(issues_path.join(&id).join("metadata.json"))
+        .unwrap()
+        .replace("\"createdAt\": \"", "\"createdAt\": \"9");
+    std::fs::write(pulled.join("issue.md"), "# Theirs\n").unwrap();
+    std::fs::write(pulled.join("metadata.json"), &metadata).unwrap();
+
+    // Duplicates are served untouched while the lock is taken
+    let lock = lock_project(project_path).await.unwrap();
+    let issues = list_issues(project_path, None, None).await.unwrap();
+    assert!(issues.iter().all(|i| i.metadata.display_number == 1));
+    assert_eq!(
+        std::fs::read_to_string(pulled.join("metadata.json")).unwrap(),
+        metadata
+    );
+
+    drop(lock);
+    let issues = list_issues(project_path, None, None).await.unwrap();
+    let numbers: Vec<_> = issues
+        .iter()
+        .map(|i| (i.title.as_str(), i.metadata.display_number))
+        .collect();
+    assert_eq!(numbers, vec![("Mine", 1), ("Theirs", 2)]);
+}
+
+#[tokio::test]
+async fn test_archived_issues_are_read_when_asked_for() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let id = create_titled_issue(project_path, "Active").await;
+    list_issues(project_path, None, None).await.unwrap();
+
+    // An issue archived on another branch arrives with a pull
+    let active = project_path.join(".centy/issues").join(&id);
+    let metadata = std::fs::read_to_string(active.join("metadata.json"))
+        .unwrap()
+        .replace("\"displayNumber\": 1", "\"displayNumber\": 7");
+    let archived = project_path.join(".centy/archive/issues/550e8400-e29b-41d4-a716-446655440000");
+    std::fs::create_dir_all(&archived).unwrap();
+    std::fs::write(archived.join("issue.md"), "# Old\n").unwrap();
+    std::fs::write(archived.join("metadata.json"), metadata).unwrap();
+
+    // Archived issues are not cached, so they show up without waiting for the watcher
+    assert_eq!(list_issues(project_path, None, None).await.unwrap().len(), 1);
+    let filter = IssueFilter {
+        include_archived: true,
+        ..Default::default()
+    };
+    let titles: Vec<_> = list_issues_filtered(project_path, &filter)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|i| i.title)
+        .collect();
+    assert_eq!(titles, vec!["Active", "Old"]);
+    let issue = get_issue_by_display_number(project_path, 7).await.unwrap();
+    assert_eq!(issue.title, "Old");
+    assert!(issue.metadata.archived);
+}
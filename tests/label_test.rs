@@ -0,0 +1,295 @@
+mod common;
+
+use centy_daemon::config::{
+    config_etag, read_config, write_config, CentyConfig, ConfigError, LabelDefinition,
+};
+use centy_daemon::issue::{
+    archive_issues, create_issue, delete_label, get_issue, get_issue_history, list_issues_filtered,
+    rename_label,
+    unarchive_issue, update_config, update_issue, ConfigUpdateError, CreateIssueOptions,
+    IssueError, IssueFilter, LabelError, UpdateIssueOptions,
+};
+use common::{create_issue_with, create_test_dir, init_centy_project};
+use std::path::Path;
+
+fn label(name: &str) -> LabelDefinition {
+    LabelDefinition {
+        name: name.to_string(),
+        color: Some("#ef4444".to_string()),
+        description: None,
+    }
+}
+
+async fn set_labels(project_path: &Path, names: &[&str]) {
+    let mut config = read_config(project_path)
+        .await
+        .expect("Should read config")
+        .unwrap_or_default();
+    config.labels = names.iter().map(|n| label(n)).collect();
+    write_config(project_path, &config)
+        .await
+        .expect("Should write config");
+}
+
+async fn create_labeled_issue(project_path: &Path, title: &str, labels: &[&str]) -> String {
+    create_issue_with(
+        project_path,
+        CreateIssueOptions {
+            title: title.to_string(),
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_create_issue_with_labels() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    set_labels(project_path, &["bug", "ui"]).await;
+
+    let id = create_labeled_issue(project_path, "Broken button", &["bug", " ui ", "bug"]).await;
+
+    let issue = get_issue(project_path, &id).await.expect("Should get issue");
+    assert_eq!(issue.metadata.labels, vec!["bug", "ui"]);
+}
+
+#[tokio::test]
+async fn test_create_issue_rejects_unknown_label() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    set_labels(project_path, &["bug"]).await;
+
+    let result = create_issue(
+        project_path,
+        CreateIssueOptions {
+            title: "Issue".to_string(),
+            labels: vec!["feature".to_string()],
+            ..Default::default()
+        },
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(IssueError::LabelError(LabelError::UnknownLabel(_)))
+    ));
+}
+
+#[tokio::test]
+async fn test_labels_allowed_freely_without_definitions() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let id = create_labeled_issue(project_path, "Issue", &["anything"]).await;
+
+    let issue = get_issue(project_path, &id).await.expect("Should get issue");
+    assert_eq!(issue.metadata.labels, vec!["anything"]);
+}
+
+#[tokio::test]
+async fn test_update_issue_labels() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    set_labels(project_path, &["bug", "ui"]).await;
+
+    let id = create_labeled_issue(project_path, "Issue", &["bug"]).await;
+
+    // Other updates leave labels alone
+    let result = update_issue(
+        project_path,
+        &id,
+        UpdateIssueOptions {
+            title: Some("Renamed".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should update");
+    assert_eq!(result.issue.metadata.labels, vec!["bug"]);
+
+    let result = update_issue(
+        project_path,
+        &id,
+        UpdateIssueOptions {
+            labels: Some(vec!["ui".to_string()]),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should update");
+    assert_eq!(result.issue.metadata.labels, vec!["ui"]);
+
+    let result = update_issue(
+        project_path,
+        &id,
+        UpdateIssueOptions {
+            labels: Some(Vec::new()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should update");
+    assert!(result.issue.metadata.labels.is_empty());
+}
+
+#[tokio::test]
+async fn test_list_issues_filtered_by_labels() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    create_labeled_issue(project_path, "Both", &["bug", "ui"]).await;
+    create_labeled_issue(project_path, "Bug only", &["bug"]).await;
+    create_labeled_issue(project_path, "None", &[]).await;
+
+    let filter = IssueFilter {
+        labels: vec!["bug".to_string()],
+        ..Default::default()
+    };
+    let issues = list_issues_filtered(project_path, &filter)
+        .await
+        .expect("Should list");
+    assert_eq!(issues.len(), 2);
+
+    let filter = IssueFilter {
+        labels: vec!["bug".to_string(), "ui".to_string()],
+        ..Default::default()
+    };
+    let issues = list_issues_filtered(project_path, &filter)
+        .await
+        .expect("Should list");
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].title, "Both");
+}
+
+#[tokio::test]
+async fn test_rename_label_updates_config_and_issues() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    set_labels(project_path, &["bug", "ui"]).await;
+
+    let tagged = create_labeled_issue(project_path, "Tagged", &["bug", "ui"]).await;
+    create_labeled_issue(project_path, "Untagged", &["ui"]).await;
+
+    let result = rename_label(project_path, "bug", "defect")
+        .await
+        .expect("Should rename");
+
+    assert_eq!(result.updated_issue_ids, vec![tagged.clone()]);
+    let names: Vec<_> = result.config.labels.iter().map(|l| l.name.as_str()).collect();
+    assert_eq!(names, vec!["defect", "ui"]);
+    assert_eq!(result.config.labels[0].color.as_deref(), Some("#ef4444"));
+
+    let issue = get_issue(project_path, &tagged).await.expect("Should get issue");
+    assert_eq!(issue.metadata.labels, vec!["defect", "ui"]);
+
+    let result = rename_label(project_path, "defect", "ui").await;
+    assert!(matches!(result, Err(LabelError::LabelAlreadyExists(_))));
+
+    let result = rename_label(project_path, "missing", "other").await;
+    assert!(matches!(result, Err(LabelError::UnknownLabel(_))));
+}
+
+#[tokio::test]
+async fn test_delete_label_strips_issues() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    set_labels(project_path, &["bug", "ui"]).await;
+
+    let tagged = create_labeled_issue(project_path, "Tagged", &["bug", "ui"]).await;
+
+    let result = delete_label(project_path, "bug").await.expect("Should delete");
+    assert_eq!(result.updated_issue_ids, vec![tagged.clone()]);
+    assert_eq!(result.config.labels.len(), 1);
+
+    let issue = get_issue(project_path, &tagged).await.expect("Should get issue");
+    assert_eq!(issue.metadata.labels, vec!["ui"]);
+    let history = get_issue_history(project_path, &tagged).await.unwrap();
+    let event = history.last().expect("Should record the label change");
+    assert_eq!(event.field, "labels");
+    assert_eq!(event.old_value.as_deref(), Some("bug, ui"));
+    assert_eq!(event.new_value.as_deref(), Some("ui"));
+
+    let config: CentyConfig = read_config(project_path)
+        .await
+        .expect("Should read config")
+        .expect("Config should exist");
+    assert!(config.labels.iter().all(|l| l.name != "bug"));
+}
+
+#[tokio::test]
+async fn test_delete_label_strips_archived_issues() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    set_labels(project_path, &["bug"]).await;
+
+    let archived = create_issue_with(
+        project_path,
+        CreateIssueOptions {
+            title: "Old".to_string(),
+            status: Some("closed".to_string()),
+            labels: vec!["bug".to_string()],
+            ..Default::default()
+        },
+    )
+    .await;
+    let metadata_path = project_path.join(".centy/issues").join(&archived).join("metadata.json");
+    let mut metadata: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&metadata_path).unwrap()).unwrap();
+    metadata["updatedAt"] = "2020-01-01T00:00:00+00:00".into();
+    std::fs::write(&metadata_path, metadata.to_string()).unwrap();
+    archive_issues(project_path, Some(30)).await.expect("Should archive");
+
+    let result = delete_label(project_path, "bug").await.expect("Should delete");
+    assert_eq!(result.updated_issue_ids, vec![archived.clone()]);
+    let history = get_issue_history(project_path, &archived).await.unwrap();
+    let event = history.last().expect("Should record the label change");
+    assert_eq!(event.field, "labels");
+    assert_eq!(event.old_value.as_deref(), Some("bug"));
+    assert!(event.new_value.is_none());
+
+    unarchive_issue(project_path, &archived).await.expect("Should unarchive");
+    let issue = get_issue(project_path, &archived).await.expect("Should get issue");
+    assert!(issue.metadata.labels.is_empty());
+}
+
+#[tokio::test]
+async fn test_update_config_strips_dropped_labels() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    set_labels(project_path, &["bug", "ui"]).await;
+
+    let tagged = create_labeled_issue(project_path, "Tagged", &["bug", "ui"]).await;
+    let etag = config_etag(project_path).await.expect("Should read etag");
+
+    let mut config = read_config(project_path).await.unwrap().unwrap();
+    config.labels = vec![label("ui")];
+
+    // A stale etag writes nothing and leaves the issue alone
+    let result = update_config(project_path, &config, Some("stale"), false).await;
+    assert!(matches!(
+        result,
+        Err(ConfigUpdateError::ConfigError(ConfigError::Conflict { .. }))
+    ));
+    let issue = get_issue(project_path, &tagged).await.expect("Should get issue");
+    assert_eq!(issue.metadata.labels, vec!["bug", "ui"]);
+
+    let result = update_config(project_path, &config, Some(&etag), false)
+        .await
+        .expect("Should update config");
+    assert_eq!(result.stripped_issue_ids, vec![tagged.clone()]);
+    assert_eq!(result.etag, config_etag(project_path).await.unwrap());
+
+    let issue = get_issue(project_path, &tagged).await.expect("Should get issue");
+    assert_eq!(issue.metadata.labels, vec!["ui"]);
+}
@@ -0,0 +1,252 @@
+mod common;
+
+use centy_daemon::issue::{
+    create_issue, get_issue, get_issue_history, list_issues_filtered, update_issue,
+    CreateIssueOptions, IssueError, IssueFilter, UpdateIssueOptions,
+};
+use centy_daemon::milestone::{
+    create_milestone, delete_milestone, get_milestone, list_milestones, milestone_progress,
+    update_milestone, CreateMilestoneOptions, MilestoneError, MilestoneState,
+    UpdateMilestoneOptions,
+};
+use centy_daemon::pr::{create_pr, get_pr, update_pr, CreatePrOptions, UpdatePrOptions};
+use common::{create_issue_with, create_test_dir, init_centy_project};
+use std::path::Path;
+
+async fn create_release(project_path: &Path, title: &str, target_date: Option<&str>) -> String {
+    create_milestone(
+        project_path,
+        CreateMilestoneOptions {
+            title: title.to_string(),
+            target_date: target_date.map(str::to_string),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should create milestone")
+    .milestone
+    .slug
+}
+
+async fn create_in_milestone(project_path: &Path, title: &str, milestone: &str) -> String {
+    create_issue_with(
+        project_path,
+        CreateIssueOptions {
+            title: title.to_string(),
+            milestone: Some(milestone.to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_milestone_crud() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let slug = create_release(project_path, "Version 1.0", Some("2024-09-01")).await;
+    assert_eq!(slug, "version-10");
+    assert!(project_path
+        .join(".centy/milestones/version-10.json")
+        .exists());
+
+    let result = create_milestone(
+        project_path,
+        CreateMilestoneOptions {
+            title: "Version 1.0".to_string(),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(result, Err(MilestoneError::SlugAlreadyExists(_))));
+
+    let updated = update_milestone(
+        project_path,
+        &slug,
+        UpdateMilestoneOptions {
+            description: Some("First stable release".to_string()),
+            target_date: Some(String::new()),
+            state: Some(MilestoneState::Closed),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should update milestone");
+    assert_eq!(updated.milestone.metadata.target_date, None);
+
+    let milestone = get_milestone(project_path, &slug).await.expect("Should get milestone");
+    assert_eq!(milestone.title, "Version 1.0");
+    assert_eq!(milestone.description, "First stable release");
+    assert_eq!(milestone.metadata.state, MilestoneState::Closed);
+
+    delete_milestone(project_path, &slug)
+        .await
+        .expect("Should delete milestone");
+    let result = get_milestone(project_path, &slug).await;
+    assert!(matches!(result, Err(MilestoneError::MilestoneNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_list_milestones_orders_open_by_target_date() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    create_release(project_path, "Later", Some("2025-01-01")).await;
+    create_release(project_path, "Undated", None).await;
+    create_release(project_path, "Sooner", Some("2024-06-01")).await;
+    let done = create_release(project_path, "Done", Some("2023-01-01")).await;
+    update_milestone(
+        project_path,
+        &done,
+        UpdateMilestoneOptions {
+            state: Some(MilestoneState::Closed),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should close milestone");
+
+    let slugs: Vec<_> = list_milestones(project_path, None)
+        .await
+        .expect("Should list")
+        .into_iter()
+        .map(|m| m.slug)
+        .collect();
+    assert_eq!(slugs, vec!["sooner", "later", "undated", "done"]);
+
+    let open = list_milestones(project_path, Some(MilestoneState::Open))
+        .await
+        .expect("Should list");
+    assert_eq!(open.len(), 3);
+}
+
+#[tokio::test]
+async fn test_milestone_progress_counts_issues_and_prs() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let slug = create_release(project_path, "v1", None).await;
+
+    create_in_milestone(project_path, "Open work", &slug).await;
+    let done = create_in_milestone(project_path, "Done work", &slug).await;
+    update_issue(
+        project_path,
+        &done,
+        UpdateIssueOptions {
+            status: Some("closed".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should close issue");
+    create_issue(
+        project_path,
+        CreateIssueOptions {
+            title: "Elsewhere".to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should create issue");
+
+    let pr = create_pr(
+        project_path,
+        CreatePrOptions {
+            title: "Feature".to_string(),
+            source_branch: Some("feature".to_string()),
+            milestone: Some(slug.clone()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should create PR");
+    update_pr(
+        project_path,
+        &pr.id,
+        UpdatePrOptions {
+            status: Some("merged".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should merge PR");
+
+    let progress = milestone_progress(project_path, &slug)
+        .await
+        .expect("Should compute progress");
+    assert_eq!(progress.total_issues, 2);
+    assert_eq!(progress.completed_issues, 1);
+    assert_eq!(progress.total_prs, 1);
+    assert_eq!(progress.completed_prs, 1);
+    assert!((progress.completion_percentage - 200.0 / 3.0).abs() < 1e-9);
+    assert_eq!(progress.issue_status_counts.get("open"), Some(&1));
+    assert_eq!(progress.pr_status_counts.get("merged"), Some(&1));
+
+    let filter = IssueFilter {
+        milestone: Some(slug.clone()),
+        ..Default::default()
+    };
+    let issues = list_issues_filtered(project_path, &filter)
+        .await
+        .expect("Should list issues");
+    assert_eq!(issues.len(), 2);
+}
+
+#[tokio::test]
+async fn test_assigning_unknown_milestone_fails() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let result = create_issue(
+        project_path,
+        CreateIssueOptions {
+            title: "Issue".to_string(),
+            milestone: Some("nope".to_string()),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(result, Err(IssueError::MilestoneNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_delete_milestone_unassigns_items() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let slug = create_release(project_path, "v2", None).await;
+    let issue_id = create_in_milestone(project_path, "Issue", &slug).await;
+    let pr = create_pr(
+        project_path,
+        CreatePrOptions {
+            title: "PR".to_string(),
+            source_branch: Some("feature".to_string()),
+            milestone: Some(slug.clone()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should create PR");
+
+    let result = delete_milestone(project_path, &slug)
+        .await
+        .expect("Should delete milestone");
+    assert_eq!(result.unassigned_issues, vec![issue_id.clone()]);
+    assert_eq!(result.unassigned_prs, vec![pr.id.clone()]);
+
+    let issue = get_issue(project_path, &issue_id).await.expect("Should get issue");
+    assert_eq!(issue.metadata.milestone, None);
+    let history = get_issue_history(project_path, &issue_id).await.unwrap();
+    let event = history.last().expect("Should record the milestone change");
+    assert_eq!(event.field, "milestone");
+    assert_eq!(event.old_value.as_deref(), Some(slug.as_str()));
+    assert!(event.new_value.is_none());
+    let pr = get_pr(project_path, &pr.id).await.expect("Should get PR");
+    assert_eq!(pr.metadata.milestone, None);
+}
@@ -0,0 +1,151 @@
+mod common;
+
+use centy_daemon::config::read_config;
+use centy_daemon::issue::{
+    get_issue, get_issue_history, preview_priority_rescale, rescale_priorities, update_config,
+    ConfigUpdateError, CreateIssueOptions, PriorityItemKind, RescaleError,
+};
+use centy_daemon::pr::{create_pr, get_pr, CreatePrOptions};
+use common::{create_issue_with, create_test_dir, init_centy_project};
+use std::path::Path;
+
+async fn create(project_path: &Path, title: &str, priority: u32) -> String {
+    create_issue_with(
+        project_path,
+        CreateIssueOptions {
+            title: title.to_string(),
+            priority: Some(priority),
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_preview_does_not_write() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let high = create(project_path, "High", 1).await;
+    create(project_path, "Medium", 2).await;
+    create(project_path, "Low", 3).await;
+
+    let preview = preview_priority_rescale(project_path, 3, 5)
+        .await
+        .expect("Should preview");
+    assert!(!preview.applied);
+    let mapped: Vec<(u32, u32)> = preview
+        .changes
+        .iter()
+        .map(|c| (c.old_priority, c.new_priority))
+        .collect();
+    assert_eq!(mapped, vec![(1, 2), (2, 3), (3, 5)]);
+
+    let issue = get_issue(project_path, &high).await.unwrap();
+    assert_eq!(issue.metadata.priority, 1);
+}
+
+#[tokio::test]
+async fn test_rescale_rewrites_issues_and_prs() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let high = create(project_path, "High", 1).await;
+    let low = create(project_path, "Low", 3).await;
+    let pr = create_pr(
+        project_path,
+        CreatePrOptions {
+            title: "Feature".to_string(),
+            source_branch: Some("feature".to_string()),
+            priority: Some(3),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should create PR");
+
+    let result = rescale_priorities(project_path, 3, 4)
+        .await
+        .expect("Should rescale");
+    assert!(result.applied);
+    assert_eq!(result.changes.len(), 3);
+    assert_eq!(result.changes[2].kind, PriorityItemKind::Pr);
+
+    assert_eq!((result.old_levels, result.new_levels), (3, 4));
+
+    // high -> high (2 of 4), low -> low (4 of 4)
+    assert_eq!(get_issue(project_path, &high).await.unwrap().metadata.priority, 2);
+    assert_eq!(get_issue(project_path, &low).await.unwrap().metadata.priority, 4);
+    assert_eq!(get_pr(project_path, &pr.id).await.unwrap().metadata.priority, 4);
+}
+
+#[tokio::test]
+async fn test_rescale_records_history() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let high = create(project_path, "High", 1).await;
+    rescale_priorities(project_path, 3, 5)
+        .await
+        .expect("Should rescale");
+
+    let history = get_issue_history(project_path, &high).await.unwrap();
+    let event = history.last().expect("Should record an event");
+    assert_eq!(event.field, "priority");
+    assert_eq!(event.old_value.as_deref(), Some("1"));
+    assert_eq!(event.new_value.as_deref(), Some("2"));
+}
+
+#[tokio::test]
+async fn test_update_config_rescales_priorities() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let low = create(project_path, "Low", 3).await;
+    let mut config = read_config(project_path).await.unwrap().unwrap_or_default();
+    assert_eq!(config.priority_levels, 3);
+
+    config.priority_levels = 5;
+    let result = update_config(project_path, &config, None, true)
+        .await
+        .expect("Should update config");
+    assert_eq!(result.priority_changes.len(), 1);
+    assert_eq!(result.priority_changes[0].id, low);
+    assert_eq!(get_issue(project_path, &low).await.unwrap().metadata.priority, 5);
+}
+
+#[tokio::test]
+async fn test_update_config_with_invalid_levels_writes_nothing() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let low = create(project_path, "Low", 3).await;
+    let mut config = read_config(project_path).await.unwrap().unwrap_or_default();
+    config.priority_levels = 11;
+
+    let result = update_config(project_path, &config, None, true).await;
+    assert!(matches!(
+        result,
+        Err(ConfigUpdateError::RescaleError(RescaleError::InvalidLevels(11)))
+    ));
+    let stored = read_config(project_path).await.unwrap().unwrap_or_default();
+    assert_eq!(stored.priority_levels, 3);
+    assert_eq!(get_issue(project_path, &low).await.unwrap().metadata.priority, 3);
+}
+
+#[tokio::test]
+async fn test_rescale_rejects_invalid_levels() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let result = preview_priority_rescale(project_path, 0, 5).await;
+    assert!(matches!(result, Err(RescaleError::InvalidLevels(0))));
+    let result = rescale_priorities(project_path, 3, 11).await;
+    assert!(matches!(result, Err(RescaleError::InvalidLevels(11))));
+}
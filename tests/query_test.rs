@@ -0,0 +1,225 @@
+mod common;
+
+use centy_daemon::docs::{create_doc, list_docs, CreateDocOptions};
+use centy_daemon::issue::{list_issues, CreateIssueOptions};
+use centy_daemon::pr::{create_pr, list_prs, CreatePrOptions};
+use centy_daemon::query::{apply_list_query, ListQuery, QueryContext, QueryError};
+use common::{create_issue_with, create_test_dir, init_centy_project};
+use std::path::Path;
+
+async fn create(project_path: &Path, title: &str, status: &str, priority: u32, labels: &[&str]) {
+    create_issue_with(
+        project_path,
+        CreateIssueOptions {
+            title: title.to_string(),
+            description: format!("Description of {}", title),
+            status: Some(status.to_string()),
+            priority: Some(priority),
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            ..Default::default()
+        },
+    )
+    .await;
+}
+
+async fn issue_titles(project_path: &Path, query: &str, sort: &str) -> Vec<String> {
+    let list_query = ListQuery {
+        query: query.to_string(),
+        sort: sort.to_string(),
+        ..Default::default()
+    };
+    let issues = list_issues(project_path, None, None).await.expect("Should list issues");
+    apply_list_query(issues, &list_query, &QueryContext::default())
+        .expect("Query should be valid")
+        .items
+        .into_iter()
+        .map(|i| i.title)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_query_filters_issues() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    create(project_path, "Login fails", "open", 1, &["bug"]).await;
+    create(project_path, "Login is slow", "in-progress", 3, &["bug"]).await;
+    create(project_path, "Signup copy", "open", 2, &[]).await;
+    create(project_path, "Old login bug", "closed", 1, &["bug", "wontfix"]).await;
+
+    assert_eq!(
+        issue_titles(project_path, r#"status:open,in-progress label:bug "login""#, "").await,
+        vec!["Login fails", "Login is slow"]
+    );
+    assert_eq!(
+        issue_titles(project_path, "priority:<=2 -status:closed", "").await,
+        vec!["Login fails", "Signup copy"]
+    );
+    assert_eq!(issue_titles(project_path, "label:none", "").await, vec!["Signup copy"]);
+    assert_eq!(issue_titles(project_path, "-label:wontfix bug", "").await, Vec::<String>::new());
+    assert_eq!(
+        issue_titles(project_path, "created:>2000-01-01 number:>=3", "").await,
+        vec!["Signup copy", "Old login bug"]
+    );
+    assert_eq!(
+        issue_titles(project_path, "", "-priority,title").await,
+        vec!["Login is slow", "Signup copy", "Login fails", "Old login bug"]
+    );
+
+    let issues = list_issues(project_path, None, None).await.unwrap();
+    let result = apply_list_query(
+        issues,
+        &ListQuery {
+            query: "owner:me".to_string(),
+            ..Default::default()
+        },
+        &QueryContext::default(),
+    );
+    assert!(matches!(result, Err(QueryError::UnknownField(f)) if f == "owner"));
+}
+
+#[tokio::test]
+async fn test_pagination_survives_new_items() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    for (i, priority) in [2, 1, 2, 1, 3].into_iter().enumerate() {
+        create(project_path, &format!("Issue {}", i + 1), "open", priority, &[]).await;
+    }
+
+    let mut list_query = ListQuery {
+        sort: "priority,-number".to_string(),
+        page_size: 2,
+        ..Default::default()
+    };
+    let mut seen = Vec::new();
+    loop {
+        let issues = list_issues(project_path, None, None).await.unwrap();
+        let page = apply_list_query(issues, &list_query, &QueryContext::default())
+            .expect("Should page");
+        assert!(page.items.len() <= 2);
+        seen.extend(page.items.into_iter().map(|i| i.title));
+
+        // An issue created mid-way that sorts before the cursor is not
+        // picked up, and nothing after it is repeated or skipped
+        if seen.len() == 2 {
+            create(project_path, "Late", "open", 1, &[]).await;
+        }
+
+        match page.next_page_token {
+            Some(token) => list_query.page_token = token,
+            None => break,
+        }
+    }
+    assert_eq!(seen, vec!["Issue 4", "Issue 2", "Issue 3", "Issue 1", "Issue 5"]);
+
+    list_query.page_token = "not-a-token".to_string();
+    let issues = list_issues(project_path, None, None).await.unwrap();
+    let result = apply_list_query(issues, &list_query, &QueryContext::default());
+    assert!(matches!(result, Err(QueryError::InvalidPageToken)));
+}
+
+#[tokio::test]
+async fn test_sort_mixes_numeric_and_text_values() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    for title in ["1a", "10", "b", "9", "2026-01-01T00:00:00Z"] {
+        create(project_path, title, "open", 1, &[]).await;
+    }
+    let expected = vec!["9", "10", "2026-01-01T00:00:00Z", "1a", "b"];
+    assert_eq!(issue_titles(project_path, "", "title").await, expected);
+
+    // Paging through the same order neither skips nor repeats an issue
+    let mut list_query = ListQuery {
+        sort: "title".to_string(),
+        page_size: 1,
+        ..Default::default()
+    };
+    let mut seen = Vec::new();
+    loop {
+        let issues = list_issues(project_path, None, None).await.unwrap();
+        let page = apply_list_query(issues, &list_query, &QueryContext::default())
+            .expect("Should page");
+        seen.extend(page.items.into_iter().map(|i| i.title));
+        match page.next_page_token {
+            Some(token) => list_query.page_token = token,
+            None => break,
+        }
+    }
+    assert_eq!(seen, expected);
+}
+
+#[tokio::test]
+async fn test_query_applies_to_prs_and_docs() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    for (title, source) in [("Add login", "feature/login"), ("Fix signup", "fix/signup")] {
+        create_pr(
+            project_path,
+            CreatePrOptions {
+                title: title.to_string(),
+                source_branch: Some(source.to_string()),
+                target_branch: Some("main".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Should create PR");
+    }
+    for (title, content) in [("Setup", "How to install"), ("Usage", "How to run")] {
+        create_doc(
+            project_path,
+            CreateDocOptions {
+                title: title.to_string(),
+                content: content.to_string(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Should create doc");
+    }
+
+    let prs = list_prs(project_path, None, None, None, None).await.unwrap();
+    let page = apply_list_query(
+        prs,
+        &ListQuery {
+            query: "target:main source:fix/signup".to_string(),
+            ..Default::default()
+        },
+        &QueryContext::default(),
+    )
+    .expect("Should query PRs");
+    assert_eq!(page.total_count, 1);
+    assert_eq!(page.items[0].title, "Fix signup");
+
+    let docs = list_docs(project_path).await.unwrap();
+    let page = apply_list_query(
+        docs,
+        &ListQuery {
+            query: "install".to_string(),
+            sort: "-title".to_string(),
+            ..Default::default()
+        },
+        &QueryContext::default(),
+    )
+    .expect("Should query docs");
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].title, "Setup");
+
+    let docs = list_docs(project_path).await.unwrap();
+    let result = apply_list_query(
+        docs,
+        &ListQuery {
+            query: "status:open".to_string(),
+            ..Default::default()
+        },
+        &QueryContext::default(),
+    );
+    assert!(matches!(result, Err(QueryError::UnknownField(_))));
+}
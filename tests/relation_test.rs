@@ -0,0 +1,177 @@
+mod common;
+
+use centy_daemon::issue::{
+    delete_issue, get_issue, get_issue_graph, link_issues, list_issues, unlink_issues,
+    RelationError, RelationKind,
+};
+use common::{create_test_dir, create_titled_issue, init_centy_project};
+
+#[tokio::test]
+async fn test_link_issues_records_both_directions() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let a = create_titled_issue(project_path, "A").await;
+    let b = create_titled_issue(project_path, "B").await;
+
+    let result = link_issues(project_path, &a, &b, RelationKind::Blocks)
+        .await
+        .expect("Should link");
+
+    assert_eq!(result.source.metadata.relations.len(), 1);
+    assert_eq!(result.source.metadata.relations[0].kind, RelationKind::Blocks);
+    assert_eq!(result.source.metadata.relations[0].issue_id, b);
+
+    let target = get_issue(project_path, &b).await.expect("Should get issue");
+    assert_eq!(target.metadata.relations.len(), 1);
+    assert_eq!(target.metadata.relations[0].kind, RelationKind::BlockedBy);
+    assert_eq!(target.metadata.relations[0].issue_id, a);
+}
+
+#[tokio::test]
+async fn test_link_issues_rejects_duplicates_and_self_links() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let a = create_titled_issue(project_path, "A").await;
+    let b = create_titled_issue(project_path, "B").await;
+
+    link_issues(project_path, &a, &b, RelationKind::RelatesTo)
+        .await
+        .expect("Should link");
+
+    let result = link_issues(project_path, &a, &b, RelationKind::RelatesTo).await;
+    assert!(matches!(result, Err(RelationError::AlreadyLinked(_))));
+
+    let result = link_issues(project_path, &a, &a, RelationKind::Blocks).await;
+    assert!(matches!(result, Err(RelationError::SelfLink)));
+
+    let result = link_issues(project_path, &a, "missing", RelationKind::Blocks).await;
+    assert!(matches!(result, Err(RelationError::IssueNotFound(_))));
+
+    let outside = format!("../issues/{}", b);
+    let result = link_issues(project_path, &a, &outside, RelationKind::Blocks).await;
+    assert!(matches!(result, Err(RelationError::IssueNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_link_issues_rejects_blocking_cycle() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let a = create_titled_issue(project_path, "A").await;
+    let b = create_titled_issue(project_path, "B").await;
+    let c = create_titled_issue(project_path, "C").await;
+
+    link_issues(project_path, &a, &b, RelationKind::Blocks)
+        .await
+        .expect("Should link");
+    link_issues(project_path, &b, &c, RelationKind::Blocks)
+        .await
+        .expect("Should link");
+
+    let result = link_issues(project_path, &c, &a, RelationKind::Blocks).await;
+    assert!(matches!(result, Err(RelationError::CycleDetected(_))));
+
+    // Same cycle expressed from the other side
+    let result = link_issues(project_path, &a, &c, RelationKind::BlockedBy).await;
+    assert!(matches!(result, Err(RelationError::CycleDetected(_))));
+
+    // Non-blocking links may close a loop
+    link_issues(project_path, &c, &a, RelationKind::RelatesTo)
+        .await
+        .expect("relates-to should not be cycle-checked");
+}
+
+#[tokio::test]
+async fn test_unlink_issues_removes_both_directions() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let a = create_titled_issue(project_path, "A").await;
+    let b = create_titled_issue(project_path, "B").await;
+
+    link_issues(project_path, &a, &b, RelationKind::Duplicates)
+        .await
+        .expect("Should link");
+
+    let result = unlink_issues(project_path, &a, &b, RelationKind::Duplicates)
+        .await
+        .expect("Should unlink");
+    assert!(result.source.metadata.relations.is_empty());
+    assert!(result.target.metadata.relations.is_empty());
+
+    let result = unlink_issues(project_path, &a, &b, RelationKind::Duplicates).await;
+    assert!(matches!(result, Err(RelationError::NotLinked(_))));
+}
+
+#[tokio::test]
+async fn test_delete_issue_removes_inverse_links() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let a = create_titled_issue(project_path, "A").await;
+    let b = create_titled_issue(project_path, "B").await;
+
+    link_issues(project_path, &a, &b, RelationKind::Blocks)
+        .await
+        .expect("Should link");
+    list_issues(project_path, None, None).await.expect("Should list issues");
+    delete_issue(project_path, &a).await.expect("Should delete");
+
+    let issue = get_issue(project_path, &b).await.expect("Should get issue");
+    assert!(issue.metadata.relations.is_empty());
+    // Listed issues see the change at once, without waiting for the watcher
+    let issues = list_issues(project_path, None, None).await.expect("Should list issues");
+    assert!(issues[0].metadata.relations.is_empty());
+}
+
+#[tokio::test]
+async fn test_get_issue_graph() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let a = create_titled_issue(project_path, "A").await;
+    let b = create_titled_issue(project_path, "B").await;
+    let c = create_titled_issue(project_path, "C").await;
+    let d = create_titled_issue(project_path, "D").await;
+    let e = create_titled_issue(project_path, "E").await;
+
+    link_issues(project_path, &a, &b, RelationKind::Blocks)
+        .await
+        .expect("Should link");
+    link_issues(project_path, &c, &b, RelationKind::BlockedBy)
+        .await
+        .expect("Should link");
+    link_issues(project_path, &d, &e, RelationKind::RelatesTo)
+        .await
+        .expect("Should link");
+
+    // Component containing A: A → B → C, only forward edges
+    let graph = get_issue_graph(project_path, Some(&a))
+        .await
+        .expect("Should build graph");
+    assert_eq!(graph.nodes.len(), 3);
+    assert_eq!(graph.edges.len(), 2);
+    assert!(graph
+        .edges
+        .iter()
+        .all(|e| e.kind == RelationKind::Blocks));
+    assert!(graph.edges.iter().any(|e| e.source == b && e.target == c));
+
+    // Whole project: every linked issue, relates-to reported once
+    let graph = get_issue_graph(project_path, None)
+        .await
+        .expect("Should build graph");
+    assert_eq!(graph.nodes.len(), 5);
+    assert_eq!(graph.edges.len(), 3);
+
+    let result = get_issue_graph(project_path, Some("missing")).await;
+    assert!(matches!(result, Err(RelationError::IssueNotFound(_))));
+}
@@ -0,0 +1,245 @@
+mod common;
+
+use centy_daemon::docs::{create_doc, delete_doc, update_doc, CreateDocOptions, UpdateDocOptions};
+use centy_daemon::issue::{delete_issue, update_issue, CreateIssueOptions, UpdateIssueOptions};
+use centy_daemon::pr::{create_pr, CreatePrOptions};
+use centy_daemon::search::{search, SearchEntityType, SearchError, SearchOptions};
+use common::{create_issue_with, create_test_dir, init_centy_project};
+use std::path::Path;
+use std::time::Duration;
+
+fn query(q: &str) -> SearchOptions {
+    SearchOptions {
+        query: q.to_string(),
+        ..Default::default()
+    }
+}
+
+async fn create_test_issue(project_path: &Path, title: &str, description: &str) -> String {
+    create_issue_with(
+        project_path,
+        CreateIssueOptions {
+            title: title.to_string(),
+            description: description.to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_search_finds_issues_docs_and_prs() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    create_test_issue(project_path, "Login button broken", "Clicking does nothing").await;
+    create_test_issue(project_path, "Unrelated", "Nothing to see").await;
+    create_doc(
+        project_path,
+        CreateDocOptions {
+            title: "Auth guide".to_string(),
+            content: "How the login flow works".to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should create doc");
+    create_pr(
+        project_path,
+        CreatePrOptions {
+            title: "Fix login".to_string(),
+            source_branch: Some("fix-login".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should create PR");
+
+    let hits = search(project_path, query("login")).await.expect("Should search").hits;
+
+    assert_eq!(hits.len(), 3);
+    let types: Vec<_> = hits.iter().map(|h| h.entity_type).collect();
+    assert!(types.contains(&SearchEntityType::Issue));
+    assert!(types.contains(&SearchEntityType::Doc));
+    assert!(types.contains(&SearchEntityType::Pr));
+
+    let doc_hit = hits
+        .iter()
+        .find(|h| h.entity_type == SearchEntityType::Doc)
+        .unwrap();
+    assert_eq!(doc_hit.id, "auth-guide");
+    assert_eq!(doc_hit.display_number, 0);
+    assert_eq!(doc_hit.snippet, "How the login flow works");
+}
+
+#[tokio::test]
+async fn test_search_ranks_title_matches_first() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    create_test_issue(project_path, "Other", "There is a crash somewhere").await;
+    create_test_issue(project_path, "Crash on startup", "Details").await;
+
+    let hits = search(project_path, query("crash")).await.expect("Should search").hits;
+
+    assert_eq!(hits.len(), 2);
+    assert_eq!(hits[0].title, "Crash on startup");
+    assert!(hits[0].score > hits[1].score);
+}
+
+#[tokio::test]
+async fn test_search_requires_all_terms() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    create_test_issue(project_path, "Login broken", "On mobile").await;
+    create_test_issue(project_path, "Login slow", "On desktop").await;
+
+    let hits = search(project_path, query("login mobile")).await.expect("Should search").hits;
+
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].title, "Login broken");
+}
+
+#[tokio::test]
+async fn test_search_filters_entity_types_and_limit() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    for i in 1..=3 {
+        create_test_issue(project_path, &format!("Widget {}", i), "").await;
+    }
+    create_doc(
+        project_path,
+        CreateDocOptions {
+            title: "Widget docs".to_string(),
+            content: String::new(),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should create doc");
+
+    let options = SearchOptions {
+        query: "widget".to_string(),
+        entity_types: vec![SearchEntityType::Doc],
+        limit: None,
+    };
+    let hits = search(project_path, options).await.expect("Should search").hits;
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].entity_type, SearchEntityType::Doc);
+
+    let options = SearchOptions {
+        query: "widget".to_string(),
+        entity_types: Vec::new(),
+        limit: Some(2),
+    };
+    let results = search(project_path, options).await.expect("Should search");
+    assert_eq!(results.hits.len(), 2);
+    // The total counts every match, not just the ones returned
+    assert_eq!(results.total_count, 4);
+}
+
+#[tokio::test]
+async fn test_search_reflects_updates_and_deletes() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let issue_id = create_test_issue(project_path, "Original title", "").await;
+    create_doc(
+        project_path,
+        CreateDocOptions {
+            title: "Notes".to_string(),
+            content: "Original content".to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should create doc");
+
+    // Build the index
+    let hits = search(project_path, query("original")).await.expect("Should search").hits;
+    assert_eq!(hits.len(), 2);
+
+    update_issue(
+        project_path,
+        &issue_id,
+        UpdateIssueOptions {
+            title: Some("Renamed title".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should update issue");
+    update_doc(
+        project_path,
+        "notes",
+        UpdateDocOptions {
+            new_slug: Some("renamed-notes".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should update doc");
+
+    let hits = search(project_path, query("original")).await.expect("Should search").hits;
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].id, "renamed-notes");
+
+    let hits = search(project_path, query("renamed")).await.expect("Should search").hits;
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].id, issue_id);
+
+    delete_issue(project_path, &issue_id).await.expect("Should delete issue");
+    delete_doc(project_path, "renamed-notes").await.expect("Should delete doc");
+
+    let hits = search(project_path, query("original renamed")).await.expect("Should search").hits;
+    assert!(hits.is_empty());
+}
+
+#[tokio::test]
+async fn test_search_sees_outside_edit_right_after_daemon_write() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let issue_id = create_test_issue(project_path, "Original title", "").await;
+    let hits = search(project_path, query("original")).await.expect("Should search").hits;
+    assert_eq!(hits.len(), 1);
+
+    // Close enough to the daemon's write to be taken for its echo by the feed
+    let issue_md = project_path.join(".centy/issues").join(&issue_id).join("issue.md");
+    std::fs::write(&issue_md, "# Original title\n\nEdited outside\n").unwrap();
+
+    for _ in 0..50 {
+        let hits = search(project_path, query("outside")).await.expect("Should search").hits;
+        if hits.len() == 1 {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("Search index never picked up the outside edit");
+}
+
+#[tokio::test]
+async fn test_search_empty_query_fails() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let result = search(project_path, query("  ")).await;
+    assert!(matches!(result, Err(SearchError::EmptyQuery)));
+}
+
+#[tokio::test]
+async fn test_search_requires_init() {
+    let temp_dir = create_test_dir();
+
+    let result = search(temp_dir.path(), query("anything")).await;
+    assert!(matches!(result, Err(SearchError::NotInitialized)));
+}
@@ -0,0 +1,180 @@
+mod common;
+
+use centy_daemon::issue::{
+    add_comment, find_redirect, get_issue, get_issue_history, list_comments, transfer_issue,
+    AddCommentOptions, CreateIssueOptions, IssueCrudError, TransferError, TransferIssueOptions,
+    TransferMode,
+};
+use common::{create_issue_with, create_test_dir, create_titled_issue, init_centy_project};
+
+#[tokio::test]
+async fn test_move_issue_keeps_content_and_leaves_redirect() {
+    let source_dir = create_test_dir();
+    let destination_dir = create_test_dir();
+    let source = source_dir.path();
+    let destination = destination_dir.path();
+    init_centy_project(source).await;
+    init_centy_project(destination).await;
+
+    create_titled_issue(destination, "Already here").await;
+    let id = create_issue_with(
+        source,
+        CreateIssueOptions {
+            title: "Moving out".to_string(),
+            description: "Details".to_string(),
+            ..Default::default()
+        },
+    )
+    .await;
+    add_comment(
+        source,
+        &id,
+        AddCommentOptions {
+            author: "alice".to_string(),
+            body: "Looks good".to_string(),
+            reply_to: None,
+        },
+    )
+    .await
+    .expect("Should add comment");
+    std::fs::write(
+        source.join(".centy/issues").join(&id).join("assets/log.txt"),
+        "trace",
+    )
+    .unwrap();
+
+    let result = transfer_issue(source, &id, destination, TransferIssueOptions::default())
+        .await
+        .expect("Should move issue");
+    let moved = result.issue;
+    assert_ne!(moved.id, id);
+    assert_eq!(moved.title, "Moving out");
+    assert_eq!(moved.description, "Details");
+    assert_eq!(moved.metadata.display_number, 2);
+    assert!(result.source_manifest.is_some());
+
+    let comments = list_comments(destination, &moved.id).await.expect("Should list comments");
+    assert_eq!(comments.len(), 1);
+    assert!(destination
+        .join(".centy/issues")
+        .join(&moved.id)
+        .join("assets/log.txt")
+        .exists());
+    let history = get_issue_history(destination, &moved.id).await.expect("Should read history");
+    assert!(history.iter().any(|e| e.field == "project"));
+
+    assert!(!source.join(".centy/issues").join(&id).exists());
+    // Nothing is left of the staging folder
+    let leftovers: Vec<_> = std::fs::read_dir(destination.join(".centy"))
+        .unwrap()
+        .filter_map(|e| e.unwrap().file_name().into_string().ok())
+        .filter(|name| name.starts_with(".transfer-"))
+        .collect();
+    assert!(leftovers.is_empty(), "Leftovers: {:?}", leftovers);
+    let redirect = find_redirect(source, &id).await.expect("Should leave redirect");
+    assert_eq!(redirect.issue_id, moved.id);
+    assert_eq!(redirect.new_display_number, 2);
+    let result = get_issue(source, &id).await;
+    assert!(matches!(result, Err(IssueCrudError::IssueMoved(..))));
+}
+
+#[tokio::test]
+async fn test_copy_issue_keeps_source() {
+    let source_dir = create_test_dir();
+    let destination_dir = create_test_dir();
+    let source = source_dir.path();
+    let destination = destination_dir.path();
+    init_centy_project(source).await;
+    init_centy_project(destination).await;
+
+    let id = create_titled_issue(source, "Shared").await;
+    let result = transfer_issue(
+        source,
+        &id,
+        destination,
+        TransferIssueOptions {
+            mode: TransferMode::Copy,
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should copy issue");
+    assert!(result.source_manifest.is_none());
+    assert_eq!(result.issue.metadata.display_number, 1);
+
+    get_issue(source, &id).await.expect("Source should still exist");
+    assert!(find_redirect(source, &id).await.is_none());
+}
+
+#[tokio::test]
+async fn test_transfer_requires_initialized_distinct_projects() {
+    let source_dir = create_test_dir();
+    let other_dir = create_test_dir();
+    let source = source_dir.path();
+    init_centy_project(source).await;
+
+    let id = create_titled_issue(source, "Issue").await;
+
+    let result = transfer_issue(source, &id, source, TransferIssueOptions::default()).await;
+    assert!(matches!(result, Err(TransferError::SameProject)));
+
+    let result = transfer_issue(source, &id, other_dir.path(), TransferIssueOptions::default()).await;
+    assert!(matches!(result, Err(TransferError::DestinationNotInitialized(_))));
+}
+
+#[tokio::test]
+async fn test_transfer_rejects_paths_as_issue_ids() {
+    let source_dir = create_test_dir();
+    let destination_dir = create_test_dir();
+    let source = source_dir.path();
+    let destination = destination_dir.path();
+    init_centy_project(source).await;
+    init_centy_project(destination).await;
+
+    // An issue outside the active issues folder must not be reachable
+    let id = create_titled_issue(source, "Elsewhere").await;
+    let outside = source.join(".centy/elsewhere");
+    std::fs::create_dir_all(&outside).unwrap();
+    std::fs::rename(source.join(".centy/issues").join(&id), outside.join(&id)).unwrap();
+
+    let result = transfer_issue(
+        source,
+        &format!("../elsewhere/{}", id),
+        destination,
+        TransferIssueOptions::default(),
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(TransferError::IssueError(IssueCrudError::IssueNotFound(_)))
+    ));
+    assert!(outside.join(&id).exists());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_opposite_transfers_do_not_deadlock() {
+    let a_dir = create_test_dir();
+    let b_dir = create_test_dir();
+    let a = a_dir.path().to_path_buf();
+    let b = b_dir.path().to_path_buf();
+    init_centy_project(&a).await;
+    init_centy_project(&b).await;
+
+    let mut tasks = Vec::new();
+    for i in 0..4 {
+        let (from, to) = if i % 2 == 0 { (a.clone(), b.clone()) } else { (b.clone(), a.clone()) };
+        let id = create_titled_issue(&from, &format!("Issue {}", i)).await;
+        tasks.push(tokio::spawn(async move {
+            transfer_issue(&from, &id, &to, TransferIssueOptions::default()).await
+        }));
+    }
+
+    let all = async {
+        for task in tasks {
+            task.await.unwrap().expect("Should move issue");
+        }
+    };
+    tokio::time::timeout(std::time::Duration::from_secs(10), all)
+        .await
+        .expect("Transfers should not deadlock");
+}
@@ -0,0 +1,254 @@
+mod common;
+
+use centy_daemon::docs::{create_doc, delete_doc, get_doc, CreateDocOptions};
+use centy_daemon::issue::{delete_issue, get_issue, link_issues, set_parent, RelationKind};
+use centy_daemon::pr::{create_pr, delete_pr, get_pr, CreatePrOptions};
+use centy_daemon::trash::{
+    empty_trash, list_trash, restore_from_trash, TrashError, TrashKind,
+};
+use common::{create_test_dir, create_titled_issue, init_centy_project};
+
+#[tokio::test]
+async fn test_deleted_items_go_to_trash() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let issue_id = create_titled_issue(project_path, "Old issue").await;
+    let doc = create_doc(
+        project_path,
+        CreateDocOptions {
+            title: "Notes".to_string(),
+            content: "Some notes".to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should create doc");
+    let pr = create_pr(
+        project_path,
+        CreatePrOptions {
+            title: "Feature".to_string(),
+            source_branch: Some("feature".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should create PR");
+
+    delete_issue(project_path, &issue_id).await.expect("Should delete issue");
+    delete_doc(project_path, &doc.slug).await.expect("Should delete doc");
+    delete_pr(project_path, &pr.id).await.expect("Should delete PR");
+
+    let entries = list_trash(project_path).await.expect("Should list trash");
+    assert_eq!(entries.len(), 3);
+    let issue_entry = entries
+        .iter()
+        .find(|e| e.kind == TrashKind::Issue)
+        .expect("Issue should be in the trash");
+    assert_eq!(issue_entry.item_id, issue_id);
+    assert_eq!(issue_entry.title, "Old issue");
+    assert_eq!(issue_entry.display_number, Some(1));
+    assert!(entries.iter().any(|e| e.kind == TrashKind::Doc && e.title == "Notes"));
+    assert!(entries.iter().any(|e| e.kind == TrashKind::Pr && e.item_id == pr.id));
+
+    for entry in &entries {
+        restore_from_trash(project_path, &entry.entry_id)
+            .await
+            .expect("Should restore");
+    }
+    get_issue(project_path, &issue_id).await.expect("Issue should be back");
+    let doc = get_doc(project_path, &doc.slug).await.expect("Doc should be back");
+    assert_eq!(doc.title, "Notes");
+    get_pr(project_path, &pr.id).await.expect("PR should be back");
+    assert!(list_trash(project_path).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_restore_assigns_new_display_number_when_taken() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let first = create_titled_issue(project_path, "First").await;
+    let second = create_titled_issue(project_path, "Second").await;
+    delete_issue(project_path, &first).await.expect("Should delete");
+    delete_issue(project_path, &second).await.expect("Should delete");
+
+    // #1 is free again, but #2 gets reused by a new issue
+    create_titled_issue(project_path, "Third").await;
+    create_titled_issue(project_path, "Fourth").await;
+
+    let entries = list_trash(project_path).await.expect("Should list trash");
+    let entry_for = |id: &str| entries.iter().find(|e| e.item_id == id).unwrap().entry_id.clone();
+
+    let result = restore_from_trash(project_path, &entry_for(&second))
+        .await
+        .expect("Should restore");
+    assert_eq!(result.entry.display_number, Some(2));
+    assert_eq!(result.display_number, Some(3));
+
+    let result = restore_from_trash(project_path, &entry_for(&first))
+        .await
+        .expect("Should restore");
+    assert_eq!(result.display_number, Some(4));
+}
+
+#[tokio::test]
+async fn test_restored_pr_remembers_its_old_number() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let options = |title: &str| CreatePrOptions {
+        title: title.to_string(),
+        source_branch: Some("feature".to_string()),
+        ..Default::default()
+    };
+    let first = create_pr(project_path, options("First")).await.expect("Should create PR");
+    delete_pr(project_path, &first.id).await.expect("Should delete PR");
+    create_pr(project_path, options("Second")).await.expect("Should create PR");
+
+    let entry_id = list_trash(project_path).await.unwrap()[0].entry_id.clone();
+    let result = restore_from_trash(project_path, &entry_id)
+        .await
+        .expect("Should restore");
+    assert_eq!(result.display_number, Some(2));
+
+    let pr = get_pr(project_path, &first.id).await.expect("PR should be back");
+    assert_eq!(pr.metadata.display_number, 2);
+    assert_eq!(pr.metadata.previous_display_numbers, vec![1]);
+}
+
+#[tokio::test]
+async fn test_restore_keeps_free_display_number() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let first = create_titled_issue(project_path, "First").await;
+    create_titled_issue(project_path, "Second").await;
+    delete_issue(project_path, &first).await.expect("Should delete");
+
+    let entry_id = list_trash(project_path).await.unwrap()[0].entry_id.clone();
+    // Only entry IDs, not paths, are accepted
+    let result = restore_from_trash(project_path, &format!("../trash/{}", entry_id)).await;
+    assert!(matches!(result, Err(TrashError::EntryNotFound(_))));
+
+    let result = restore_from_trash(project_path, &entry_id)
+        .await
+        .expect("Should restore");
+    assert_eq!(result.display_number, Some(1));
+
+    let result = restore_from_trash(project_path, &entry_id).await;
+    assert!(matches!(result, Err(TrashError::EntryNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_trashing_a_doc_slug_twice_keeps_both() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    for content in ["First draft", "Second draft"] {
+        create_doc(
+            project_path,
+            CreateDocOptions {
+                title: "Notes".to_string(),
+                content: content.to_string(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Should create doc");
+        delete_doc(project_path, "notes").await.expect("Should delete doc");
+    }
+
+    let entries = list_trash(project_path).await.expect("Should list trash");
+    assert_eq!(entries.len(), 2);
+    assert!(entries.iter().all(|e| e.item_id == "notes"));
+
+    let mut restored = Vec::new();
+    for entry in &entries {
+        restore_from_trash(project_path, &entry.entry_id)
+            .await
+            .expect("Should restore");
+        let doc = get_doc(project_path, "notes").await.expect("Doc should be back");
+        restored.push(doc.content);
+        // Make room for the other copy
+        delete_doc(project_path, "notes").await.expect("Should delete doc");
+    }
+    restored.sort();
+    assert_eq!(restored, vec!["First draft", "Second draft"]);
+}
+
+#[tokio::test]
+async fn test_restore_relinks_relations_and_children() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let epic = create_titled_issue(project_path, "Epic").await;
+    let task = create_titled_issue(project_path, "Task").await;
+    let blocked = create_titled_issue(project_path, "Blocked").await;
+    let gone = create_titled_issue(project_path, "Gone").await;
+    set_parent(project_path, &task, Some(&epic)).await.unwrap();
+    link_issues(project_path, &epic, &blocked, RelationKind::Blocks).await.unwrap();
+    link_issues(project_path, &epic, &gone, RelationKind::RelatesTo).await.unwrap();
+
+    delete_issue(project_path, &epic).await.expect("Should delete");
+    delete_issue(project_path, &gone).await.expect("Should delete");
+    assert!(get_issue(project_path, &blocked).await.unwrap().metadata.relations.is_empty());
+    assert_eq!(get_issue(project_path, &task).await.unwrap().metadata.parent_id, None);
+
+    let entries = list_trash(project_path).await.unwrap();
+    let entry = entries.iter().find(|e| e.item_id == epic).unwrap();
+    restore_from_trash(project_path, &entry.entry_id)
+        .await
+        .expect("Should restore");
+
+    // Links come back on both sides, except to the issue still in the trash
+    let restored = get_issue(project_path, &epic).await.unwrap();
+    assert_eq!(restored.metadata.relations.len(), 1);
+    assert_eq!(restored.metadata.relations[0].issue_id, blocked);
+    let other = get_issue(project_path, &blocked).await.unwrap();
+    assert_eq!(other.metadata.relations.len(), 1);
+    assert_eq!(other.metadata.relations[0].kind, RelationKind::BlockedBy);
+    assert_eq!(other.metadata.relations[0].issue_id, epic);
+    assert_eq!(
+        get_issue(project_path, &task).await.unwrap().metadata.parent_id.as_deref(),
+        Some(epic.as_str())
+    );
+}
+
+#[tokio::test]
+async fn test_empty_trash_and_retention() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let old = create_titled_issue(project_path, "Old").await;
+    let recent = create_titled_issue(project_path, "Recent").await;
+    delete_issue(project_path, &old).await.expect("Should delete");
+    delete_issue(project_path, &recent).await.expect("Should delete");
+
+    // Backdate one entry past the default 30 day retention
+    let entries = list_trash(project_path).await.expect("Should list trash");
+    let old_entry = entries.iter().find(|e| e.item_id == old).unwrap();
+    let entry_file = project_path
+        .join(".centy/trash")
+        .join(&old_entry.entry_id)
+        .join("entry.json");
+    let mut entry: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&entry_file).unwrap()).unwrap();
+    entry["deletedAt"] = "2000-01-01T00:00:00+00:00".into();
+    std::fs::write(&entry_file, entry.to_string()).unwrap();
+
+    let entries = list_trash(project_path).await.expect("Should list trash");
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].item_id, recent);
+
+    let result = empty_trash(project_path).await.expect("Should empty trash");
+    assert_eq!(result.purged_count, 1);
+    assert!(list_trash(project_path).await.unwrap().is_empty());
+}
@@ -0,0 +1,188 @@
+mod common;
+
+use centy_daemon::docs::{create_doc, CreateDocOptions};
+use centy_daemon::issue::CreateIssueOptions;
+use centy_daemon::view::{
+    delete_view, get_view, list_views, run_view, save_view, SaveViewOptions, ViewEntity, ViewError,
+    ViewItems,
+};
+use common::{create_issue_with, create_test_dir, init_centy_project};
+
+fn issue_titles(items: &ViewItems) -> Vec<String> {
+    match items {
+        ViewItems::Issues(issues) => issues.iter().map(|i| i.title.clone()).collect(),
+        other => panic!("Expected issues, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_save_list_and_delete_views() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let result = save_view(
+        project_path,
+        SaveViewOptions {
+            title: "Release blockers".to_string(),
+            query: "priority:1 status:open".to_string(),
+            sort: "-updated".to_string(),
+            columns: vec!["Number".to_string(), "title".to_string(), "assignee".to_string()],
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should save view");
+    assert!(result.created);
+    assert_eq!(result.view.slug, "release-blockers");
+    assert_eq!(result.view.columns, vec!["number", "title", "assignee"]);
+    assert!(project_path.join(".centy/views/release-blockers.json").exists());
+
+    save_view(
+        project_path,
+        SaveViewOptions {
+            title: "Guides".to_string(),
+            entity: ViewEntity::Docs,
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should save view");
+
+    // Saving under the same slug replaces the view
+    let result = save_view(
+        project_path,
+        SaveViewOptions {
+            title: "Release blockers".to_string(),
+            query: "priority:1".to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should replace view");
+    assert!(!result.created);
+    let view = get_view(project_path, "release-blockers").await.unwrap();
+    assert_eq!(view.query, "priority:1");
+    assert!(view.columns.is_empty());
+
+    let titles: Vec<_> = list_views(project_path, None)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|v| v.title)
+        .collect();
+    assert_eq!(titles, vec!["Guides", "Release blockers"]);
+    assert_eq!(list_views(project_path, Some(ViewEntity::Prs)).await.unwrap().len(), 0);
+
+    delete_view(project_path, "guides").await.expect("Should delete view");
+    assert_eq!(list_views(project_path, None).await.unwrap().len(), 1);
+    let result = delete_view(project_path, "guides").await;
+    assert!(matches!(result, Err(ViewError::ViewNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_run_view() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    for (title, priority, labels) in [
+        ("Crash on start", 1, vec!["bug"]),
+        ("Typo", 3, vec!["bug"]),
+        ("New feature", 1, vec![]),
+        ("Slow login", 2, vec!["bug"]),
+    ] {
+        create_issue_with(
+            project_path,
+            CreateIssueOptions {
+                title: title.to_string(),
+                priority: Some(priority),
+                labels: labels.into_iter().map(str::to_string).collect(),
+                ..Default::default()
+            },
+        )
+        .await;
+    }
+    create_doc(
+        project_path,
+        CreateDocOptions {
+            title: "Bug triage".to_string(),
+            content: "How we triage".to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should create doc");
+
+    save_view(
+        project_path,
+        SaveViewOptions {
+            title: "Bugs".to_string(),
+            query: "label:bug".to_string(),
+            sort: "priority".to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should save view");
+
+    let page = run_view(project_path, "bugs", 2, String::new())
+        .await
+        .expect("Should run view");
+    assert_eq!(page.total_count, 3);
+    assert_eq!(issue_titles(&page.items), vec!["Crash on start", "Slow login"]);
+    let token = page.next_page_token.expect("Should have another page");
+
+    let page = run_view(project_path, "bugs", 2, token)
+        .await
+        .expect("Should run view");
+    assert_eq!(issue_titles(&page.items), vec!["Typo"]);
+    assert!(page.next_page_token.is_none());
+
+    save_view(
+        project_path,
+        SaveViewOptions {
+            title: "Triage docs".to_string(),
+            entity: ViewEntity::Docs,
+            query: "triage".to_string(),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should save view");
+    let page = run_view(project_path, "triage-docs", 0, String::new())
+        .await
+        .expect("Should run view");
+    assert!(matches!(&page.items, ViewItems::Docs(docs) if docs.len() == 1));
+
+    let result = run_view(project_path, "missing", 0, String::new()).await;
+    assert!(matches!(result, Err(ViewError::ViewNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_save_view_validates_query_and_columns() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+
+    let save = |entity, query: &str, columns: &[&str]| {
+        save_view(
+            project_path,
+            SaveViewOptions {
+                title: "View".to_string(),
+                entity,
+                query: query.to_string(),
+                columns: columns.iter().map(|c| c.to_string()).collect(),
+                ..Default::default()
+            },
+        )
+    };
+
+    let result = save(ViewEntity::Issues, r#"title:"open"#, &[]).await;
+    assert!(matches!(result, Err(ViewError::InvalidQuery(_))));
+    let result = save(ViewEntity::Docs, "status:open", &[]).await;
+    assert!(matches!(result, Err(ViewError::InvalidQuery(_))));
+    let result = save(ViewEntity::Prs, "", &["title", "labels"]).await;
+    assert!(matches!(result, Err(ViewError::UnknownColumn(c)) if c == "labels"));
+    assert!(save(ViewEntity::Prs, "reviewer:me", &["title", "reviewers"]).await.is_ok());
+}
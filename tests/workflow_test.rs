@@ -0,0 +1,204 @@
+mod common;
+
+use centy_daemon::config::{
+    read_config, write_config, TransitionRule, WorkflowConfig, WorkflowError, ANY_STATE,
+};
+use centy_daemon::issue::{
+    create_issue, get_issue, list_comments, update_issue, CreateIssueOptions, IssueCrudError,
+    IssueError, UpdateIssueOptions, UpdateIssueResult,
+};
+use common::{create_test_dir, create_titled_issue, init_centy_project};
+use std::collections::HashMap;
+use std::path::Path;
+
+async fn set_workflow(project_path: &Path, strict: bool) {
+    let mut config = read_config(project_path)
+        .await
+        .expect("Should read config")
+        .unwrap_or_default();
+    config.allowed_states = ["open", "in-progress", "closed", "wontfix"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    config.workflow = WorkflowConfig {
+        strict,
+        transitions: HashMap::from([
+            (
+                "open".to_string(),
+                vec!["in-progress".to_string(), "wontfix".to_string()],
+            ),
+            (
+                "in-progress".to_string(),
+                vec!["open".to_string(), "closed".to_string()],
+            ),
+        ]),
+        comment_required: vec![TransitionRule {
+            from: ANY_STATE.to_string(),
+            to: "wontfix".to_string(),
+        }],
+        terminal_states: vec!["wontfix".to_string()],
+    };
+    write_config(project_path, &config)
+        .await
+        .expect("Should write config");
+}
+
+async fn move_to(
+    project_path: &Path,
+    issue_id: &str,
+    status: &str,
+    comment: Option<&str>,
+) -> Result<UpdateIssueResult, IssueCrudError> {
+    update_issue(
+        project_path,
+        issue_id,
+        UpdateIssueOptions {
+            status: Some(status.to_string()),
+            comment: comment.map(str::to_string),
+            actor: Some("alice".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_strict_workflow_rejects_disallowed_transition() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    set_workflow(project_path, true).await;
+
+    let id = create_titled_issue(project_path, "Issue").await;
+
+    let result = move_to(project_path, &id, "closed", None).await;
+    assert!(matches!(
+        result,
+        Err(IssueCrudError::WorkflowError(WorkflowError::TransitionNotAllowed { .. }))
+    ));
+    let issue = get_issue(project_path, &id).await.expect("Should get issue");
+    assert_eq!(issue.metadata.status, "open");
+
+    move_to(project_path, &id, "in-progress", None)
+        .await
+        .expect("open → in-progress is allowed");
+    move_to(project_path, &id, "closed", None)
+        .await
+        .expect("in-progress → closed is allowed");
+
+    let result = move_to(project_path, &id, "done", None).await;
+    assert!(matches!(
+        result,
+        Err(IssueCrudError::WorkflowError(WorkflowError::UnknownStatus(_)))
+    ));
+}
+
+#[tokio::test]
+async fn test_lenient_workflow_warns() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    set_workflow(project_path, false).await;
+
+    let id = create_titled_issue(project_path, "Issue").await;
+
+    let result = move_to(project_path, &id, "closed", None)
+        .await
+        .expect("Lenient workflow accepts the transition");
+    assert_eq!(result.issue.metadata.status, "closed");
+    assert_eq!(result.warnings.len(), 1);
+}
+
+#[tokio::test]
+async fn test_transition_requires_comment() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    set_workflow(project_path, true).await;
+
+    let id = create_titled_issue(project_path, "Issue").await;
+
+    let result = move_to(project_path, &id, "wontfix", None).await;
+    assert!(matches!(
+        result,
+        Err(IssueCrudError::WorkflowError(WorkflowError::CommentRequired { .. }))
+    ));
+
+    // A comment that cannot be written leaves the status alone
+    let result = update_issue(
+        project_path,
+        &id,
+        UpdateIssueOptions {
+            status: Some("wontfix".to_string()),
+            comment: Some("Works as intended".to_string()),
+            actor: Some("  ".to_string()),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(result, Err(IssueCrudError::CommentError(_))));
+    let issue = get_issue(project_path, &id).await.unwrap();
+    assert_eq!(issue.metadata.status, "open");
+
+    move_to(project_path, &id, "wontfix", Some("Works as intended"))
+        .await
+        .expect("Should accept transition with comment");
+
+    let comments = list_comments(project_path, &id).await.expect("Should list comments");
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0].body, "Works as intended");
+    assert_eq!(comments[0].author, "alice");
+}
+
+#[tokio::test]
+async fn test_terminal_state_cannot_be_left() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    set_workflow(project_path, true).await;
+
+    let id = create_titled_issue(project_path, "Issue").await;
+    move_to(project_path, &id, "wontfix", Some("Duplicate"))
+        .await
+        .expect("Should move to wontfix");
+
+    let result = move_to(project_path, &id, "open", None).await;
+    assert!(matches!(
+        result,
+        Err(IssueCrudError::WorkflowError(WorkflowError::TerminalState(_)))
+    ));
+
+    // Non-status updates are unaffected
+    update_issue(
+        project_path,
+        &id,
+        UpdateIssueOptions {
+            title: Some("Renamed".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("Should update title");
+}
+
+#[tokio::test]
+async fn test_strict_workflow_rejects_unknown_initial_status() {
+    let temp_dir = create_test_dir();
+    let project_path = temp_dir.path();
+    init_centy_project(project_path).await;
+    set_workflow(project_path, true).await;
+
+    let result = create_issue(
+        project_path,
+        CreateIssueOptions {
+            title: "Issue".to_string(),
+            status: Some("made-up".to_string()),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(IssueError::WorkflowError(WorkflowError::UnknownStatus(_)))
+    ));
+}